@@ -1,7 +1,7 @@
 // Allocation Strategies Contract
 #![no_std]
 
-use shared_utils::{Pausable, RateLimiter};
+use shared_utils::{AccessControl, Pausable, RateLimiter};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map,
     Symbol, Vec,
@@ -624,15 +624,50 @@ impl AllocationStrategiesContract {
         read_version(&env)
     }
 
-    /// Update admin (admin-only).
+    /// Update admin (admin-only). Deprecated: routed through `propose_admin`, so the change
+    /// only takes effect once `new_admin` calls `accept_admin`.
     pub fn set_admin(env: Env, caller: Address, new_admin: Address) -> Result<(), Error> {
+        Self::propose_admin(env, caller, new_admin)
+    }
+
+    /// Nominate a new admin (admin-only). Takes effect once the nominee calls `accept_admin`.
+    pub fn propose_admin(env: Env, caller: Address, new_admin: Address) -> Result<(), Error> {
         caller.require_auth();
         Self::require_initialized(&env)?;
         Self::require_admin(&env, &caller)?;
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        AccessControl::propose_admin(&env, &caller, &new_admin);
         Ok(())
     }
 
+    /// Accept a pending admin nomination. Caller must be the nominee.
+    pub fn accept_admin(env: Env, caller: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if !AccessControl::accept_admin(&env, &caller, &admin) {
+            return Err(Error::Unauthorized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &caller);
+        Ok(())
+    }
+
+    /// Withdraw a pending admin nomination (admin-only).
+    pub fn cancel_admin_proposal(env: Env, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_admin(&env, &caller)?;
+        AccessControl::cancel_admin_proposal(&env, &caller);
+        Ok(())
+    }
+
+    /// Get the pending admin nominee, if any.
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        AccessControl::get_pending_admin(&env)
+    }
+
     /// Upgrade contract WASM (admin-only).
     pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
         caller.require_auth();
@@ -57,7 +57,7 @@ fn test_register_pool() {
     assert_eq!(pool.risk_level, RiskLevel::Low);
     assert_eq!(pool.apy, 500);
     assert_eq!(pool.max_capacity, 1_000_000_000);
-    assert_eq!(pool.active, true);
+    assert!(pool.active);
     assert_eq!(pool.total_liquidity, 0);
 }
 
@@ -468,3 +468,23 @@ fn test_no_active_pools_fails() {
     let user = Address::generate(&env);
     client.allocate(&user, &1, &100_000, &Strategy::Safe);
 }
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (admin, _, client) = create_contract(&env);
+    let new_admin = Address::generate(&env);
+
+    client.propose_admin(&admin, &new_admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert!(client.try_accept_admin(&Address::generate(&env)).is_err());
+
+    client.accept_admin(&new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+    // Old admin lost its rights, new admin gained them
+    assert!(client
+        .try_register_pool(&admin, &0, &RiskLevel::Low, &500, &1_000_000)
+        .is_err());
+    client.register_pool(&new_admin, &0, &RiskLevel::Low, &500, &1_000_000);
+}
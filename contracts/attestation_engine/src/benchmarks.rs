@@ -82,13 +82,18 @@ fn store_mock_commitment(
             early_exit_penalty: 10,
             min_fee_threshold: 0,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
         },
         amount: 1_000,
+        gross_amount: 1_000,
         asset_address: Address::generate(e),
         created_at: 0,
         expires_at: 86_400,
         current_value: 1_000,
-        status: String::from_str(e, "active"),
+        status: CommitmentStatus::Active,
+        allocated_amount: 0,
+        yield_earned: 0,
+        is_basket: false,
     };
 
     e.as_contract(core_contract_id, || {
@@ -1,5 +1,7 @@
 #![no_std]
-use shared_utils::{BatchError, BatchMode, BatchProcessor, BatchResultVoid, Pausable, RateLimiter};
+use shared_utils::{
+    AccessControl, BatchError, BatchMode, BatchProcessor, BatchResultVoid, Pausable, RateLimiter,
+};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env,
     IntoVal, Map, String, Symbol, TryIntoVal, Val, Vec,
@@ -118,6 +120,16 @@ pub struct CommitmentRules {
     pub early_exit_penalty: u32,
     pub min_fee_threshold: i128,
     pub grace_period_days: u32,
+    pub transfer_policy: TransferPolicy,
+}
+
+/// Mirror of `commitment_core::TransferPolicy` so cross-contract reads decode typed.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransferPolicy {
+    Free,
+    LockedUntilExpiry,
+    Soulbound,
 }
 
 #[contracttype]
@@ -128,11 +140,25 @@ pub struct Commitment {
     pub nft_token_id: u32,
     pub rules: CommitmentRules,
     pub amount: i128,
+    pub gross_amount: i128,
     pub asset_address: Address,
     pub created_at: u64,
     pub expires_at: u64,
     pub current_value: i128,
-    pub status: String, // "active", "settled", "violated", "early_exit"
+    pub status: CommitmentStatus,
+    pub allocated_amount: i128,
+    pub yield_earned: i128,
+    pub is_basket: bool,
+}
+
+/// Mirror of `commitment_core::CommitmentStatus` so cross-contract reads decode typed.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommitmentStatus {
+    Active,
+    Settled,
+    Violated,
+    EarlyExit,
 }
 
 #[contracttype]
@@ -348,13 +374,45 @@ impl AttestationEngineContract {
         read_version(&e)
     }
 
-    /// Update admin (admin-only).
+    /// Update admin (admin-only). Deprecated: routed through `propose_admin`, so the change
+    /// only takes effect once `new_admin` calls `accept_admin`.
     pub fn set_admin(e: Env, caller: Address, new_admin: Address) -> Result<(), AttestationError> {
+        Self::propose_admin(e, caller, new_admin)
+    }
+
+    /// Nominate a new admin (admin-only). Takes effect once the nominee calls `accept_admin`.
+    pub fn propose_admin(
+        e: Env,
+        caller: Address,
+        new_admin: Address,
+    ) -> Result<(), AttestationError> {
         require_admin(&e, &caller)?;
-        e.storage().instance().set(&DataKey::Admin, &new_admin);
+        AccessControl::propose_admin(&e, &caller, &new_admin);
         Ok(())
     }
 
+    /// Accept a pending admin nomination. Caller must be the nominee.
+    pub fn accept_admin(e: Env, caller: Address) -> Result<(), AttestationError> {
+        let admin = Self::get_admin(e.clone())?;
+        if !AccessControl::accept_admin(&e, &caller, &admin) {
+            return Err(AttestationError::Unauthorized);
+        }
+        e.storage().instance().set(&DataKey::Admin, &caller);
+        Ok(())
+    }
+
+    /// Withdraw a pending admin nomination (admin-only).
+    pub fn cancel_admin_proposal(e: Env, caller: Address) -> Result<(), AttestationError> {
+        require_admin(&e, &caller)?;
+        AccessControl::cancel_admin_proposal(&e, &caller);
+        Ok(())
+    }
+
+    /// Get the pending admin nominee, if any.
+    pub fn get_pending_admin(e: Env) -> Option<Address> {
+        AccessControl::get_pending_admin(&e)
+    }
+
     /// Upgrade contract WASM (admin-only).
     pub fn upgrade(
         e: Env,
@@ -472,10 +530,7 @@ impl AttestationEngineContract {
             args,
         );
 
-        match result {
-            Ok(Ok(_)) => true,
-            _ => false,
-        }
+        matches!(result, Ok(Ok(_)))
     }
 
     // ========================================================================
@@ -589,9 +644,8 @@ impl AttestationEngineContract {
         }
 
         // OPTIMIZATION: Single pass parsing with early exit on invalid char
-        for i in start_idx..len as usize {
-            let b = buf[i];
-            if b < b'0' || b > b'9' {
+        for &b in buf.iter().take(len as usize).skip(start_idx) {
+            if !b.is_ascii_digit() {
                 return None; // Invalid character - early exit
             }
             result = result.checked_mul(10)?;
@@ -655,7 +709,7 @@ impl AttestationEngineContract {
         RateLimiter::check(&e, &caller, &fn_symbol);
 
         // 4. Validate commitment_id is not empty
-        if commitment_id.len() == 0 {
+        if commitment_id.is_empty() {
             e.storage().instance().remove(&DataKey::ReentrancyGuard);
             return Err(AttestationError::InvalidCommitmentId);
         }
@@ -1117,7 +1171,7 @@ impl AttestationEngineContract {
 
         if expires_at > created_at {
             let total_duration = expires_at.checked_sub(created_at).unwrap_or(1);
-            let elapsed = current_time.checked_sub(created_at).unwrap_or(0);
+            let elapsed = current_time.saturating_sub(created_at);
 
             // Check if we're on track (not too far behind or ahead)
             // Simplified: if elapsed is within reasonable bounds of expected progress
@@ -1134,11 +1188,7 @@ impl AttestationEngineContract {
         }
 
         // Clamp between 0 and 100
-        if score < 0 {
-            score = 0;
-        } else if score > 100 {
-            score = 100;
-        }
+        score = score.clamp(0, 100);
 
         // Emit compliance score update event
         e.events().publish(
@@ -1286,7 +1336,7 @@ impl AttestationEngineContract {
             let params = params_list.get(i).unwrap();
 
             // Validate commitment_id
-            if params.commitment_id.len() == 0 {
+            if params.commitment_id.is_empty() {
                 if mode == BatchMode::Atomic {
                     e.storage().instance().remove(&DataKey::ReentrancyGuard);
                     errors.push_back(BatchError {
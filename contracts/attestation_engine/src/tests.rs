@@ -43,3 +43,24 @@ fn test_initialize_twice_fails() {
     assert_eq!(second, Err(AttestationError::AlreadyInitialized));
 }
 
+#[test]
+fn test_two_step_admin_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, AttestationEngineContract);
+    let client = AttestationEngineContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    let new_admin = Address::generate(&e);
+    client.initialize(&admin, &Address::generate(&e));
+
+    client.propose_admin(&admin, &new_admin);
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(
+        client.try_accept_admin(&Address::generate(&e)),
+        Err(Ok(AttestationError::Unauthorized))
+    );
+
+    client.accept_admin(&new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+}
@@ -68,13 +68,18 @@ fn build_benchmark_commitment(
             commitment_type: String::from_str(e, "balanced"),
             early_exit_penalty: 10,
             min_fee_threshold: 1000,
+            transfer_policy: TransferPolicy::Free,
         },
         amount,
+        gross_amount: amount,
         asset_address: Address::generate(e),
         created_at: e.ledger().timestamp(),
         expires_at: e.ledger().timestamp() + (30 * 86400),
         current_value: amount,
-        status: String::from_str(e, "active"),
+        status: CommitmentStatus::Active,
+        allocated_amount: 0,
+        yield_earned: 0,
+        is_basket: false,
     }
 }
 
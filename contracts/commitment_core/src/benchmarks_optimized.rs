@@ -40,6 +40,7 @@ fn benchmark_create_commitment_storage_reads() {
         early_exit_penalty: 10,
         min_fee_threshold: 1000,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
     
     // Measure CPU and memory before
@@ -75,6 +76,7 @@ fn benchmark_batch_counter_updates() {
         early_exit_penalty: 10,
         min_fee_threshold: 1000,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
     
     // Create multiple commitments to test counter updates
@@ -132,6 +134,7 @@ fn benchmark_check_violations() {
         early_exit_penalty: 10,
         min_fee_threshold: 1000,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
     
     let commitment_id = client.create_commitment(&owner, &10000, &asset, &rules);
@@ -218,6 +221,7 @@ fn benchmark_settle_function() {
         early_exit_penalty: 10,
         min_fee_threshold: 1000,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
     
     let commitment_id = client.create_commitment(&owner, &10000, &asset, &rules);
@@ -232,7 +236,7 @@ fn benchmark_settle_function() {
     let cpu_before = env.budget().cpu_instruction_cost();
     let mem_before = env.budget().memory_bytes_cost();
     
-    client.settle(&commitment_id);
+    client.settle(&commitment_id, &owner);
     
     let cpu_after = env.budget().cpu_instruction_cost();
     let mem_after = env.budget().memory_bytes_cost();
@@ -258,6 +262,7 @@ fn benchmark_memory_usage() {
         early_exit_penalty: 10,
         min_fee_threshold: 1000,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
     
     env.budget().reset_unlimited();
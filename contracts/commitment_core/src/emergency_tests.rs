@@ -1,7 +1,12 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use crate::tests::setup_funded_commitment;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    Address, Env, String,
+};
 
 #[test]
 fn test_emergency_mode_toggle() {
@@ -47,6 +52,7 @@ fn test_create_commitment_forbidden_in_emergency() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     // This should panic because of emergency mode
@@ -55,7 +61,7 @@ fn test_create_commitment_forbidden_in_emergency() {
 
 #[test]
 #[should_panic(expected = "Action only allowed in emergency mode")]
-fn test_emergency_withdraw_forbidden_in_normal_mode() {
+fn test_emergency_sweep_forbidden_in_normal_mode() {
     let e = Env::default();
     e.mock_all_auths();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
@@ -66,9 +72,10 @@ fn test_emergency_withdraw_forbidden_in_normal_mode() {
     let asset = Address::generate(&e);
 
     client.initialize(&admin, &nft_contract);
+    client.set_recovery_address(&admin, &to);
 
     // Normal mode, should panic
-    client.emergency_withdraw(&admin, &asset, &to, &1000);
+    client.emergency_sweep(&admin, &asset, &1000);
 }
 
 #[test]
@@ -87,3 +94,101 @@ fn test_set_emergency_mode_unauthorized() {
     // Using attacker address should fail the require_admin check
     client.set_emergency_mode(&attacker, &true);
 }
+
+#[test]
+fn test_emergency_exit_returns_full_value_without_penalty() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, owner, asset_address) =
+        setup_funded_commitment(&e, "emg_exit", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "emg_exit");
+
+    client.set_emergency_mode(&admin, &true);
+    client.emergency_exit(&commitment_id, &owner);
+
+    let commitment = client.get_commitment(&commitment_id);
+    assert_eq!(commitment.status, CommitmentStatus::EarlyExit);
+    assert_eq!(commitment.current_value, 0);
+    assert_eq!(client.get_total_value_locked(), 0);
+    assert_eq!(client.get_collected_penalties(&asset_address), 0);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&owner), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Commitment has funds allocated to pools")]
+fn test_emergency_exit_with_allocated_funds_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, owner, _asset_address) =
+        setup_funded_commitment(&e, "emg_exit_alloc", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "emg_exit_alloc");
+
+    client.allocate(&commitment_id, &Address::generate(&e), &400);
+    client.set_emergency_mode(&admin, &true);
+    client.emergency_exit(&commitment_id, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Action only allowed in emergency mode")]
+fn test_emergency_exit_forbidden_in_normal_mode() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, owner, _asset_address) =
+        setup_funded_commitment(&e, "emg_exit_normal", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.emergency_exit(&String::from_str(&e, "emg_exit_normal"), &owner);
+}
+
+#[test]
+#[should_panic(expected = "Action not allowed in emergency mode")]
+fn test_top_up_forbidden_in_emergency() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, owner, asset_address) =
+        setup_funded_commitment(&e, "emg_top_up", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    StellarAssetClient::new(&e, &asset_address).mint(&owner, &500);
+
+    client.set_emergency_mode(&admin, &true);
+    client.top_up(&String::from_str(&e, "emg_top_up"), &owner, &500);
+}
+
+#[test]
+#[should_panic(expected = "Emergency sweep timelock has not elapsed")]
+fn test_emergency_sweep_before_timelock_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, _owner, asset_address) =
+        setup_funded_commitment(&e, "emg_sweep_early", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.set_recovery_address(&admin, &Address::generate(&e));
+
+    client.set_emergency_mode(&admin, &true);
+    e.ledger()
+        .with_mut(|l| l.timestamp += EMERGENCY_SWEEP_DELAY - 1);
+    client.emergency_sweep(&admin, &asset_address, &1000);
+}
+
+#[test]
+fn test_emergency_sweep_after_timelock() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, _owner, asset_address) =
+        setup_funded_commitment(&e, "emg_sweep", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let recovery = Address::generate(&e);
+    client.set_recovery_address(&admin, &recovery);
+
+    client.set_emergency_mode(&admin, &true);
+    e.ledger()
+        .with_mut(|l| l.timestamp += EMERGENCY_SWEEP_DELAY);
+    client.emergency_sweep(&admin, &asset_address, &1000);
+
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&recovery), 1000);
+    assert_eq!(token.balance(&contract_id), 0);
+}
@@ -1,9 +1,12 @@
 #![no_std]
 
-use shared_utils::{emit_error_event, Pausable, RateLimiter, SafeMath, TimeUtils, Validation};
+use shared_utils::{
+    emit_error_event, fees, AccessControl, BatchError, BatchResultVoid, EmergencyControl, Pausable,
+    RateLimiter, SafeMath, TimeUtils, Validation,
+};
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, log, symbol_short, token, Address, Env,
-    IntoVal, String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, log, symbol_short, token, Address, BytesN,
+    Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
 #[contracterror]
@@ -27,6 +30,56 @@ pub enum CommitmentError {
     NotExpired = 15,
     /// Duration would cause expires_at to overflow u64
     ExpirationOverflow = 16,
+    /// Treasury recipient not set; cannot withdraw penalties
+    TreasuryRecipientNotSet = 17,
+    /// Requested withdrawal exceeds collected penalties for the asset
+    InsufficientPenalties = 18,
+    /// Commitment is within its post-expiry grace window; only the owner may settle
+    GracePeriodActive = 19,
+    /// Fee in basis points is above the allowed maximum
+    InvalidFeeBps = 20,
+    /// refresh_value_from_oracle called before a price oracle was configured
+    OracleNotSet = 21,
+    /// Oracle price is older than the oracle's max staleness
+    StalePrice = 22,
+    /// Oracle has no usable price for the asset
+    PriceUnavailable = 23,
+    /// migrate called with a from_version that does not match storage
+    InvalidVersion = 24,
+    /// Storage is already at CURRENT_VERSION
+    AlreadyMigrated = 25,
+    /// upgrade called with an all-zero WASM hash
+    InvalidWasmHash = 26,
+    /// emergency_sweep called before EMERGENCY_SWEEP_DELAY elapsed
+    EmergencyTimelockActive = 27,
+    /// emergency_sweep called before a recovery address was configured
+    RecoveryAddressNotSet = 28,
+    /// Fee recipient not set; cannot withdraw creation fees
+    FeeRecipientNotSet = 29,
+    /// Requested withdrawal exceeds collected creation fees for the asset
+    InsufficientFees = 30,
+    /// Commitment still has principal allocated to pools
+    FundsAllocated = 31,
+    /// Deallocation exceeds the principal allocated to the pool
+    InsufficientAllocation = 32,
+    /// Basket must hold 2..=MAX_BASKET_ASSETS distinct assets
+    InvalidBasket = 33,
+    /// Operation only applies to single-asset commitments
+    MultiAssetNotSupported = 34,
+    /// Operator approval expiry is not in the future
+    InvalidExpiry = 35,
+    /// A call into the NFT contract failed
+    NftCallFailed = 36,
+    /// Violated commitment has already been paid out by `resolve_violation`
+    AlreadyResolved = 37,
+    /// Template ranges are inconsistent or produce invalid rules
+    InvalidTemplate = 38,
+    TemplateNotFound = 39,
+    TemplateDisabled = 40,
+    /// Asset is not accepted for this commitment
+    AssetNotAllowed = 41,
+    /// Commitment would push an asset past its TVL or per-owner cap
+    AssetCapExceeded = 42,
 }
 
 impl CommitmentError {
@@ -51,6 +104,38 @@ impl CommitmentError {
             CommitmentError::ExpirationOverflow => {
                 "Duration would cause expiration timestamp overflow"
             }
+            CommitmentError::TreasuryRecipientNotSet => "Treasury recipient not set",
+            CommitmentError::InsufficientPenalties => {
+                "Insufficient collected penalties for withdrawal"
+            }
+            CommitmentError::GracePeriodActive => "Grace period active: only owner may settle",
+            CommitmentError::InvalidFeeBps => "Invalid fee: basis points out of range",
+            CommitmentError::OracleNotSet => "Price oracle not configured",
+            CommitmentError::StalePrice => "Oracle price is stale",
+            CommitmentError::PriceUnavailable => "Oracle price unavailable or invalid",
+            CommitmentError::InvalidVersion => "Invalid version for migration",
+            CommitmentError::AlreadyMigrated => "Contract storage already migrated",
+            CommitmentError::InvalidWasmHash => "Invalid WASM hash",
+            CommitmentError::EmergencyTimelockActive => "Emergency sweep timelock has not elapsed",
+            CommitmentError::RecoveryAddressNotSet => "Recovery address not set",
+            CommitmentError::FeeRecipientNotSet => "Fee recipient not set",
+            CommitmentError::InsufficientFees => "Insufficient collected fees for withdrawal",
+            CommitmentError::FundsAllocated => "Commitment has funds allocated to pools",
+            CommitmentError::InsufficientAllocation => {
+                "Insufficient allocation in pool for deallocation"
+            }
+            CommitmentError::InvalidBasket => "Invalid basket: need 2 or more distinct assets",
+            CommitmentError::MultiAssetNotSupported => {
+                "Operation not supported for multi-asset commitments"
+            }
+            CommitmentError::InvalidExpiry => "Invalid expiry: must be in the future",
+            CommitmentError::NftCallFailed => "NFT contract call failed",
+            CommitmentError::AlreadyResolved => "Violation already resolved",
+            CommitmentError::InvalidTemplate => "Invalid template",
+            CommitmentError::TemplateNotFound => "Template not found",
+            CommitmentError::TemplateDisabled => "Template is disabled",
+            CommitmentError::AssetNotAllowed => "Asset not allowed",
+            CommitmentError::AssetCapExceeded => "Asset cap exceeded",
         }
     }
 }
@@ -82,11 +167,93 @@ pub struct CommitmentRules {
     pub early_exit_penalty: u32,
     pub min_fee_threshold: i128,
     pub grace_period_days: u32,
+    /// Whether the commitment's NFT may change hands before maturity
+    pub transfer_policy: TransferPolicy,
+}
+
+/// Transferability of a commitment NFT, fixed at mint.
+/// Mirrors `commitment_nft::TransferPolicy`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransferPolicy {
+    /// Transferable at any time
+    Free,
+    /// Not transferable while active and before `expires_at`
+    LockedUntilExpiry,
+    /// Never transferable
+    Soulbound,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Commitment {
+    pub commitment_id: String,
+    pub owner: Address,
+    pub nft_token_id: u32,
+    pub rules: CommitmentRules,
+    /// Principal locked after the creation fee
+    pub amount: i128,
+    /// Amount the owner transferred at creation, before the creation fee
+    pub gross_amount: i128,
+    pub asset_address: Address,
+    pub created_at: u64,
+    pub expires_at: u64,
+    /// Value held by this contract; excludes principal currently allocated to pools
+    pub current_value: i128,
+    pub status: CommitmentStatus,
+    /// Principal currently allocated to pools and not yet deallocated
+    pub allocated_amount: i128,
+    /// Realized yield credited by `record_return`, kept apart from principal
+    pub yield_earned: i128,
+    /// Multi-asset commitment. Its tokens are listed by `get_holdings` and its
+    /// aggregate value is the `OracleValuation`; `amount`, `gross_amount` and
+    /// `current_value` stay 0 and `asset_address` is only the first holding.
+    pub is_basket: bool,
+}
+
+/// Lifecycle state of a commitment. Transitions are validated by
+/// `CommitmentStatus::can_transition_to`; terminal states never change again.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommitmentStatus {
+    Active,
+    Settled,
+    Violated,
+    EarlyExit,
+}
+
+impl CommitmentStatus {
+    /// Allowed transitions: `Active` -> `Settled` | `Violated` | `EarlyExit`.
+    pub fn can_transition_to(self, next: CommitmentStatus) -> bool {
+        matches!(
+            (self, next),
+            (
+                CommitmentStatus::Active,
+                CommitmentStatus::Settled
+                    | CommitmentStatus::Violated
+                    | CommitmentStatus::EarlyExit
+            )
+        )
+    }
+
+    /// Parse a pre-enum status string ("active", "settled", "violated", "early_exit").
+    pub fn from_legacy(e: &Env, status: &String) -> Option<CommitmentStatus> {
+        [
+            ("active", CommitmentStatus::Active),
+            ("settled", CommitmentStatus::Settled),
+            ("violated", CommitmentStatus::Violated),
+            ("early_exit", CommitmentStatus::EarlyExit),
+        ]
+        .into_iter()
+        .find(|(label, _)| *status == String::from_str(e, label))
+        .map(|(_, parsed)| parsed)
+    }
+}
+
+/// Commitment layout written before `status` became `CommitmentStatus`; read only by `migrate`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyCommitment {
     pub commitment_id: String,
     pub owner: Address,
     pub nft_token_id: u32,
@@ -96,19 +263,234 @@ pub struct Commitment {
     pub created_at: u64,
     pub expires_at: u64,
     pub current_value: i128,
-    pub status: String, // "active", "settled", "violated", "early_exit"
+    pub status: String,
+}
+
+/// What an operator may do on an owner's behalf. Exits always pay the owner.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OperatorScope {
+    /// `create_commitment_for` with the owner as beneficiary
+    Create,
+    /// `top_up`, funded by the operator
+    TopUp,
+    /// `early_exit` and `partial_exit`
+    Exit,
+    /// Every scope above
+    All,
+}
+
+impl OperatorScope {
+    pub fn covers(&self, needed: OperatorScope) -> bool {
+        *self == OperatorScope::All || *self == needed
+    }
+}
+
+/// An owner's grant to an operator, valid until `expires_at` (ledger timestamp).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorApproval {
+    pub scope: OperatorScope,
+    pub expires_at: u64,
+}
+
+/// An admin-managed commitment product. Owners pick `duration_days` and
+/// `max_loss_percent` within the template's ranges; every other rule is fixed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitmentTemplate {
+    pub name: String,
+    pub commitment_type: String,
+    pub min_duration_days: u32,
+    pub max_duration_days: u32,
+    pub min_loss_percent: u32,
+    pub max_loss_percent: u32,
+    pub early_exit_penalty: u32,
+    pub min_fee_threshold: i128,
+    pub grace_period_days: u32,
+    pub transfer_policy: TransferPolicy,
+    pub min_amount: i128,
+    /// Assets the template accepts; empty accepts any asset
+    pub allowed_assets: Vec<Address>,
+    pub enabled: bool,
+}
+
+/// Onboarding limits for one asset. Caps count principal in active commitments,
+/// in the asset's own units; a cap of 0 is unlimited.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetConfig {
+    pub enabled: bool,
+    /// Smallest principal a new commitment may lock
+    pub min_amount: i128,
+    /// Most principal locked across all owners
+    pub tvl_cap: i128,
+    /// Most principal locked by any single owner
+    pub owner_cap: i128,
+}
+
+/// Where a commitment sits relative to its post-expiry grace window.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GraceState {
+    /// Before `expires_at`
+    NotExpired,
+    /// Between `expires_at` and `expires_at + grace_period_days`; only the owner may settle
+    InGracePeriod,
+    /// Grace window has elapsed; any keeper may settle on the owner's behalf
+    GraceElapsed,
+}
+
+/// Value flows for one asset, in its own units. Basket commitments are tracked
+/// separately in the price oracle's quote units.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AssetMetrics {
+    /// Current value of active and unresolved violated commitments
+    pub value_locked: i128,
+    /// Cumulative value closed out by `settle` and `rollover`
+    pub settled_volume: i128,
+    /// Cumulative value withdrawn by early, partial and emergency exits and `resolve_violation`
+    pub exited_volume: i128,
+}
+
+/// Protocol-wide snapshot returned by `get_protocol_metrics`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolMetrics {
+    pub total_commitments: u64,
+    pub active_count: u32,
+    pub settled_count: u32,
+    pub violated_count: u32,
+    pub early_exit_count: u32,
+    /// Every asset that has backed a commitment, in first-seen order
+    pub assets: Vec<(Address, AssetMetrics)>,
+    pub basket: AssetMetrics,
 }
 
+/// Detailed violation information returned by `get_violation_details`.
+/// Only a loss breach is a violation; expiry is reported for settlement.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ViolationDetails {
+    pub has_violations: bool,
+    pub loss_violated: bool,
+    pub expired: bool,
+    pub loss_percent: i128,
+    pub time_remaining: u64,
+    pub grace_state: GraceState,
+    pub grace_ends_at: u64,
+}
+
+/// Price feed as returned by `price_oracle::get_price_valid`.
+/// Mirrors `price_oracle::PriceData` field-for-field so the cross-contract result decodes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OraclePrice {
+    pub price: i128,
+    pub updated_at: u64,
+    pub decimals: u32,
+}
+
+/// Oracle valuation of a commitment in the price oracle's quote units, kept apart from
+/// `current_value` so payouts never read it. Only the loss-limit check does.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleValuation {
+    /// Valuation the loss is measured against, recorded at creation or first refresh
+    pub entry_value: i128,
+    /// Valuation at the last `refresh_value_from_oracle`
+    pub value: i128,
+    pub updated_at: u64,
+}
+
+/// `price_oracle::OracleError::StalePrice`
+const ORACLE_STALE_PRICE: u32 = 6;
+
+/// Delay between enabling emergency mode and the first `emergency_sweep` (72 hours),
+/// giving owners a window to pull their funds with `emergency_exit` first.
+pub const EMERGENCY_SWEEP_DELAY: u64 = 72 * 60 * 60;
+
+/// Storage layout version. Deployments that predate versioning read as 0.
+/// v1: typed `CommitmentStatus`; v2: commitments and owner indexes in persistent storage;
+/// v3: per-status secondary index; v4: `Commitment::gross_amount`;
+/// v5: `Commitment::allocated_amount` and `Commitment::yield_earned`;
+/// v6: per-asset principal counters behind `AssetConfig` caps; v7: per-asset `AssetMetrics`.
+/// v8: `CommitmentRules::transfer_policy` and `CommitmentTemplate::transfer_policy`.
+/// v9: `Commitment::is_basket`; basket values move to `OracleValuation`.
+pub const CURRENT_VERSION: u32 = 9;
+
+/// Upper bound on records returned by the `list_commitments*` queries.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Ledgers per day at a ~5s close time.
+const DAY_IN_LEDGERS: u32 = 17_280;
+/// Persistent commitment entries are extended to live this many ledgers.
+pub const COMMITMENT_TTL_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+/// Extend once the remaining TTL drops below this many ledgers.
+pub const COMMITMENT_TTL_THRESHOLD: u32 = COMMITMENT_TTL_EXTEND_TO - DAY_IN_LEDGERS;
+
+/// Upper bound for the keeper settlement fee (5%).
+pub const MAX_KEEPER_FEE_BPS: u32 = 500;
+
+/// Upper bound for the creation fee (10%).
+pub const MAX_CREATION_FEE_BPS: u32 = 1_000;
+
+/// Upper bound for the performance fee on realized yield (20%).
+pub const MAX_PERFORMANCE_FEE_BPS: u32 = 2_000;
+
+/// Upper bound for the violation penalty (50%).
+pub const MAX_VIOLATION_PENALTY_BPS: u32 = 5_000;
+
+/// Most assets a multi-asset commitment may hold.
+pub const MAX_BASKET_ASSETS: u32 = 10;
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
     NftContract,
-    Commitment(String),        // commitment_id -> Commitment
-    OwnerCommitments(Address), // owner -> Vec<commitment_id>
-    TotalCommitments,          // counter
-    ReentrancyGuard,           // reentrancy protection flag
-    TotalValueLocked,          // aggregate value locked across active commitments
+    Commitment(String),                 // commitment_id -> Commitment (persistent)
+    OwnerCommitments(Address),          // owner -> Vec<commitment_id> (persistent)
+    TotalCommitments,                   // counter
+    ReentrancyGuard,                    // reentrancy protection flag
+    TotalValueLocked,                   // aggregate value locked across active commitments
+    TreasuryRecipient,                  // protocol treasury for withdrawn penalties
+    CollectedPenalties(Address),        // asset -> early-exit penalties held by the contract
+    KeeperFeeBps,                       // fee paid to third-party settlers after the grace period
+    PriceOracle,                        // price_oracle contract used by refresh_value_from_oracle
+    ValuationUpdater(Address),          // address -> allowed to call update_value
+    Version,                            // storage layout version
+    EmergencyActivatedAt,               // timestamp emergency mode was last enabled
+    RecoveryAddress,                    // destination for emergency_sweep
+    CreationFeeBps,                     // fee taken from create_commitment amounts
+    FeeRecipient,                       // destination for withdraw_fees
+    CollectedFees(Address),             // asset -> creation fees held by the contract
+    Successor(String),                  // commitment_id -> rolled-into commitment_id (persistent)
+    Predecessor(String),                // commitment_id -> rolled-from commitment_id (persistent)
+    StatusCount(CommitmentStatus),      // number of commitments currently in a status
+    StatusEntry(CommitmentStatus, u32), // (status, slot) -> commitment_id (persistent)
+    StatusPosition(String),             // commitment_id -> slot in its status list (persistent)
+    PerformanceFeeBps,                  // fee taken from realized yield on every payout
+    Allocation(String, Address),        // (commitment_id, pool) -> allocated principal (persistent)
+    Holdings(String),                   // commitment_id -> basket Vec<(asset, amount)> (persistent)
+    Operator(Address, Address),         // (owner, operator) -> OperatorApproval (persistent)
+    SweepReward,                        // (asset, amount) paid per commitment marked by a sweep
+    KeeperBudget(Address),              // asset -> funds set aside for sweep rewards
+    ViolationPenaltyBps,                // penalty withheld by resolve_violation on loss breaches
+    ViolationResolved(String),          // commitment_id -> resolution timestamp (persistent)
+    TemplateCount,                      // number of templates; next template ID
+    Template(u32),                      // template_id -> CommitmentTemplate (persistent)
+    CommitmentTemplate(String),         // commitment_id -> source template_id (persistent)
+    AssetConfig(Address),               // asset -> AssetConfig
+    AssetAllowlistEnforced,             // reject assets without an AssetConfig
+    AssetLocked(Address),               // asset -> principal in active commitments
+    OwnerAssetLocked(Address, Address), // (owner, asset) -> principal (persistent)
+    AssetMetrics(Address),              // asset -> AssetMetrics
+    BasketMetrics,                      // AssetMetrics for baskets, in oracle quote units
+    MetricAssets,                       // Vec<asset> with AssetMetrics, first-seen order
+    OracleValuation(String),            // commitment_id -> OracleValuation (persistent)
+    AllocationPools(String),            // commitment_id -> Vec<pool> with allocations (persistent)
 }
 
 /// Transfer assets from owner to contract
@@ -132,856 +514,4099 @@ fn call_nft_mint(
     nft_contract: &Address,
     owner: &Address,
     commitment_id: &String,
-    duration_days: u32,
-    max_loss_percent: u32,
-    commitment_type: &String,
+    rules: &CommitmentRules,
     initial_amount: i128,
     asset_address: &Address,
-    early_exit_penalty: u32,
 ) -> u32 {
     let mut args = Vec::new(e);
+    args.push_back(e.current_contract_address().into_val(e));
     args.push_back(owner.clone().into_val(e));
     args.push_back(commitment_id.clone().into_val(e));
-    args.push_back(duration_days.into_val(e));
-    args.push_back(max_loss_percent.into_val(e));
-    args.push_back(commitment_type.clone().into_val(e));
+    args.push_back(rules.duration_days.into_val(e));
+    args.push_back(rules.max_loss_percent.into_val(e));
+    args.push_back(rules.commitment_type.clone().into_val(e));
     args.push_back(initial_amount.into_val(e));
     args.push_back(asset_address.clone().into_val(e));
-    args.push_back(early_exit_penalty.into_val(e));
+    args.push_back(rules.early_exit_penalty.into_val(e));
+    args.push_back(rules.transfer_policy.into_val(e));
 
     // In Soroban, contract calls return the value directly
     // Failures cause the entire transaction to fail
     e.invoke_contract::<u32>(nft_contract, &Symbol::new(e, "mint"), args)
 }
 
-// Storage helpers
-fn read_commitment(e: &Env, commitment_id: &String) -> Option<Commitment> {
+/// Mint the NFT for a basket commitment. Basket NFTs carry no amount or asset;
+/// the holdings are read from `get_holdings`.
+fn call_nft_mint_basket(
+    e: &Env,
+    nft_contract: &Address,
+    owner: &Address,
+    commitment_id: &String,
+    rules: &CommitmentRules,
+) -> u32 {
+    let mut args = Vec::new(e);
+    args.push_back(e.current_contract_address().into_val(e));
+    args.push_back(owner.clone().into_val(e));
+    args.push_back(commitment_id.clone().into_val(e));
+    args.push_back(rules.duration_days.into_val(e));
+    args.push_back(rules.max_loss_percent.into_val(e));
+    args.push_back(rules.commitment_type.clone().into_val(e));
+    args.push_back(rules.early_exit_penalty.into_val(e));
+    args.push_back(rules.transfer_policy.into_val(e));
+    e.invoke_contract::<u32>(nft_contract, &Symbol::new(e, "mint_basket"), args)
+}
+
+/// Record a new Active commitment under the next counter-derived ID, together with
+/// its owner and status index entries and the commitment counter.
+/// `nft_token_id` is left at 0 for the caller to fill in after minting; the caller
+/// adds the value to TVL once any basket holdings are written.
+#[allow(clippy::too_many_arguments)]
+fn store_new_commitment(
+    e: &Env,
+    owner: &Address,
+    amount: i128,
+    gross_amount: i128,
+    asset_address: &Address,
+    rules: &CommitmentRules,
+    expires_at: u64,
+    context: &str,
+) -> Commitment {
+    let current_total = e
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::TotalCommitments)
+        .unwrap_or(0);
+
+    // Generate unique commitment ID using counter
+    let commitment_id = CommitmentCoreContract::generate_commitment_id(e, current_total);
+
+    // CHECKS: Validate commitment doesn't already exist
+    if has_commitment(e, &commitment_id) {
+        set_reentrancy_guard(e, false);
+        fail(e, CommitmentError::InvalidStatus, context);
+    }
+
+    let commitment = Commitment {
+        commitment_id: commitment_id.clone(),
+        owner: owner.clone(),
+        nft_token_id: 0, // Will be set after NFT mint
+        rules: rules.clone(),
+        amount,
+        gross_amount,
+        asset_address: asset_address.clone(),
+        created_at: TimeUtils::now(e),
+        expires_at,
+        current_value: amount, // Initially same as amount
+        status: CommitmentStatus::Active,
+        allocated_amount: 0,
+        yield_earned: 0,
+        is_basket: false,
+    };
+    set_commitment(e, &commitment);
+
+    // Update owner's commitment list
+    let mut owner_commitments = read_owner_commitments(e, owner);
+    owner_commitments.push_back(commitment_id.clone());
+    write_owner_commitments(e, owner, &owner_commitments);
+    status_index_add(e, CommitmentStatus::Active, &commitment_id);
+
     e.storage()
         .instance()
-        .get::<_, Commitment>(&DataKey::Commitment(commitment_id.clone()))
+        .set(&DataKey::TotalCommitments, &(current_total + 1));
+
+    commitment
 }
 
-fn set_commitment(e: &Env, commitment: &Commitment) {
-    e.storage().instance().set(
-        &DataKey::Commitment(commitment.commitment_id.clone()),
-        commitment,
-    );
+/// How value left TVL, for the cumulative volume metrics.
+#[derive(Clone, Copy)]
+enum ValueExit {
+    Settled,
+    Exited,
 }
 
-fn has_commitment(e: &Env, commitment_id: &String) -> bool {
+/// Metrics bucket for a commitment: its asset, or `None` for baskets.
+fn metrics_bucket(commitment: &Commitment) -> Option<Address> {
+    if commitment.is_basket {
+        None
+    } else {
+        Some(commitment.asset_address.clone())
+    }
+}
+
+fn read_asset_metrics(e: &Env, bucket: &Option<Address>) -> AssetMetrics {
+    let key = match bucket {
+        Some(asset_address) => DataKey::AssetMetrics(asset_address.clone()),
+        None => DataKey::BasketMetrics,
+    };
+    e.storage().instance().get(&key).unwrap_or_default()
+}
+
+fn write_asset_metrics(e: &Env, bucket: &Option<Address>, metrics: &AssetMetrics) {
+    let key = match bucket {
+        Some(asset_address) => {
+            let key = DataKey::AssetMetrics(asset_address.clone());
+            if !e.storage().instance().has(&key) {
+                let mut assets = read_metric_assets(e);
+                assets.push_back(asset_address.clone());
+                e.storage().instance().set(&DataKey::MetricAssets, &assets);
+            }
+            key
+        }
+        None => DataKey::BasketMetrics,
+    };
+    e.storage().instance().set(&key, metrics);
+}
+
+fn read_metric_assets(e: &Env) -> Vec<Address> {
     e.storage()
         .instance()
-        .has(&DataKey::Commitment(commitment_id.clone()))
+        .get(&DataKey::MetricAssets)
+        .unwrap_or_else(|| Vec::new(e))
 }
 
-/// Reentrancy protection helpers
-fn require_no_reentrancy(e: &Env) {
-    let guard: bool = e
+fn adjust_value_locked(e: &Env, bucket: &Option<Address>, delta: i128) {
+    let mut metrics = read_asset_metrics(e, bucket);
+    metrics.value_locked = SafeMath::add(metrics.value_locked, delta);
+    write_asset_metrics(e, bucket, &metrics);
+}
+
+/// Move TVL by `delta`, both in total and in the commitment's asset metrics.
+/// Baskets hold no value in token units; their oracle valuation is tracked in the
+/// basket metrics instead and never counts towards TVL.
+fn adjust_tvl(e: &Env, commitment: &Commitment, delta: i128) {
+    if commitment.is_basket {
+        return;
+    }
+    let current_tvl = e
         .storage()
         .instance()
-        .get::<_, bool>(&DataKey::ReentrancyGuard)
-        .unwrap_or(false);
+        .get::<_, i128>(&DataKey::TotalValueLocked)
+        .unwrap_or(0);
+    e.storage().instance().set(
+        &DataKey::TotalValueLocked,
+        &SafeMath::add(current_tvl, delta),
+    );
+    adjust_value_locked(e, &metrics_bucket(commitment), delta);
+}
 
-    if guard {
-        fail(
-            e,
-            CommitmentError::ReentrancyDetected,
-            "require_no_reentrancy",
-        );
+/// Take `amount` of a commitment's value out of TVL and count it as settled or exited volume.
+/// Baskets release their latest oracle valuation from the basket metrics instead.
+fn release_tvl(e: &Env, commitment: &Commitment, amount: i128, exit: ValueExit) {
+    let bucket = metrics_bucket(commitment);
+    let amount = if commitment.is_basket {
+        let value = read_oracle_valuation(e, &commitment.commitment_id)
+            .map(|valuation| valuation.value)
+            .unwrap_or(0);
+        adjust_value_locked(e, &bucket, -value);
+        value
+    } else {
+        adjust_tvl(e, commitment, -amount);
+        amount
+    };
+
+    let mut metrics = read_asset_metrics(e, &bucket);
+    match exit {
+        ValueExit::Settled => {
+            metrics.settled_volume = SafeMath::add(metrics.settled_volume, amount)
+        }
+        ValueExit::Exited => metrics.exited_volume = SafeMath::add(metrics.exited_volume, amount),
     }
+    write_asset_metrics(e, &bucket, &metrics);
 }
 
-fn set_reentrancy_guard(e: &Env, value: bool) {
-    e.storage()
-        .instance()
-        .set(&DataKey::ReentrancyGuard, &value);
+/// Add protocol fees (creation and performance) to the per-asset `CollectedFees` ledger.
+fn credit_collected_fees(e: &Env, asset_address: &Address, amount: i128) {
+    let key = DataKey::CollectedFees(asset_address.clone());
+    let collected = e.storage().instance().get::<_, i128>(&key).unwrap_or(0);
+    e.storage().instance().set(&key, &(collected + amount));
 }
 
-/// Require that the caller is the admin stored in this contract.
-fn require_admin(e: &Env, caller: &Address) {
-    caller.require_auth();
-    let admin = e
+/// Take the performance fee on `realized_yield` (all or part of the commitment's
+/// `yield_earned`) into `CollectedFees`. Returns the fee so the caller can deduct
+/// it from the payout.
+fn collect_performance_fee(e: &Env, commitment: &Commitment, realized_yield: i128) -> i128 {
+    if realized_yield <= 0 {
+        return 0;
+    }
+    let bps = e
         .storage()
         .instance()
-        .get::<_, Address>(&DataKey::Admin)
-        .unwrap_or_else(|| fail(e, CommitmentError::NotInitialized, "require_admin"));
-    if *caller != admin {
-        fail(e, CommitmentError::Unauthorized, "require_admin");
+        .get::<_, u32>(&DataKey::PerformanceFeeBps)
+        .unwrap_or(0);
+    let fee = fees::fee_from_bps(realized_yield, bps);
+    if fee > 0 {
+        credit_collected_fees(e, &commitment.asset_address, fee);
+        e.events().publish(
+            (
+                Symbol::new(e, "PerformanceFeeCollected"),
+                commitment.commitment_id.clone(),
+            ),
+            (
+                commitment.asset_address.clone(),
+                fee,
+                e.ledger().timestamp(),
+            ),
+        );
     }
+    fee
 }
 
-/// Pause the contract
-///
-/// # Arguments
-/// * `e` - The environment
-///
-/// # Panics
-/// Panics if caller is not admin or if contract is already paused
-pub fn pause(e: Env) {
-    let admin = e
+/// Whether the commitment still holds its funds: Active, or Violated and not yet resolved.
+fn holds_funds(e: &Env, commitment: &Commitment) -> bool {
+    match commitment.status {
+        CommitmentStatus::Active => true,
+        CommitmentStatus::Violated => !e.storage().persistent().has(&DataKey::ViolationResolved(
+            commitment.commitment_id.clone(),
+        )),
+        _ => false,
+    }
+}
+
+/// Whether the commitment's value or oracle valuation has fallen past its `max_loss_percent`.
+fn is_loss_violated(e: &Env, commitment: &Commitment) -> bool {
+    loss_percent(e, commitment) > commitment.rules.max_loss_percent as i128
+}
+
+/// Pay the configured sweep reward for `marked` commitments to `keeper`, out of
+/// the keeper budget for the reward asset. Capped by what remains in the budget.
+fn pay_sweep_reward(e: &Env, keeper: &Address, marked: u32) -> i128 {
+    let Some((asset_address, per_commitment)) = e
         .storage()
         .instance()
-        .get::<_, Address>(&DataKey::Admin)
-        .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "pause"));
-    admin.require_auth();
-    Pausable::pause(&e);
+        .get::<_, (Address, i128)>(&DataKey::SweepReward)
+    else {
+        return 0;
+    };
+    let key = DataKey::KeeperBudget(asset_address.clone());
+    let budget = e.storage().instance().get::<_, i128>(&key).unwrap_or(0);
+    let reward = SafeMath::mul(per_commitment, marked as i128).min(budget);
+    if reward <= 0 {
+        return 0;
+    }
+    e.storage().instance().set(&key, &(budget - reward));
+    token::Client::new(e, &asset_address).transfer(&e.current_contract_address(), keeper, &reward);
+    reward
 }
 
-/// Unpause the contract
-///
-/// # Arguments
-/// * `e` - The environment
-///
-/// # Panics
-/// Panics if caller is not admin or if contract is already unpaused
-pub fn unpause(e: Env) {
-    let admin = e
-        .storage()
+/// End of the post-expiry grace window (`expires_at + grace_period_days`).
+fn grace_period_end(commitment: &Commitment) -> u64 {
+    commitment
+        .expires_at
+        .saturating_add(TimeUtils::days_to_seconds(
+            commitment.rules.grace_period_days,
+        ))
+}
+
+fn grace_state(commitment: &Commitment, current_time: u64) -> GraceState {
+    if current_time < commitment.expires_at {
+        GraceState::NotExpired
+    } else if current_time < grace_period_end(commitment) {
+        GraceState::InGracePeriod
+    } else {
+        GraceState::GraceElapsed
+    }
+}
+
+/// Helper function to sync the NFT's recorded value after a top-up or partial exit
+fn call_nft_update_value(e: &Env, nft_contract: &Address, token_id: u32, new_value: i128) {
+    let mut args = Vec::new(e);
+    args.push_back(token_id.into_val(e));
+    args.push_back(new_value.into_val(e));
+    e.invoke_contract::<()>(nft_contract, &Symbol::new(e, "update_value"), args);
+}
+
+/// Credit an early-exit penalty to the per-asset treasury ledger.
+/// Tokens stay in the contract until `withdraw_penalties`.
+fn credit_penalty(e: &Env, commitment_id: &String, asset_address: &Address, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    let key = DataKey::CollectedPenalties(asset_address.clone());
+    let collected = e.storage().instance().get::<_, i128>(&key).unwrap_or(0);
+    e.storage()
         .instance()
-        .get::<_, Address>(&DataKey::Admin)
-        .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "unpause"));
-    admin.require_auth();
-    Pausable::unpause(&e);
+        .set(&key, &SafeMath::add(collected, amount));
+    e.events().publish(
+        (
+            Symbol::new(e, "PenaltyCollected"),
+            commitment_id.clone(),
+            asset_address.clone(),
+        ),
+        (amount, e.ledger().timestamp()),
+    );
 }
 
-/// Check if the contract is paused
-///
-/// # Arguments
-/// * `e` - The environment
-///
-/// # Returns
-/// `true` if paused, `false` otherwise
-pub fn is_paused(e: Env) -> bool {
-    Pausable::is_paused(&e)
+fn asset_locked(e: &Env, asset_address: &Address) -> i128 {
+    e.storage()
+        .instance()
+        .get::<_, i128>(&DataKey::AssetLocked(asset_address.clone()))
+        .unwrap_or(0)
 }
 
-#[contract]
-pub struct CommitmentCoreContract;
+fn owner_asset_locked(e: &Env, owner: &Address, asset_address: &Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get::<_, i128>(&DataKey::OwnerAssetLocked(
+            owner.clone(),
+            asset_address.clone(),
+        ))
+        .unwrap_or(0)
+}
 
-#[contractimpl]
-impl CommitmentCoreContract {
-    /// Validate commitment rules using shared utilities
-    fn validate_rules(e: &Env, rules: &CommitmentRules) {
-        // Duration must be > 0
-        Validation::require_valid_duration(rules.duration_days);
+/// Move the principal `owner` holds in active commitments of `asset_address` by `delta`.
+fn adjust_asset_locked(e: &Env, owner: &Address, asset_address: &Address, delta: i128) {
+    e.storage().instance().set(
+        &DataKey::AssetLocked(asset_address.clone()),
+        &SafeMath::add(asset_locked(e, asset_address), delta),
+    );
+    let owner_key = DataKey::OwnerAssetLocked(owner.clone(), asset_address.clone());
+    e.storage().persistent().set(
+        &owner_key,
+        &SafeMath::add(owner_asset_locked(e, owner, asset_address), delta),
+    );
+    extend_persistent_ttl(e, &owner_key);
+}
 
-        // Max loss percent must be between 0 and 100
-        Validation::require_valid_percent(rules.max_loss_percent);
+/// Count or release every asset a commitment locks (each holding, for baskets).
+fn adjust_commitment_locked(e: &Env, commitment: &Commitment, sign: i128) {
+    match read_holdings(e, &commitment.commitment_id) {
+        Some(holdings) => {
+            for (asset_address, amount) in holdings.iter() {
+                adjust_asset_locked(e, &commitment.owner, &asset_address, sign * amount);
+            }
+        }
+        None => adjust_asset_locked(
+            e,
+            &commitment.owner,
+            &commitment.asset_address,
+            sign * commitment.amount,
+        ),
+    }
+}
 
-        // Commitment type must be valid
-        let valid_types = ["safe", "balanced", "aggressive"];
-        Validation::require_valid_commitment_type(e, &rules.commitment_type, &valid_types);
+/// Enforce the asset registry before `owner` locks `added` more of `asset_address`.
+/// `creating` also applies the asset's minimum commitment size to `added`.
+fn require_asset_limits(
+    e: &Env,
+    owner: &Address,
+    asset_address: &Address,
+    added: i128,
+    creating: bool,
+    context: &str,
+) {
+    let config = e
+        .storage()
+        .instance()
+        .get::<_, AssetConfig>(&DataKey::AssetConfig(asset_address.clone()));
+    let Some(config) = config else {
+        if is_asset_allowlist_enforced(e) {
+            fail(e, CommitmentError::AssetNotAllowed, context);
+        }
+        return;
+    };
+    if !config.enabled {
+        fail(e, CommitmentError::AssetNotAllowed, context);
+    }
+    if creating && added < config.min_amount {
+        fail(e, CommitmentError::InvalidAmount, context);
     }
+    let over_tvl_cap =
+        config.tvl_cap > 0 && SafeMath::add(asset_locked(e, asset_address), added) > config.tvl_cap;
+    let over_owner_cap = config.owner_cap > 0
+        && SafeMath::add(owner_asset_locked(e, owner, asset_address), added) > config.owner_cap;
+    if over_tvl_cap || over_owner_cap {
+        fail(e, CommitmentError::AssetCapExceeded, context);
+    }
+}
 
-    /// Generate unique commitment ID
-    /// Optimized: Uses counter to create unique ID efficiently
-    fn generate_commitment_id(e: &Env, counter: u64) -> String {
-        // OPTIMIZATION: Use counter directly as string to minimize allocations
+fn is_asset_allowlist_enforced(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get::<_, bool>(&DataKey::AssetAllowlistEnforced)
+        .unwrap_or(false)
+}
+
+fn read_template(e: &Env, template_id: u32, context: &str) -> CommitmentTemplate {
+    let key = DataKey::Template(template_id);
+    let template = e
+        .storage()
+        .persistent()
+        .get::<_, CommitmentTemplate>(&key)
+        .unwrap_or_else(|| fail(e, CommitmentError::TemplateNotFound, context));
+    extend_persistent_ttl(e, &key);
+    template
+}
+
+fn write_template(e: &Env, template_id: u32, template: &CommitmentTemplate) {
+    let key = DataKey::Template(template_id);
+    e.storage().persistent().set(&key, template);
+    extend_persistent_ttl(e, &key);
+}
+
+/// Ranges must be ordered and every rule a template can produce must pass `validate_rules`.
+fn validate_template(e: &Env, template: &CommitmentTemplate) {
+    let valid = template.min_duration_days > 0
+        && template.min_duration_days <= template.max_duration_days
+        && template.min_loss_percent <= template.max_loss_percent
+        && template.max_loss_percent <= 100
+        && template.early_exit_penalty <= 100
+        && template.min_amount > 0
+        && ["safe", "balanced", "aggressive"]
+            .iter()
+            .any(|t| template.commitment_type == String::from_str(e, t));
+    if !valid {
+        fail(e, CommitmentError::InvalidTemplate, "validate_template");
+    }
+}
+
+fn read_holdings(e: &Env, commitment_id: &String) -> Option<Vec<(Address, i128)>> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Holdings(commitment_id.clone()))
+}
+
+fn write_holdings(e: &Env, commitment_id: &String, holdings: &Vec<(Address, i128)>) {
+    let key = DataKey::Holdings(commitment_id.clone());
+    e.storage().persistent().set(&key, holdings);
+    extend_persistent_ttl(e, &key);
+}
+
+/// Reject operations that move or value a single `asset_address` on a basket commitment.
+fn require_single_asset(e: &Env, commitment_id: &String, context: &str) {
+    if e.storage()
+        .persistent()
+        .has(&DataKey::Holdings(commitment_id.clone()))
+    {
+        set_reentrancy_guard(e, false);
+        fail(e, CommitmentError::MultiAssetNotSupported, context);
+    }
+}
+
+/// Per-asset amounts from `pay_out_holdings`, in holdings order.
+struct BasketPayout {
+    paid: Vec<(Address, i128)>,
+    kept: Vec<(Address, i128)>,
+}
+
+/// Transfer every basket holding to `recipient`, keeping back `withheld(amount)` of
+/// each asset. Returns what was paid, for events, and what was kept back per asset
+/// for the caller to route.
+fn pay_out_holdings(
+    e: &Env,
+    holdings: &Vec<(Address, i128)>,
+    recipient: &Address,
+    withheld: impl Fn(i128) -> i128,
+) -> BasketPayout {
+    let contract_address = e.current_contract_address();
+    let mut payout = BasketPayout {
+        paid: Vec::new(e),
+        kept: Vec::new(e),
+    };
+    for (asset_address, amount) in holdings.iter() {
+        let held_back = withheld(amount);
+        let paid = SafeMath::sub(amount, held_back);
+        if paid > 0 {
+            token::Client::new(e, &asset_address).transfer(&contract_address, recipient, &paid);
+        }
+        payout.paid.push_back((asset_address.clone(), paid));
+        payout.kept.push_back((asset_address, held_back));
+    }
+    payout
+}
+
+fn is_operator(e: &Env, owner: &Address, operator: &Address, needed: OperatorScope) -> bool {
+    e.storage()
+        .persistent()
+        .get::<_, OperatorApproval>(&DataKey::Operator(owner.clone(), operator.clone()))
+        .is_some_and(|approval| {
+            approval.scope.covers(needed) && e.ledger().timestamp() < approval.expires_at
+        })
+}
+
+/// `caller` must authorize and be the owner or an operator approved for `needed`.
+fn require_owner_or_operator(
+    e: &Env,
+    owner: &Address,
+    caller: &Address,
+    needed: OperatorScope,
+    context: &str,
+) {
+    caller.require_auth();
+    if caller != owner && !is_operator(e, owner, caller, needed) {
+        set_reentrancy_guard(e, false);
+        fail(e, CommitmentError::Unauthorized, context);
+    }
+}
+
+// Storage helpers
+/// Single entry point for status changes; rejects transitions the state machine forbids.
+/// Also moves the commitment between per-status index lists.
+fn transition_status(e: &Env, commitment: &mut Commitment, next: CommitmentStatus, context: &str) {
+    if !commitment.status.can_transition_to(next) {
+        fail(e, CommitmentError::InvalidStatus, context);
+    }
+    // Every transition leaves Active, releasing the principal from asset caps
+    adjust_commitment_locked(e, commitment, -1);
+    status_index_remove(e, commitment.status, &commitment.commitment_id);
+    status_index_add(e, next, &commitment.commitment_id);
+    commitment.status = next;
+}
+
+fn status_count(e: &Env, status: CommitmentStatus) -> u32 {
+    e.storage()
+        .instance()
+        .get::<_, u32>(&DataKey::StatusCount(status))
+        .unwrap_or(0)
+}
+
+/// Append `commitment_id` to the list for `status`.
+fn status_index_add(e: &Env, status: CommitmentStatus, commitment_id: &String) {
+    let slot = status_count(e, status);
+    let entry_key = DataKey::StatusEntry(status, slot);
+    let position_key = DataKey::StatusPosition(commitment_id.clone());
+    e.storage().persistent().set(&entry_key, commitment_id);
+    e.storage().persistent().set(&position_key, &slot);
+    extend_persistent_ttl(e, &entry_key);
+    extend_persistent_ttl(e, &position_key);
+    e.storage()
+        .instance()
+        .set(&DataKey::StatusCount(status), &(slot + 1));
+}
+
+/// Swap-remove `commitment_id` from the list for `status`. No-op if it is not indexed.
+fn status_index_remove(e: &Env, status: CommitmentStatus, commitment_id: &String) {
+    let position_key = DataKey::StatusPosition(commitment_id.clone());
+    let Some(slot) = e.storage().persistent().get::<_, u32>(&position_key) else {
+        return;
+    };
+    let count = status_count(e, status);
+    if count == 0 {
+        return;
+    }
+    let last = count - 1;
+    if slot != last {
+        let moved = e
+            .storage()
+            .persistent()
+            .get::<_, String>(&DataKey::StatusEntry(status, last))
+            .unwrap_or_else(|| fail(e, CommitmentError::InvalidStatus, "status_index_remove"));
+        let entry_key = DataKey::StatusEntry(status, slot);
+        let moved_key = DataKey::StatusPosition(moved.clone());
+        e.storage().persistent().set(&entry_key, &moved);
+        e.storage().persistent().set(&moved_key, &slot);
+        extend_persistent_ttl(e, &entry_key);
+        extend_persistent_ttl(e, &moved_key);
+    }
+    e.storage()
+        .persistent()
+        .remove(&DataKey::StatusEntry(status, last));
+    e.storage().persistent().remove(&position_key);
+    e.storage()
+        .instance()
+        .set(&DataKey::StatusCount(status), &last);
+}
+
+/// Clamp a `(start, limit)` page request against `len` items.
+fn page_bounds(start: u32, limit: u32, len: u32) -> (u32, u32) {
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(len);
+    (start.min(end), end)
+}
+
+fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, COMMITMENT_TTL_THRESHOLD, COMMITMENT_TTL_EXTEND_TO);
+}
+
+fn read_commitment(e: &Env, commitment_id: &String) -> Option<Commitment> {
+    let key = DataKey::Commitment(commitment_id.clone());
+    let commitment = e.storage().persistent().get::<_, Commitment>(&key);
+    if commitment.is_some() {
+        extend_persistent_ttl(e, &key);
+    }
+    commitment
+}
+
+fn set_commitment(e: &Env, commitment: &Commitment) {
+    let key = DataKey::Commitment(commitment.commitment_id.clone());
+    e.storage().persistent().set(&key, commitment);
+    extend_persistent_ttl(e, &key);
+}
+
+fn read_allocation(e: &Env, key: &DataKey) -> i128 {
+    e.storage().persistent().get::<_, i128>(key).unwrap_or(0)
+}
+
+/// Record what `commitment_id` has allocated to `pool`, keeping its list of pools
+/// with outstanding allocations in step.
+fn write_allocation(e: &Env, commitment_id: &String, pool: &Address, amount: i128) {
+    let key = DataKey::Allocation(commitment_id.clone(), pool.clone());
+    let pools_key = DataKey::AllocationPools(commitment_id.clone());
+    let mut pools = e
+        .storage()
+        .persistent()
+        .get::<_, Vec<Address>>(&pools_key)
+        .unwrap_or_else(|| Vec::new(e));
+    let index = pools.first_index_of(pool);
+    if amount == 0 {
+        e.storage().persistent().remove(&key);
+        if let Some(index) = index {
+            pools.remove(index);
+        }
+    } else {
+        e.storage().persistent().set(&key, &amount);
+        extend_persistent_ttl(e, &key);
+        if index.is_none() {
+            pools.push_back(pool.clone());
+        }
+    }
+    if pools.is_empty() {
+        e.storage().persistent().remove(&pools_key);
+    } else {
+        e.storage().persistent().set(&pools_key, &pools);
+        extend_persistent_ttl(e, &pools_key);
+    }
+}
+
+fn has_commitment(e: &Env, commitment_id: &String) -> bool {
+    e.storage()
+        .persistent()
+        .has(&DataKey::Commitment(commitment_id.clone()))
+}
+
+fn read_owner_commitments(e: &Env, owner: &Address) -> Vec<String> {
+    let key = DataKey::OwnerCommitments(owner.clone());
+    match e.storage().persistent().get::<_, Vec<String>>(&key) {
+        Some(ids) => {
+            extend_persistent_ttl(e, &key);
+            ids
+        }
+        None => Vec::new(e),
+    }
+}
+
+fn write_owner_commitments(e: &Env, owner: &Address, ids: &Vec<String>) {
+    let key = DataKey::OwnerCommitments(owner.clone());
+    e.storage().persistent().set(&key, ids);
+    extend_persistent_ttl(e, &key);
+}
+
+/// Reentrancy protection helpers
+fn require_no_reentrancy(e: &Env) {
+    let guard: bool = e
+        .storage()
+        .instance()
+        .get::<_, bool>(&DataKey::ReentrancyGuard)
+        .unwrap_or(false);
+
+    if guard {
+        fail(
+            e,
+            CommitmentError::ReentrancyDetected,
+            "require_no_reentrancy",
+        );
+    }
+}
+
+fn set_reentrancy_guard(e: &Env, value: bool) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ReentrancyGuard, &value);
+}
+
+/// Require that the caller is the admin stored in this contract.
+fn require_admin(e: &Env, caller: &Address) {
+    caller.require_auth();
+    let admin = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::Admin)
+        .unwrap_or_else(|| fail(e, CommitmentError::NotInitialized, "require_admin"));
+    if *caller != admin {
+        fail(e, CommitmentError::Unauthorized, "require_admin");
+    }
+}
+
+fn read_version(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<_, u32>(&DataKey::Version)
+        .unwrap_or(0)
+}
+
+/// Rewrite commitments stored with string statuses (v0) as `CommitmentStatus`.
+/// Commitment IDs are derived from the creation counter, so every record is reachable.
+fn migrate_legacy_statuses(e: &Env) {
+    let total = e
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::TotalCommitments)
+        .unwrap_or(0);
+    for counter in 0..total {
+        let key = DataKey::Commitment(CommitmentCoreContract::generate_commitment_id(e, counter));
+        let Some(raw) = e.storage().instance().get::<_, Val>(&key) else {
+            continue;
+        };
+        // Records whose status is already typed are left untouched
+        let legacy_status = record_fields(e, &raw)
+            .get(Symbol::new(e, "status"))
+            .is_some_and(|v| String::try_from_val(e, &v).is_ok());
+        if !legacy_status {
+            continue;
+        }
+        let mut fields = record_fields(e, &raw);
+        fill_rules_fields(e, &mut fields);
+        let legacy = LegacyCommitment::try_from_val(e, &fields.to_val())
+            .unwrap_or_else(|_| fail(e, CommitmentError::InvalidStatus, "migrate"));
+        let status = CommitmentStatus::from_legacy(e, &legacy.status)
+            .unwrap_or_else(|| fail(e, CommitmentError::InvalidStatus, "migrate"));
+        let commitment = Commitment {
+            commitment_id: legacy.commitment_id,
+            owner: legacy.owner,
+            nft_token_id: legacy.nft_token_id,
+            rules: legacy.rules,
+            amount: legacy.amount,
+            gross_amount: legacy.amount,
+            asset_address: legacy.asset_address,
+            created_at: legacy.created_at,
+            expires_at: legacy.expires_at,
+            current_value: legacy.current_value,
+            status,
+            allocated_amount: 0,
+            yield_earned: 0,
+            is_basket: false,
+        };
+        e.storage().instance().set(&key, &commitment);
+    }
+}
+
+/// Field map of a stored `#[contracttype]` struct, readable whatever its layout version.
+fn record_fields(e: &Env, raw: &Val) -> Map<Symbol, Val> {
+    Map::try_from_val(e, raw).unwrap_or_else(|_| fail(e, CommitmentError::InvalidStatus, "migrate"))
+}
+
+/// Move commitments and owner indexes (v1) from instance to persistent storage.
+fn migrate_to_persistent(e: &Env) {
+    let total = e
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::TotalCommitments)
+        .unwrap_or(0);
+    for counter in 0..total {
+        let key = DataKey::Commitment(CommitmentCoreContract::generate_commitment_id(e, counter));
+        // Moved as raw values; the record layout is upgraded by later steps
+        let Some(raw) = e.storage().instance().get::<_, Val>(&key) else {
+            continue;
+        };
+        let owner = record_fields(e, &raw)
+            .get(Symbol::new(e, "owner"))
+            .and_then(|v| Address::try_from_val(e, &v).ok())
+            .unwrap_or_else(|| fail(e, CommitmentError::InvalidStatus, "migrate"));
+        e.storage().instance().remove(&key);
+        e.storage().persistent().set(&key, &raw);
+        extend_persistent_ttl(e, &key);
+
+        let owner_key = DataKey::OwnerCommitments(owner.clone());
+        if let Some(ids) = e.storage().instance().get::<_, Vec<String>>(&owner_key) {
+            e.storage().instance().remove(&owner_key);
+            write_owner_commitments(e, &owner, &ids);
+        }
+    }
+}
+
+fn require_valid_wasm_hash(e: &Env, wasm_hash: &BytesN<32>) {
+    let zero = BytesN::from_array(e, &[0; 32]);
+    if *wasm_hash == zero {
+        fail(e, CommitmentError::InvalidWasmHash, "upgrade");
+    }
+}
+
+/// Fill in `Commitment` fields added after v3 on records written by older versions:
+/// `gross_amount` (v4) equals the locked amount, since no creation fee was charged;
+/// `allocated_amount` and `yield_earned` (v5) start at zero; `rules.transfer_policy`
+/// (v8) is `Free`, the only behaviour earlier NFTs had; `is_basket` (v9) is set for
+/// commitments with basket holdings.
+fn migrate_commitment_fields(e: &Env) {
+    let total = e
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::TotalCommitments)
+        .unwrap_or(0);
+    for counter in 0..total {
+        let commitment_id = CommitmentCoreContract::generate_commitment_id(e, counter);
+        let is_basket: Val = e
+            .storage()
+            .persistent()
+            .has(&DataKey::Holdings(commitment_id.clone()))
+            .into_val(e);
+        let key = DataKey::Commitment(commitment_id);
+        let Some(raw) = e.storage().persistent().get::<_, Val>(&key) else {
+            continue;
+        };
+        let mut fields = record_fields(e, &raw);
+        let amount = fields
+            .get(Symbol::new(e, "amount"))
+            .unwrap_or_else(|| fail(e, CommitmentError::InvalidStatus, "migrate"));
+        let zero: Val = 0i128.into_val(e);
+        for (name, default) in [
+            ("gross_amount", amount),
+            ("allocated_amount", zero),
+            ("yield_earned", zero),
+            ("is_basket", is_basket),
+        ] {
+            let field = Symbol::new(e, name);
+            if !fields.contains_key(field.clone()) {
+                fields.set(field, default);
+            }
+        }
+        fill_rules_fields(e, &mut fields);
+        e.storage().persistent().set(&key, &fields);
+        extend_persistent_ttl(e, &key);
+    }
+}
+
+/// Move basket values (v9) out of the single-asset fields: the aggregate quote value
+/// they held becomes the basket's `OracleValuation`, and leaves `TotalValueLocked`.
+fn migrate_basket_values(e: &Env) {
+    let total = e
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::TotalCommitments)
+        .unwrap_or(0);
+    for counter in 0..total {
+        let id = CommitmentCoreContract::generate_commitment_id(e, counter);
+        let Some(mut commitment) = read_commitment(e, &id) else {
+            continue;
+        };
+        if !commitment.is_basket || commitment.amount == 0 {
+            continue;
+        }
+        if read_oracle_valuation(e, &id).is_none() {
+            write_oracle_valuation(
+                e,
+                &id,
+                &OracleValuation {
+                    entry_value: commitment.amount,
+                    value: commitment.current_value,
+                    updated_at: e.ledger().timestamp(),
+                },
+            );
+        }
+        if holds_funds(e, &commitment) {
+            let tvl = e
+                .storage()
+                .instance()
+                .get::<_, i128>(&DataKey::TotalValueLocked)
+                .unwrap_or(0);
+            e.storage().instance().set(
+                &DataKey::TotalValueLocked,
+                &SafeMath::sub(tvl, commitment.current_value),
+            );
+        }
+        commitment.amount = 0;
+        commitment.gross_amount = 0;
+        commitment.current_value = 0;
+        set_commitment(e, &commitment);
+    }
+}
+
+/// Default `transfer_policy` (v8) inside a stored record's `rules`.
+fn fill_rules_fields(e: &Env, fields: &mut Map<Symbol, Val>) {
+    let rules_key = Symbol::new(e, "rules");
+    let mut rules = fields
+        .get(rules_key.clone())
+        .map(|raw| record_fields(e, &raw))
+        .unwrap_or_else(|| fail(e, CommitmentError::InvalidStatus, "migrate"));
+    let policy_key = Symbol::new(e, "transfer_policy");
+    if !rules.contains_key(policy_key.clone()) {
+        rules.set(policy_key, TransferPolicy::Free.into_val(e));
+        fields.set(rules_key, rules.to_val());
+    }
+}
+
+/// Default `transfer_policy` (v8) on stored templates to `Free`.
+fn migrate_template_fields(e: &Env) {
+    let count = e
+        .storage()
+        .instance()
+        .get::<_, u32>(&DataKey::TemplateCount)
+        .unwrap_or(0);
+    for template_id in 0..count {
+        let key = DataKey::Template(template_id);
+        let Some(raw) = e.storage().persistent().get::<_, Val>(&key) else {
+            continue;
+        };
+        let mut fields = record_fields(e, &raw);
+        let policy_key = Symbol::new(e, "transfer_policy");
+        if !fields.contains_key(policy_key.clone()) {
+            fields.set(policy_key, TransferPolicy::Free.into_val(e));
+            e.storage().persistent().set(&key, &fields);
+            extend_persistent_ttl(e, &key);
+        }
+    }
+}
+
+/// Build the per-status index (v3) from existing commitments.
+fn migrate_status_index(e: &Env) {
+    let total = e
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::TotalCommitments)
+        .unwrap_or(0);
+    for counter in 0..total {
+        let id = CommitmentCoreContract::generate_commitment_id(e, counter);
+        if let Some(commitment) = read_commitment(e, &id) {
+            status_index_add(e, commitment.status, &id);
+        }
+    }
+}
+
+/// Count the principal of every active commitment towards its asset caps.
+fn migrate_asset_locked(e: &Env) {
+    let total = e
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::TotalCommitments)
+        .unwrap_or(0);
+    for counter in 0..total {
+        let id = CommitmentCoreContract::generate_commitment_id(e, counter);
+        if let Some(commitment) = read_commitment(e, &id) {
+            if commitment.status == CommitmentStatus::Active {
+                adjust_commitment_locked(e, &commitment, 1);
+            }
+        }
+    }
+}
+
+/// Rebuild per-asset value locked from commitments still holding value.
+/// Cumulative volumes start at 0: earlier settlements and exits were not attributed.
+fn migrate_asset_metrics(e: &Env) {
+    let total = e
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::TotalCommitments)
+        .unwrap_or(0);
+    for counter in 0..total {
+        let id = CommitmentCoreContract::generate_commitment_id(e, counter);
+        if let Some(commitment) = read_commitment(e, &id) {
+            let locked = matches!(
+                commitment.status,
+                CommitmentStatus::Active | CommitmentStatus::Violated
+            );
+            if locked && commitment.current_value != 0 {
+                adjust_value_locked(e, &metrics_bucket(&commitment), commitment.current_value);
+            }
+        }
+    }
+}
+
+fn is_valuation_updater(e: &Env, address: &Address) -> bool {
+    let admin = e.storage().instance().get::<_, Address>(&DataKey::Admin);
+    if admin.as_ref() == Some(address) {
+        return true;
+    }
+    e.storage()
+        .instance()
+        .get::<_, bool>(&DataKey::ValuationUpdater(address.clone()))
+        .unwrap_or(false)
+}
+
+/// Value `amount` of `asset_address` in the oracle's quote units, failing with
+/// `context` if the oracle cannot price it.
+fn oracle_value(e: &Env, asset_address: &Address, amount: i128, context: &str) -> i128 {
+    quote_value(e, asset_address, amount).unwrap_or_else(|err| fail(e, err, context))
+}
+
+/// Value `amount` of `asset_address` in the oracle's quote units.
+/// Stale feeds surface as `StalePrice`; any other oracle failure as `PriceUnavailable`.
+fn quote_value(e: &Env, asset_address: &Address, amount: i128) -> Result<i128, CommitmentError> {
+    let oracle = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::PriceOracle)
+        .ok_or(CommitmentError::OracleNotSet)?;
+    let args = Vec::from_array(
+        e,
+        [asset_address.into_val(e), Option::<u64>::None.into_val(e)],
+    );
+    let stale = soroban_sdk::Error::from_contract_error(ORACLE_STALE_PRICE);
+    let price = match e.try_invoke_contract::<OraclePrice, soroban_sdk::Error>(
+        &oracle,
+        &Symbol::new(e, "get_price_valid"),
+        args,
+    ) {
+        Ok(Ok(price)) if price.price > 0 => price,
+        Err(Ok(err)) if err == stale => return Err(CommitmentError::StalePrice),
+        _ => return Err(CommitmentError::PriceUnavailable),
+    };
+    let scale = 10i128
+        .checked_pow(price.decimals)
+        .ok_or(CommitmentError::PriceUnavailable)?;
+    Ok(SafeMath::div(SafeMath::mul(amount, price.price), scale))
+}
+
+fn read_oracle_valuation(e: &Env, commitment_id: &String) -> Option<OracleValuation> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::OracleValuation(commitment_id.clone()))
+}
+
+fn write_oracle_valuation(e: &Env, commitment_id: &String, valuation: &OracleValuation) {
+    let key = DataKey::OracleValuation(commitment_id.clone());
+    e.storage().persistent().set(&key, valuation);
+    extend_persistent_ttl(e, &key);
+}
+
+/// Record the reference valuation a new commitment's oracle loss is measured against.
+/// Skipped when no oracle is configured or it cannot price the asset, in which case
+/// the first `refresh_value_from_oracle` records it instead.
+fn record_entry_valuation(e: &Env, commitment_id: &String, asset_address: &Address, amount: i128) {
+    if let Ok(value) = quote_value(e, asset_address, amount) {
+        write_oracle_valuation(
+            e,
+            commitment_id,
+            &OracleValuation {
+                entry_value: value,
+                value,
+                updated_at: e.ledger().timestamp(),
+            },
+        );
+    }
+}
+
+/// Loss in percent against the commitment's limit: the larger of the drop in value
+/// below `amount` and the drop in oracle valuation below its entry value. Principal
+/// allocated to pools still counts towards the value until a loss is recorded.
+fn loss_percent(e: &Env, commitment: &Commitment) -> i128 {
+    // Zero-amount commitments cannot meaningfully violate a loss limit
+    let value_loss = if commitment.amount > 0 {
+        SafeMath::loss_percent(
+            commitment.amount,
+            SafeMath::add(commitment.current_value, commitment.allocated_amount),
+        )
+    } else {
+        0
+    };
+    match read_oracle_valuation(e, &commitment.commitment_id) {
+        Some(valuation) if valuation.entry_value > 0 => value_loss.max(SafeMath::loss_percent(
+            valuation.entry_value,
+            valuation.value,
+        )),
+        _ => value_loss,
+    }
+}
+
+/// Move a commitment, its owner index entry and its per-owner locked principal to `to`.
+fn reassign_owner(e: &Env, commitment: &mut Commitment, to: &Address) {
+    let from = commitment.owner.clone();
+    let active = commitment.status == CommitmentStatus::Active;
+    if active {
+        adjust_commitment_locked(e, commitment, -1);
+    }
+    commitment.owner = to.clone();
+    set_commitment(e, commitment);
+    if active {
+        adjust_commitment_locked(e, commitment, 1);
+    }
+
+    let mut from_ids = read_owner_commitments(e, &from);
+    if let Some(index) = from_ids.first_index_of(&commitment.commitment_id) {
+        from_ids.remove(index);
+    }
+    write_owner_commitments(e, &from, &from_ids);
+    let mut to_ids = read_owner_commitments(e, to);
+    to_ids.push_back(commitment.commitment_id.clone());
+    write_owner_commitments(e, to, &to_ids);
+}
+
+/// Move an Active commitment that breached its loss limit to Violated.
+fn mark_loss_violated(e: &Env, commitment: &mut Commitment, context: &str) {
+    transition_status(e, commitment, CommitmentStatus::Violated, context);
+    set_commitment(e, commitment);
+    e.events().publish(
+        (symbol_short!("Violated"), commitment.commitment_id.clone()),
+        (symbol_short!("RuleViol"), e.ledger().timestamp()),
+    );
+}
+
+/// Persist a new valuation, flag loss violations and keep TotalValueLocked in sync.
+fn apply_valuation(e: &Env, commitment_id: &String, new_value: i128) {
+    Validation::require_non_negative(new_value);
+
+    let mut commitment = read_commitment(e, commitment_id)
+        .unwrap_or_else(|| fail(e, CommitmentError::CommitmentNotFound, "update_value"));
+
+    if commitment.status != CommitmentStatus::Active {
+        fail(e, CommitmentError::NotActive, "update_value");
+    }
+    // Basket values come only from the oracle
+    if commitment.is_basket {
+        fail(e, CommitmentError::MultiAssetNotSupported, "update_value");
+    }
+
+    let old_value = commitment.current_value;
+    commitment.current_value = new_value;
+
+    // Check for violations after updating value
+    if is_loss_violated(e, &commitment) {
+        mark_loss_violated(e, &mut commitment, "update_value");
+    } else {
+        set_commitment(e, &commitment);
+        e.events().publish(
+            (symbol_short!("ValUpd"), commitment_id.clone()),
+            (new_value, e.ledger().timestamp()),
+        );
+    }
+
+    // Adjust TotalValueLocked: TVL -= old_value, TVL += new_value
+    adjust_tvl(e, &commitment, new_value - old_value);
+}
+
+/// Pause the contract
+///
+/// # Arguments
+/// * `e` - The environment
+///
+/// # Panics
+/// Panics if caller is not admin or if contract is already paused
+pub fn pause(e: Env) {
+    let admin = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::Admin)
+        .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "pause"));
+    admin.require_auth();
+    Pausable::pause(&e);
+}
+
+/// Unpause the contract
+///
+/// # Arguments
+/// * `e` - The environment
+///
+/// # Panics
+/// Panics if caller is not admin or if contract is already unpaused
+pub fn unpause(e: Env) {
+    let admin = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::Admin)
+        .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "unpause"));
+    admin.require_auth();
+    Pausable::unpause(&e);
+}
+
+/// Check if the contract is paused
+///
+/// # Arguments
+/// * `e` - The environment
+///
+/// # Returns
+/// `true` if paused, `false` otherwise
+pub fn is_paused(e: Env) -> bool {
+    Pausable::is_paused(&e)
+}
+
+#[contract]
+pub struct CommitmentCoreContract;
+
+#[contractimpl]
+impl CommitmentCoreContract {
+    /// Validate commitment rules using shared utilities
+    fn validate_rules(e: &Env, rules: &CommitmentRules) {
+        // Duration must be > 0
+        Validation::require_valid_duration(rules.duration_days);
+
+        // Max loss percent must be between 0 and 100
+        Validation::require_valid_percent(rules.max_loss_percent);
+
+        // Commitment type must be valid
+        let valid_types = ["safe", "balanced", "aggressive"];
+        Validation::require_valid_commitment_type(e, &rules.commitment_type, &valid_types);
+    }
+
+    /// Generate unique commitment ID
+    /// Optimized: Uses counter to create unique ID efficiently
+    fn generate_commitment_id(e: &Env, counter: u64) -> String {
+        // OPTIMIZATION: Use counter directly as string to minimize allocations
         // This is more gas-efficient than string concatenation
         let mut buf = [0u8; 32];
         let prefix = b"c_";
         buf[0] = prefix[0];
         buf[1] = prefix[1];
 
-        // Convert counter to string representation
-        let mut n = counter;
-        let mut i = 2;
-        if n == 0 {
-            buf[i] = b'0';
-            i += 1;
+        // Convert counter to string representation
+        let mut n = counter;
+        let mut i = 2;
+        if n == 0 {
+            buf[i] = b'0';
+            i += 1;
+        } else {
+            let mut digits = [0u8; 20];
+            let mut digit_count = 0;
+            while n > 0 {
+                digits[digit_count] = (n % 10) as u8 + b'0';
+                n /= 10;
+                digit_count += 1;
+            }
+            // Reverse digits
+            for j in 0..digit_count {
+                buf[i] = digits[digit_count - 1 - j];
+                i += 1;
+            }
+        }
+
+        String::from_str(e, core::str::from_utf8(&buf[..i]).unwrap_or("c_0"))
+    }
+
+    /// Initialize the core commitment contract
+    pub fn initialize(e: Env, admin: Address, nft_contract: Address) {
+        // Check if already initialized
+        if e.storage().instance().has(&DataKey::Admin) {
+            fail(&e, CommitmentError::AlreadyInitialized, "initialize");
+        }
+
+        // Store admin and NFT contract address
+        e.storage().instance().set(&DataKey::Admin, &admin);
+        e.storage()
+            .instance()
+            .set(&DataKey::NftContract, &nft_contract);
+
+        // Initialize total commitments counter
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalCommitments, &0u64);
+
+        // Initialize total value locked counter
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalValueLocked, &0i128);
+
+        // Initialize paused state (default: not paused)
+        e.storage().instance().set(&Pausable::PAUSED_KEY, &false);
+
+        e.storage()
+            .instance()
+            .set(&DataKey::Version, &CURRENT_VERSION);
+    }
+
+    /// Create a new commitment
+    ///
+    /// # Reentrancy Protection
+    /// This function uses checks-effects-interactions pattern:
+    /// 1. Checks: Validate inputs
+    /// 2. Effects: Update state (commitment storage, counters)
+    /// 3. Interactions: External calls (token transfer, NFT mint)
+    /// Reentrancy guard prevents recursive calls.
+    ///
+    /// # Formal Verification
+    /// **Preconditions:**
+    /// - `amount > 0`
+    /// - `rules.duration_days > 0`
+    /// - `rules.max_loss_percent <= 100`
+    /// - `rules.commitment_type ∈ {"safe", "balanced", "aggressive"}`
+    /// - Contract is initialized
+    /// - `reentrancy_guard == false`
+    ///
+    /// **Postconditions:**
+    /// - Returns unique `commitment_id`
+    /// - `get_commitment(commitment_id).owner == owner`
+    /// - `get_commitment(commitment_id).amount == amount - creation_fee`
+    /// - `get_commitment(commitment_id).gross_amount == amount`
+    /// - `get_commitment(commitment_id).status == CommitmentStatus::Active`
+    /// - `get_total_commitments() == old(get_total_commitments()) + 1`
+    /// - `reentrancy_guard == false`
+    ///
+    /// **Invariants Maintained:**
+    /// - INV-1: Total commitments consistency
+    /// - INV-2: Commitment balance conservation
+    /// - INV-3: Owner commitment list consistency
+    /// - INV-4: Reentrancy guard invariant
+    ///
+    /// **Security Properties:**
+    /// - SP-1: Reentrancy protection
+    /// - SP-2: Access control
+    /// - SP-4: State consistency
+    /// - SP-5: Token conservation
+    pub fn create_commitment(
+        e: Env,
+        owner: Address,
+        amount: i128,
+        asset_address: Address,
+        rules: CommitmentRules,
+    ) -> String {
+        Self::create_funded_by(e, owner.clone(), owner, amount, asset_address, rules)
+    }
+
+    /// Create a commitment owned by `beneficiary` and funded by `payer`.
+    ///
+    /// `payer` must authorize and be either the beneficiary or an operator the
+    /// beneficiary approved with `OperatorScope::Create` (or `All`). The beneficiary
+    /// receives the commitment and its NFT; tokens are pulled from `payer`.
+    pub fn create_commitment_for(
+        e: Env,
+        payer: Address,
+        beneficiary: Address,
+        amount: i128,
+        asset_address: Address,
+        rules: CommitmentRules,
+    ) -> String {
+        payer.require_auth();
+        if payer != beneficiary && !is_operator(&e, &beneficiary, &payer, OperatorScope::Create) {
+            fail(&e, CommitmentError::Unauthorized, "create_commitment_for");
+        }
+        let commitment_id = Self::create_funded_by(
+            e.clone(),
+            payer.clone(),
+            beneficiary.clone(),
+            amount,
+            asset_address,
+            rules,
+        );
+        e.events().publish(
+            (
+                Symbol::new(&e, "CreatedFor"),
+                commitment_id.clone(),
+                beneficiary,
+            ),
+            (payer, e.ledger().timestamp()),
+        );
+        commitment_id
+    }
+
+    /// Shared body of `create_commitment` and `create_commitment_for`.
+    fn create_funded_by(
+        e: Env,
+        payer: Address,
+        owner: Address,
+        amount: i128,
+        asset_address: Address,
+        rules: CommitmentRules,
+    ) -> String {
+        // Reentrancy protection
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        // Check if contract is paused
+        Pausable::require_not_paused(&e);
+        EmergencyControl::require_not_emergency(&e);
+
+        // Rate limit: per-owner commitment creation
+        let fn_symbol = symbol_short!("create");
+        RateLimiter::check(&e, &owner, &fn_symbol);
+
+        // Validate amount > 0 using shared utilities
+        Validation::require_positive(amount);
+
+        // Validate rules
+        Self::validate_rules(&e, &rules);
+
+        // Reject duration_days that would cause expires_at to overflow u64
+        let expires_at = TimeUtils::checked_calculate_expiration(&e, rules.duration_days)
+            .unwrap_or_else(|| {
+                set_reentrancy_guard(&e, false);
+                fail(&e, CommitmentError::ExpirationOverflow, "create_commitment")
+            });
+
+        let nft_contract = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::NftContract)
+            .unwrap_or_else(|| {
+                set_reentrancy_guard(&e, false);
+                fail(&e, CommitmentError::NotInitialized, "create_commitment")
+            });
+
+        // Creation fee is taken from the deposit; only the net amount is locked
+        let creation_fee = fees::fee_from_bps(amount, Self::get_creation_fee_bps(e.clone()));
+        let net_amount = amount - creation_fee;
+        require_asset_limits(
+            &e,
+            &owner,
+            &asset_address,
+            net_amount,
+            true,
+            "create_commitment",
+        );
+
+        // EFFECTS: Update state before external calls (expires_at already computed above with overflow check)
+        let commitment = store_new_commitment(
+            &e,
+            &owner,
+            net_amount,
+            amount,
+            &asset_address,
+            &rules,
+            expires_at,
+            "create_commitment",
+        );
+        let commitment_id = commitment.commitment_id.clone();
+        adjust_asset_locked(&e, &owner, &asset_address, net_amount);
+        adjust_tvl(&e, &commitment, net_amount);
+        record_entry_valuation(&e, &commitment_id, &asset_address, net_amount);
+        if creation_fee > 0 {
+            credit_collected_fees(&e, &asset_address, creation_fee);
+        }
+
+        // INTERACTIONS: External calls (token transfer, NFT mint)
+        // Transfer assets from owner to contract
+        let contract_address = e.current_contract_address();
+        transfer_assets(&e, &payer, &contract_address, &asset_address, amount);
+
+        // Mint NFT
+        let nft_token_id = call_nft_mint(
+            &e,
+            &nft_contract,
+            &owner,
+            &commitment_id,
+            &rules,
+            net_amount,
+            &asset_address,
+        );
+
+        // Update commitment with NFT token ID
+        let mut updated_commitment = commitment;
+        updated_commitment.nft_token_id = nft_token_id;
+        set_commitment(&e, &updated_commitment);
+
+        // Clear reentrancy guard
+        set_reentrancy_guard(&e, false);
+
+        // Emit creation event
+        e.events().publish(
+            (
+                symbol_short!("Created"),
+                commitment_id.clone(),
+                owner.clone(),
+            ),
+            (net_amount, rules, nft_token_id, e.ledger().timestamp()),
+        );
+        if creation_fee > 0 {
+            e.events().publish(
+                (
+                    Symbol::new(&e, "CreationFeeCollected"),
+                    commitment_id.clone(),
+                ),
+                (asset_address, creation_fee, e.ledger().timestamp()),
+            );
+        }
+        commitment_id
+    }
+
+    /// Create one commitment, under one NFT, over a basket of assets.
+    ///
+    /// Every `(asset_address, amount)` holding is transferred in, less the creation fee
+    /// per asset. The commitment is marked `is_basket`; its aggregate value, in the price
+    /// oracle's quote units, is recorded as its `OracleValuation` and drives the loss-limit
+    /// check, while `amount`, `gross_amount` and `current_value` stay 0. Baskets count
+    /// towards the basket metrics, not TVL. Requires a configured price oracle.
+    pub fn create_multi_asset_commitment(
+        e: Env,
+        owner: Address,
+        holdings: Vec<(Address, i128)>,
+        rules: CommitmentRules,
+    ) -> String {
+        // Reentrancy protection
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        Pausable::require_not_paused(&e);
+        EmergencyControl::require_not_emergency(&e);
+
+        // Rate limit: shares the per-owner budget with create_commitment
+        let fn_symbol = symbol_short!("create");
+        RateLimiter::check(&e, &owner, &fn_symbol);
+
+        // CHECKS: 2..=MAX_BASKET_ASSETS distinct assets, each with a positive amount
+        if holdings.len() < 2 || holdings.len() > MAX_BASKET_ASSETS {
+            set_reentrancy_guard(&e, false);
+            fail(
+                &e,
+                CommitmentError::InvalidBasket,
+                "create_multi_asset_commitment",
+            );
+        }
+        for (i, (asset_address, amount)) in holdings.iter().enumerate() {
+            if amount <= 0 {
+                set_reentrancy_guard(&e, false);
+                fail(
+                    &e,
+                    CommitmentError::InvalidAmount,
+                    "create_multi_asset_commitment",
+                );
+            }
+            if holdings
+                .iter()
+                .skip(i + 1)
+                .any(|(other, _)| other == asset_address)
+            {
+                set_reentrancy_guard(&e, false);
+                fail(
+                    &e,
+                    CommitmentError::InvalidBasket,
+                    "create_multi_asset_commitment",
+                );
+            }
+        }
+
+        Self::validate_rules(&e, &rules);
+        let expires_at = TimeUtils::checked_calculate_expiration(&e, rules.duration_days)
+            .unwrap_or_else(|| {
+                set_reentrancy_guard(&e, false);
+                fail(
+                    &e,
+                    CommitmentError::ExpirationOverflow,
+                    "create_multi_asset_commitment",
+                )
+            });
+        let nft_contract = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::NftContract)
+            .unwrap_or_else(|| {
+                set_reentrancy_guard(&e, false);
+                fail(
+                    &e,
+                    CommitmentError::NotInitialized,
+                    "create_multi_asset_commitment",
+                )
+            });
+
+        // Creation fee is taken from each holding; aggregate values come from the oracle
+        let fee_bps = Self::get_creation_fee_bps(e.clone());
+        let mut net_holdings = Vec::new(&e);
+        let mut gross_value = 0i128;
+        let mut net_value = 0i128;
+        for (asset_address, amount) in holdings.iter() {
+            let net_amount = fees::net_after_fee_bps(amount, fee_bps);
+            require_asset_limits(
+                &e,
+                &owner,
+                &asset_address,
+                net_amount,
+                true,
+                "create_multi_asset_commitment",
+            );
+            gross_value = SafeMath::add(
+                gross_value,
+                oracle_value(&e, &asset_address, amount, "create_multi_asset_commitment"),
+            );
+            net_value = SafeMath::add(
+                net_value,
+                oracle_value(
+                    &e,
+                    &asset_address,
+                    net_amount,
+                    "create_multi_asset_commitment",
+                ),
+            );
+            net_holdings.push_back((asset_address, net_amount));
+        }
+        if net_value <= 0 {
+            set_reentrancy_guard(&e, false);
+            fail(
+                &e,
+                CommitmentError::InvalidAmount,
+                "create_multi_asset_commitment",
+            );
+        }
+        let (primary_asset, _) = holdings.get_unchecked(0);
+
+        // EFFECTS: Record the commitment, its basket and the fees before external calls
+        let mut commitment = store_new_commitment(
+            &e,
+            &owner,
+            0,
+            0,
+            &primary_asset,
+            &rules,
+            expires_at,
+            "create_multi_asset_commitment",
+        );
+        commitment.is_basket = true;
+        set_commitment(&e, &commitment);
+        let commitment_id = commitment.commitment_id.clone();
+        write_holdings(&e, &commitment_id, &net_holdings);
+        adjust_commitment_locked(&e, &commitment, 1);
+        adjust_value_locked(&e, &None, net_value);
+        write_oracle_valuation(
+            &e,
+            &commitment_id,
+            &OracleValuation {
+                entry_value: net_value,
+                value: net_value,
+                updated_at: e.ledger().timestamp(),
+            },
+        );
+        for ((asset_address, amount), (_, net_amount)) in holdings.iter().zip(net_holdings.iter()) {
+            if amount > net_amount {
+                credit_collected_fees(&e, &asset_address, amount - net_amount);
+            }
+        }
+
+        // INTERACTIONS: Pull in every holding, then mint one NFT for the basket
+        let contract_address = e.current_contract_address();
+        for (asset_address, amount) in holdings.iter() {
+            transfer_assets(&e, &owner, &contract_address, &asset_address, amount);
+        }
+        commitment.nft_token_id =
+            call_nft_mint_basket(&e, &nft_contract, &owner, &commitment_id, &rules);
+        set_commitment(&e, &commitment);
+
+        set_reentrancy_guard(&e, false);
+
+        e.events().publish(
+            (
+                Symbol::new(&e, "MultiAssetCreated"),
+                commitment_id.clone(),
+                owner,
+            ),
+            (
+                net_holdings,
+                net_value,
+                commitment.nft_token_id,
+                e.ledger().timestamp(),
+            ),
+        );
+        commitment_id
+    }
+
+    /// Token holdings locked by a commitment: the basket for multi-asset commitments,
+    /// otherwise the single `(asset_address, amount)`.
+    pub fn get_holdings(e: Env, commitment_id: String) -> Vec<(Address, i128)> {
+        if let Some(holdings) = read_holdings(&e, &commitment_id) {
+            return holdings;
+        }
+        let commitment = read_commitment(&e, &commitment_id)
+            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "get_holdings"));
+        Vec::from_array(&e, [(commitment.asset_address, commitment.amount)])
+    }
+
+    /// Get commitment details
+    pub fn get_commitment(e: Env, commitment_id: String) -> Commitment {
+        read_commitment(&e, &commitment_id)
+            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "get_commitment"))
+    }
+
+    /// List all commitment IDs owned by the given address.
+    ///
+    /// This is a convenience wrapper around `get_owner_commitments` with a
+    /// name optimized for off-chain indexers and UIs.
+    pub fn list_commitments_by_owner(e: Env, owner: Address) -> Vec<String> {
+        Self::get_owner_commitments(e, owner)
+    }
+
+    /// Get all commitments for an owner
+    pub fn get_owner_commitments(e: Env, owner: Address) -> Vec<String> {
+        read_owner_commitments(&e, &owner)
+    }
+
+    /// Move a commitment to the new holder of its NFT. Called by the NFT contract
+    /// on every transfer, so the NFT holder is the beneficial owner: they control
+    /// exits and receive settlement proceeds.
+    ///
+    /// The recipient's asset caps are not enforced here, so a transfer can never
+    /// be blocked by core. Tokens core did not mint (no commitment with this ID, or
+    /// one backed by a different token) are ignored, so other minters' NFTs stay
+    /// transferable.
+    pub fn on_nft_transfer(
+        e: Env,
+        commitment_id: String,
+        token_id: u32,
+        from: Address,
+        to: Address,
+    ) {
+        let nft_contract = Self::get_nft_contract(e.clone());
+        nft_contract.require_auth();
+
+        let Some(mut commitment) = read_commitment(&e, &commitment_id) else {
+            return;
+        };
+        if commitment.nft_token_id != token_id {
+            return;
+        }
+        if commitment.owner != from {
+            fail(&e, CommitmentError::Unauthorized, "on_nft_transfer");
+        }
+
+        reassign_owner(&e, &mut commitment, &to);
+
+        e.events().publish(
+            (Symbol::new(&e, "OwnerChanged"), commitment_id),
+            (from, to, e.ledger().timestamp()),
+        );
+    }
+
+    /// Set a commitment's owner to the current holder of its NFT. Admin only.
+    ///
+    /// Repairs commitments whose NFT changed hands before `on_nft_transfer` existed:
+    /// their recorded owner no longer matches the NFT's `from`, so later transfers
+    /// would fail. Does nothing if the owner is already in sync.
+    pub fn resync_owner(e: Env, caller: Address, commitment_id: String) {
+        require_admin(&e, &caller);
+
+        let mut commitment = read_commitment(&e, &commitment_id)
+            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "resync_owner"));
+        let nft_contract = Self::get_nft_contract(e.clone());
+        let holder = e.invoke_contract::<Address>(
+            &nft_contract,
+            &Symbol::new(&e, "owner_of"),
+            Vec::from_array(&e, [commitment.nft_token_id.into_val(&e)]),
+        );
+        if holder == commitment.owner {
+            return;
+        }
+
+        let previous = commitment.owner.clone();
+        reassign_owner(&e, &mut commitment, &holder);
+
+        e.events().publish(
+            (Symbol::new(&e, "OwnerChanged"), commitment_id),
+            (previous, holder, e.ledger().timestamp()),
+        );
+    }
+
+    /// List commitments in creation order. At most `MAX_PAGE_SIZE` records per call.
+    pub fn list_commitments(e: Env, start: u32, limit: u32) -> Vec<Commitment> {
+        let total = e
+            .storage()
+            .instance()
+            .get::<_, u64>(&DataKey::TotalCommitments)
+            .unwrap_or(0);
+        let (from, to) = page_bounds(start, limit, total.min(u32::MAX as u64) as u32);
+        let mut page = Vec::new(&e);
+        for counter in from..to {
+            let id = Self::generate_commitment_id(&e, counter as u64);
+            if let Some(commitment) = read_commitment(&e, &id) {
+                page.push_back(commitment);
+            }
+        }
+        page
+    }
+
+    /// List an owner's commitments in creation order. At most `MAX_PAGE_SIZE` records per call.
+    pub fn list_commitments_by_owner_paged(
+        e: Env,
+        owner: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Commitment> {
+        let ids = read_owner_commitments(&e, &owner);
+        let (from, to) = page_bounds(start, limit, ids.len());
+        let mut page = Vec::new(&e);
+        for i in from..to {
+            if let Some(commitment) = read_commitment(&e, &ids.get_unchecked(i)) {
+                page.push_back(commitment);
+            }
+        }
+        page
+    }
+
+    /// List commitments currently in `status`. At most `MAX_PAGE_SIZE` records per call.
+    ///
+    /// Ordering is not stable: a commitment leaving a status is swap-removed,
+    /// so the last entry takes its slot.
+    pub fn list_commitments_by_status(
+        e: Env,
+        status: CommitmentStatus,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Commitment> {
+        let (from, to) = page_bounds(start, limit, status_count(&e, status));
+        let mut page = Vec::new(&e);
+        for slot in from..to {
+            let id = e
+                .storage()
+                .persistent()
+                .get::<_, String>(&DataKey::StatusEntry(status, slot));
+            if let Some(commitment) = id.and_then(|id| read_commitment(&e, &id)) {
+                page.push_back(commitment);
+            }
+        }
+        page
+    }
+
+    /// Number of commitments currently in `status`.
+    pub fn get_status_count(e: Env, status: CommitmentStatus) -> u32 {
+        status_count(&e, status)
+    }
+
+    /// Extend the TTL of a commitment and every persistent entry it depends on: its
+    /// owner's index and locked-principal counters, its status index slot, rollover
+    /// links, basket holdings, oracle valuation and pool allocations. Callable by
+    /// anyone, so keepers can keep long-dated commitments from being archived.
+    pub fn bump_commitment(e: Env, commitment_id: String) {
+        let key = DataKey::Commitment(commitment_id.clone());
+        let commitment = e
+            .storage()
+            .persistent()
+            .get::<_, Commitment>(&key)
+            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "bump_commitment"));
+        extend_persistent_ttl(&e, &key);
+
+        let mut linked = Vec::from_array(
+            &e,
+            [
+                DataKey::OwnerCommitments(commitment.owner.clone()),
+                DataKey::StatusPosition(commitment_id.clone()),
+                DataKey::Successor(commitment_id.clone()),
+                DataKey::Predecessor(commitment_id.clone()),
+                DataKey::Holdings(commitment_id.clone()),
+                DataKey::ViolationResolved(commitment_id.clone()),
+                DataKey::CommitmentTemplate(commitment_id.clone()),
+                DataKey::OracleValuation(commitment_id.clone()),
+                DataKey::AllocationPools(commitment_id.clone()),
+            ],
+        );
+        if let Some(slot) = e
+            .storage()
+            .persistent()
+            .get::<_, u32>(&DataKey::StatusPosition(commitment_id.clone()))
+        {
+            linked.push_back(DataKey::StatusEntry(commitment.status, slot));
+        }
+        match read_holdings(&e, &commitment_id) {
+            Some(holdings) => {
+                for (asset_address, _) in holdings.iter() {
+                    linked.push_back(DataKey::OwnerAssetLocked(
+                        commitment.owner.clone(),
+                        asset_address,
+                    ));
+                }
+            }
+            None => linked.push_back(DataKey::OwnerAssetLocked(
+                commitment.owner.clone(),
+                commitment.asset_address.clone(),
+            )),
+        }
+        if let Some(pools) = e
+            .storage()
+            .persistent()
+            .get::<_, Vec<Address>>(&DataKey::AllocationPools(commitment_id.clone()))
+        {
+            for pool in pools.iter() {
+                linked.push_back(DataKey::Allocation(commitment_id.clone(), pool));
+            }
+        }
+
+        for linked in linked.iter() {
+            if e.storage().persistent().has(&linked) {
+                extend_persistent_ttl(&e, &linked);
+            }
+        }
+    }
+
+    /// Get the commitment this one was rolled into, if any.
+    pub fn get_successor(e: Env, commitment_id: String) -> Option<String> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Successor(commitment_id))
+    }
+
+    /// Get the commitment this one was rolled from, if any.
+    pub fn get_predecessor(e: Env, commitment_id: String) -> Option<String> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Predecessor(commitment_id))
+    }
+
+    /// Get total number of commitments
+    pub fn get_total_commitments(e: Env) -> u64 {
+        e.storage()
+            .instance()
+            .get::<_, u64>(&DataKey::TotalCommitments)
+            .unwrap_or(0)
+    }
+
+    /// Get total value locked across all active commitments.
+    ///
+    /// This sums every asset's units together; use `get_asset_metrics` or
+    /// `get_protocol_metrics` for a per-asset breakdown.
+    pub fn get_total_value_locked(e: Env) -> i128 {
+        e.storage()
+            .instance()
+            .get::<_, i128>(&DataKey::TotalValueLocked)
+            .unwrap_or(0)
+    }
+
+    /// Get value locked and cumulative settled/exited volume for one asset.
+    pub fn get_asset_metrics(e: Env, asset_address: Address) -> AssetMetrics {
+        read_asset_metrics(&e, &Some(asset_address))
+    }
+
+    /// Snapshot of commitment counts by status and per-asset value flows.
+    pub fn get_protocol_metrics(e: Env) -> ProtocolMetrics {
+        let mut assets = Vec::new(&e);
+        for asset_address in read_metric_assets(&e).iter() {
+            let metrics = read_asset_metrics(&e, &Some(asset_address.clone()));
+            assets.push_back((asset_address, metrics));
+        }
+        ProtocolMetrics {
+            total_commitments: Self::get_total_commitments(e.clone()),
+            active_count: status_count(&e, CommitmentStatus::Active),
+            settled_count: status_count(&e, CommitmentStatus::Settled),
+            violated_count: status_count(&e, CommitmentStatus::Violated),
+            early_exit_count: status_count(&e, CommitmentStatus::EarlyExit),
+            assets,
+            basket: read_asset_metrics(&e, &None),
+        }
+    }
+
+    /// Get admin address
+    pub fn get_admin(e: Env) -> Address {
+        e.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::Admin)
+            .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "get_admin"))
+    }
+
+    /// Get NFT contract address
+    pub fn get_nft_contract(e: Env) -> Address {
+        e.storage()
+            .instance()
+            .get::<_, Address>(&DataKey::NftContract)
+            .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "get_nft_contract"))
+    }
+
+    /// Update commitment value (called by allocation logic or an off-chain valuation keeper).
+    /// Persists new_value to commitment.current_value and updates TotalValueLocked.
+    /// Caller must be the admin or a registered valuation updater.
+    pub fn update_value(e: Env, caller: Address, commitment_id: String, new_value: i128) {
+        caller.require_auth();
+        if !is_valuation_updater(&e, &caller) {
+            fail(&e, CommitmentError::Unauthorized, "update_value");
+        }
+
+        // Global per-function rate limit (per contract instance)
+        let fn_symbol = symbol_short!("upd_val");
+        let contract_address = e.current_contract_address();
+        RateLimiter::check(&e, &contract_address, &fn_symbol);
+
+        apply_valuation(&e, &commitment_id, new_value);
+    }
+
+    /// Revalue a commitment from the configured price oracle and enforce its loss limit.
+    ///
+    /// The oracle valuation is `amount * price / 10^decimals` in the oracle's quote units,
+    /// summed over the holdings for baskets. It is stored apart from `current_value`,
+    /// which stays in the committed asset's units and is what payouts read; the loss
+    /// check compares it to the entry valuation recorded at creation (or on the first
+    /// refresh when no price was available then). Callable by anyone since the value is
+    /// never caller-supplied; stale or missing prices are rejected.
+    pub fn refresh_value_from_oracle(e: Env, commitment_id: String) -> i128 {
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            fail(
+                &e,
+                CommitmentError::CommitmentNotFound,
+                "refresh_value_from_oracle",
+            )
+        });
+        if commitment.status != CommitmentStatus::Active {
+            fail(&e, CommitmentError::NotActive, "refresh_value_from_oracle");
+        }
+
+        // Baskets are valued as the sum of their holdings
+        let value = match read_holdings(&e, &commitment_id) {
+            Some(holdings) => holdings.iter().fold(0, |total, (asset_address, amount)| {
+                SafeMath::add(
+                    total,
+                    oracle_value(&e, &asset_address, amount, "refresh_value_from_oracle"),
+                )
+            }),
+            None => oracle_value(
+                &e,
+                &commitment.asset_address,
+                commitment.amount,
+                "refresh_value_from_oracle",
+            ),
+        };
+        let previous = read_oracle_valuation(&e, &commitment_id);
+        let entry_value = previous
+            .as_ref()
+            .map(|valuation| valuation.entry_value)
+            .unwrap_or(value);
+        if commitment.is_basket {
+            let old_value = previous.map(|valuation| valuation.value).unwrap_or(0);
+            adjust_value_locked(&e, &None, value - old_value);
+        }
+        write_oracle_valuation(
+            &e,
+            &commitment_id,
+            &OracleValuation {
+                entry_value,
+                value,
+                updated_at: e.ledger().timestamp(),
+            },
+        );
+
+        if is_loss_violated(&e, &commitment) {
+            mark_loss_violated(&e, &mut commitment, "refresh_value_from_oracle");
+        } else {
+            e.events().publish(
+                (Symbol::new(&e, "OracleValUpd"), commitment_id),
+                (value, entry_value, e.ledger().timestamp()),
+            );
+        }
+        value
+    }
+
+    /// Latest oracle valuation of a commitment, in quote units. None until an entry
+    /// valuation has been recorded.
+    pub fn get_oracle_valuation(e: Env, commitment_id: String) -> Option<OracleValuation> {
+        read_oracle_valuation(&e, &commitment_id)
+    }
+
+    /// Check if commitment rules are violated
+    /// Returns true if the loss limit is breached, the same test `sweep_violations`
+    /// applies. Expiry is not a violation: matured commitments are settled.
+    ///
+    /// # Formal Verification
+    /// **Preconditions:**
+    /// - `commitment_id` exists
+    ///
+    /// **Postconditions:**
+    /// - Returns `true` if `loss_percent > max_loss_percent`
+    /// - Returns `false` otherwise
+    /// - Pure function (no state changes)
+    ///
+    /// **Invariants Maintained:**
+    /// - INV-2: Commitment balance conservation
+    ///
+    /// **Security Properties:**
+    /// - SP-4: State consistency (read-only)
+    pub fn check_violations(e: Env, commitment_id: String) -> bool {
+        let commitment = read_commitment(&e, &commitment_id)
+            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "check_violations"));
+
+        // Skip check if already settled or violated
+        if commitment.status != CommitmentStatus::Active {
+            return false; // Already processed
+        }
+
+        let violated = is_loss_violated(&e, &commitment);
+
+        if violated {
+            // Emit violation event
+            e.events().publish(
+                (symbol_short!("Violated"), commitment_id),
+                (symbol_short!("RuleViol"), e.ledger().timestamp()),
+            );
+        }
+
+        violated
+    }
+
+    /// Get detailed violation information, including where the commitment sits
+    /// relative to its post-expiry grace window. `has_violations` matches
+    /// `check_violations`; `expired` alone does not make a commitment violated.
+    pub fn get_violation_details(e: Env, commitment_id: String) -> ViolationDetails {
+        let commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            fail(
+                &e,
+                CommitmentError::CommitmentNotFound,
+                "get_violation_details",
+            )
+        });
+
+        let current_time = e.ledger().timestamp();
+
+        // Calculate loss percentage
+        let loss_percent = loss_percent(&e, &commitment);
+
+        // Check loss limit violation
+        let max_loss = commitment.rules.max_loss_percent as i128;
+        let loss_violated = loss_percent > max_loss;
+
+        // Calculate time remaining (0 if expired)
+        let time_remaining = commitment.expires_at.saturating_sub(current_time);
+
+        ViolationDetails {
+            has_violations: loss_violated,
+            loss_violated,
+            expired: current_time >= commitment.expires_at,
+            loss_percent,
+            time_remaining,
+            grace_state: grace_state(&commitment, current_time),
+            grace_ends_at: grace_period_end(&commitment),
+        }
+    }
+
+    /// Walk a page of the `Active` status index and mark every commitment that has
+    /// breached its loss limit as `Violated`. The NFT is marked inactive alongside.
+    /// Matured commitments are left alone: past their grace window a keeper closes
+    /// them out through `settle`, which charges the performance fee.
+    ///
+    /// `start`/`limit` address slots of the `Active` list as seen before the sweep;
+    /// marked commitments leave that list, so a keeper should rescan from the same
+    /// `start` until a page marks nothing. `success_count` is the number marked;
+    /// commitments whose NFT call failed are reported in `errors` and left active.
+    ///
+    /// If a sweep reward is configured, the caller is paid `amount` per commitment
+    /// marked, out of the keeper budget funded through `fund_keeper_budget`.
+    pub fn sweep_violations(e: Env, caller: Address, start: u32, limit: u32) -> BatchResultVoid {
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+        Pausable::require_not_paused(&e);
+        caller.require_auth();
+
+        // Snapshot the page first: marking swap-removes entries from the Active list
+        let (from, to) = page_bounds(start, limit, status_count(&e, CommitmentStatus::Active));
+        let mut ids = Vec::new(&e);
+        for slot in from..to {
+            if let Some(id) = e
+                .storage()
+                .persistent()
+                .get::<_, String>(&DataKey::StatusEntry(CommitmentStatus::Active, slot))
+            {
+                ids.push_back(id);
+            }
+        }
+
+        let nft_contract = Self::get_nft_contract(e.clone());
+        let current_time = e.ledger().timestamp();
+        let mut marked = 0u32;
+        let mut errors = Vec::new(&e);
+
+        for (index, commitment_id) in ids.iter().enumerate() {
+            let mut commitment = match read_commitment(&e, &commitment_id) {
+                Some(c) if c.status == CommitmentStatus::Active => c,
+                _ => continue,
+            };
+            if !is_loss_violated(&e, &commitment) {
+                continue;
+            }
+
+            let nft_result = e.try_invoke_contract::<(), soroban_sdk::Error>(
+                &nft_contract,
+                &Symbol::new(&e, "mark_inactive"),
+                Vec::from_array(&e, [commitment.nft_token_id.into_val(&e)]),
+            );
+            if !matches!(nft_result, Ok(Ok(()))) {
+                errors.push_back(BatchError {
+                    index: index as u32,
+                    error_code: CommitmentError::NftCallFailed as u32,
+                    context: String::from_str(&e, "nft_mark_inactive"),
+                });
+                continue;
+            }
+
+            transition_status(
+                &e,
+                &mut commitment,
+                CommitmentStatus::Violated,
+                "sweep_violations",
+            );
+            set_commitment(&e, &commitment);
+            marked += 1;
+
+            e.events().publish(
+                (symbol_short!("Violated"), commitment_id),
+                (symbol_short!("Swept"), current_time),
+            );
+        }
+
+        let reward = pay_sweep_reward(&e, &caller, marked);
+        set_reentrancy_guard(&e, false);
+
+        e.events().publish(
+            (Symbol::new(&e, "ViolationsSwept"), caller),
+            (marked, reward, current_time),
+        );
+
+        BatchResultVoid::partial(marked, errors)
+    }
+
+    /// Settle commitment at maturity
+    ///
+    /// Between `expires_at` and `expires_at + grace_period_days` only the owner may
+    /// settle. Once the grace window has elapsed any keeper may settle on the owner's
+    /// behalf; a non-owner caller then earns the configured keeper fee, deducted from
+    /// the settlement amount. Proceeds always go to the owner.
+    ///
+    /// # Reentrancy Protection
+    /// Uses checks-effects-interactions pattern with reentrancy guard.
+    pub fn settle(e: Env, commitment_id: String, caller: Address) {
+        // Reentrancy protection
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        // Check if contract is paused
+        Pausable::require_not_paused(&e);
+
+        caller.require_auth();
+
+        // CHECKS: Get and validate commitment
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "settle")
+        });
+
+        // Verify commitment is expired
+        let current_time = e.ledger().timestamp();
+        if current_time < commitment.expires_at {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotExpired, "settle");
+        }
+
+        // Verify commitment is active
+        if commitment.status != CommitmentStatus::Active {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotActive, "settle");
+        }
+
+        // Only the owner may settle during the grace window
+        let is_owner = caller == commitment.owner;
+        if !is_owner && grace_state(&commitment, current_time) == GraceState::InGracePeriod {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::GracePeriodActive, "settle");
+        }
+
+        // Principal still in pools must be deallocated before it can be paid out
+        if commitment.allocated_amount > 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::FundsAllocated, "settle");
+        }
+
+        // EFFECTS: Update state before external calls
+        // Principal plus yield, less the protocol's performance fee on the yield
+        let settlement_amount = commitment.current_value;
+        let performance_fee = collect_performance_fee(&e, &commitment, commitment.yield_earned);
+        let payout = SafeMath::sub(settlement_amount, performance_fee);
+        let keeper_bps = if is_owner {
+            0
+        } else {
+            Self::get_keeper_fee_bps(e.clone())
+        };
+        let keeper_fee = if payout > 0 {
+            fees::fee_from_bps(payout, keeper_bps)
+        } else {
+            0
+        };
+        let owner_amount = SafeMath::sub(payout, keeper_fee);
+        transition_status(&e, &mut commitment, CommitmentStatus::Settled, "settle");
+        set_commitment(&e, &commitment);
+
+        // Decrease total value locked
+        release_tvl(&e, &commitment, settlement_amount, ValueExit::Settled);
+
+        // INTERACTIONS: External calls (token transfer, NFT settlement)
+        // Transfer assets back to owner (and keeper fee to the settling keeper)
+        let contract_address = e.current_contract_address();
+        let mut basket_payout = None;
+        if let Some(holdings) = read_holdings(&e, &commitment_id) {
+            // Baskets pay out every asset, keeping back the keeper's share of each holding
+            let payout = pay_out_holdings(&e, &holdings, &commitment.owner, |amount| {
+                fees::fee_from_bps(amount, keeper_bps)
+            });
+            for (asset_address, fee) in payout.kept.iter() {
+                if fee > 0 {
+                    token::Client::new(&e, &asset_address).transfer(
+                        &contract_address,
+                        &caller,
+                        &fee,
+                    );
+                }
+            }
+            basket_payout = Some((holdings, payout));
+        } else {
+            let token_client = token::Client::new(&e, &commitment.asset_address);
+            token_client.transfer(&contract_address, &commitment.owner, &owner_amount);
+            if keeper_fee > 0 {
+                token_client.transfer(&contract_address, &caller, &keeper_fee);
+            }
+        }
+
+        // Call NFT contract to mark NFT as settled
+        let nft_contract = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::NftContract)
+            .unwrap_or_else(|| {
+                set_reentrancy_guard(&e, false);
+                fail(&e, CommitmentError::NotInitialized, "settle")
+            });
+
+        let mut args = Vec::new(&e);
+        args.push_back(commitment.nft_token_id.into_val(&e));
+        e.invoke_contract::<()>(&nft_contract, &Symbol::new(&e, "settle"), args);
+
+        // Clear reentrancy guard
+        set_reentrancy_guard(&e, false);
+
+        // Emit settlement event; baskets report each asset where single-asset
+        // commitments report one amount
+        let timestamp = e.ledger().timestamp();
+        match basket_payout {
+            Some((holdings, payout)) => {
+                if keeper_bps > 0 {
+                    e.events().publish(
+                        (
+                            Symbol::new(&e, "KeeperRewarded"),
+                            commitment_id.clone(),
+                            caller,
+                        ),
+                        (payout.kept, timestamp),
+                    );
+                }
+                e.events().publish(
+                    (symbol_short!("Settled"), commitment_id),
+                    (holdings, timestamp),
+                );
+            }
+            None => {
+                if keeper_fee > 0 {
+                    e.events().publish(
+                        (
+                            Symbol::new(&e, "KeeperRewarded"),
+                            commitment_id.clone(),
+                            caller,
+                        ),
+                        (keeper_fee, timestamp),
+                    );
+                }
+                e.events().publish(
+                    (symbol_short!("Settled"), commitment_id),
+                    (settlement_amount, timestamp),
+                );
+            }
+        }
+    }
+
+    /// Roll a matured commitment into a new term without moving tokens out.
+    ///
+    /// The old commitment is settled in place (status `Settled`, NFT settled) and its
+    /// `current_value` becomes the locked amount of a successor created under
+    /// `new_rules`, with a freshly minted NFT. No creation fee is charged. The two are
+    /// linked through `get_successor` / `get_predecessor`. Owner only.
+    pub fn rollover(e: Env, commitment_id: String, new_rules: CommitmentRules) -> String {
+        // Reentrancy protection
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        Pausable::require_not_paused(&e);
+        EmergencyControl::require_not_emergency(&e);
+
+        // CHECKS: Get and validate commitment
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "rollover")
+        });
+        commitment.owner.require_auth();
+
+        if e.ledger().timestamp() < commitment.expires_at {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotExpired, "rollover");
+        }
+        if commitment.status != CommitmentStatus::Active {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotActive, "rollover");
+        }
+        require_single_asset(&e, &commitment_id, "rollover");
+
+        Self::validate_rules(&e, &new_rules);
+        let expires_at = TimeUtils::checked_calculate_expiration(&e, new_rules.duration_days)
+            .unwrap_or_else(|| {
+                set_reentrancy_guard(&e, false);
+                fail(&e, CommitmentError::ExpirationOverflow, "rollover")
+            });
+
+        if commitment.allocated_amount > 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::FundsAllocated, "rollover");
+        }
+        let settlement_amount = commitment.current_value;
+        if settlement_amount <= 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InvalidAmount, "rollover");
+        }
+
+        let nft_contract = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::NftContract)
+            .unwrap_or_else(|| {
+                set_reentrancy_guard(&e, false);
+                fail(&e, CommitmentError::NotInitialized, "rollover")
+            });
+
+        // EFFECTS: Close the old commitment's accounting as a settlement
+        let rolled_amount =
+            settlement_amount - collect_performance_fee(&e, &commitment, commitment.yield_earned);
+        transition_status(&e, &mut commitment, CommitmentStatus::Settled, "rollover");
+        set_commitment(&e, &commitment);
+        release_tvl(&e, &commitment, settlement_amount, ValueExit::Settled);
+
+        // The old principal is released above, so caps see only the rolled amount
+        require_asset_limits(
+            &e,
+            &commitment.owner,
+            &commitment.asset_address,
+            rolled_amount,
+            true,
+            "rollover",
+        );
+
+        // Open the successor over the same tokens and link the two
+        let mut successor = store_new_commitment(
+            &e,
+            &commitment.owner,
+            rolled_amount,
+            rolled_amount,
+            &commitment.asset_address,
+            &new_rules,
+            expires_at,
+            "rollover",
+        );
+        let successor_id = successor.commitment_id.clone();
+        adjust_commitment_locked(&e, &successor, 1);
+        adjust_tvl(&e, &successor, rolled_amount);
+        record_entry_valuation(&e, &successor_id, &successor.asset_address, rolled_amount);
+        let successor_key = DataKey::Successor(commitment_id.clone());
+        e.storage().persistent().set(&successor_key, &successor_id);
+        extend_persistent_ttl(&e, &successor_key);
+        let predecessor_key = DataKey::Predecessor(successor_id.clone());
+        e.storage()
+            .persistent()
+            .set(&predecessor_key, &commitment_id);
+        extend_persistent_ttl(&e, &predecessor_key);
+
+        // INTERACTIONS: Settle the old NFT and mint one for the successor
+        let mut args = Vec::new(&e);
+        args.push_back(commitment.nft_token_id.into_val(&e));
+        e.invoke_contract::<()>(&nft_contract, &Symbol::new(&e, "settle"), args);
+
+        successor.nft_token_id = call_nft_mint(
+            &e,
+            &nft_contract,
+            &successor.owner,
+            &successor_id,
+            &new_rules,
+            rolled_amount,
+            &successor.asset_address,
+        );
+        set_commitment(&e, &successor);
+
+        set_reentrancy_guard(&e, false);
+
+        e.events().publish(
+            (
+                Symbol::new(&e, "RolledOver"),
+                commitment_id,
+                successor_id.clone(),
+            ),
+            (
+                rolled_amount,
+                successor.nft_token_id,
+                e.ledger().timestamp(),
+            ),
+        );
+        successor_id
+    }
+
+    pub fn early_exit(e: Env, commitment_id: String, caller: Address) {
+        // Reentrancy protection
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        // Check if contract is paused
+        Pausable::require_not_paused(&e);
+
+        // CHECKS: Get and validate commitment
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "early_exit")
+        });
+
+        // Verify caller is the owner or an approved operator
+        require_owner_or_operator(
+            &e,
+            &commitment.owner,
+            &caller,
+            OperatorScope::Exit,
+            "early_exit",
+        );
+
+        // Verify commitment is active
+        if commitment.status != CommitmentStatus::Active {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotActive, "early_exit");
+        }
+
+        // Principal in pools would be forfeited by exiting now
+        if commitment.allocated_amount > 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::FundsAllocated, "early_exit");
+        }
+
+        // EFFECTS: Performance fee on realized yield, then the penalty on what remains
+        let original_value = commitment.current_value;
+        let performance_fee = collect_performance_fee(&e, &commitment, commitment.yield_earned);
+        let net_value = SafeMath::sub(original_value, performance_fee);
+        let penalty_amount =
+            SafeMath::penalty_amount(net_value, commitment.rules.early_exit_penalty);
+        let returned_amount = SafeMath::sub(net_value, penalty_amount);
+
+        // Update commitment status to early_exit
+        transition_status(
+            &e,
+            &mut commitment,
+            CommitmentStatus::EarlyExit,
+            "early_exit",
+        );
+        commitment.current_value = 0; // All value has been distributed
+        set_commitment(&e, &commitment);
+
+        // Decrease total value locked by full current value (no longer locked)
+        release_tvl(&e, &commitment, original_value, ValueExit::Exited);
+
+        let mut basket_payout = None;
+        if let Some(holdings) = read_holdings(&e, &commitment_id) {
+            // Baskets return every asset less the same penalty percentage; the
+            // withheld share of each asset is credited to the treasury ledger
+            let penalty_percent = commitment.rules.early_exit_penalty;
+            let payout = pay_out_holdings(&e, &holdings, &commitment.owner, |amount| {
+                SafeMath::penalty_amount(amount, penalty_percent)
+            });
+            for (asset_address, penalty) in payout.kept.iter() {
+                credit_penalty(&e, &commitment_id, &asset_address, penalty);
+            }
+            basket_payout = Some(payout);
+        } else {
+            // Credit the penalty to the protocol treasury ledger (tokens stay in the contract)
+            credit_penalty(
+                &e,
+                &commitment_id,
+                &commitment.asset_address,
+                penalty_amount,
+            );
+
+            // INTERACTIONS: External calls (token transfer)
+            // Transfer remaining amount (after penalty) to owner
+            let contract_address = e.current_contract_address();
+            let token_client = token::Client::new(&e, &commitment.asset_address);
+
+            if returned_amount > 0 {
+                token_client.transfer(&contract_address, &commitment.owner, &returned_amount);
+            }
+        }
+
+        // Call NFT contract to mark as inactive (early exited, not settled)
+        let nft_contract = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::NftContract)
+            .unwrap_or_else(|| {
+                set_reentrancy_guard(&e, false);
+                fail(&e, CommitmentError::NotInitialized, "early_exit")
+            });
+
+        // Call mark_inactive on NFT instead of settle (since not expired)
+        let mut args = Vec::new(&e);
+        args.push_back(commitment.nft_token_id.into_val(&e));
+        e.invoke_contract::<()>(&nft_contract, &Symbol::new(&e, "mark_inactive"), args);
+
+        // Clear reentrancy guard
+        set_reentrancy_guard(&e, false);
+
+        // Emit early exit event with detailed information; baskets report the
+        // penalty and returned amount of each asset
+        let topics = (
+            symbol_short!("EarlyExt"),
+            commitment_id.clone(),
+            caller.clone(),
+        );
+        match basket_payout {
+            Some(payout) => e
+                .events()
+                .publish(topics, (payout.kept, payout.paid, e.ledger().timestamp())),
+            None => e.events().publish(
+                topics,
+                (penalty_amount, returned_amount, e.ledger().timestamp()),
+            ),
+        }
+    }
+
+    /// Unwind a violated commitment: pay what remains to the owner and mark the NFT
+    /// inactive. Callable by anyone, so keepers can close out violations.
+    ///
+    /// The performance fee on realized yield is taken first, as on `settle`. If the
+    /// commitment is past its loss limit, the configured violation penalty (basis
+    /// points of what remains, or of each basket holding) is then withheld and
+    /// credited to the treasury ledger. Commitments that earlier versions of
+    /// `sweep_violations` marked only for sitting past their grace window are
+    /// returned in full.
+    pub fn resolve_violation(e: Env, commitment_id: String) {
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+        Pausable::require_not_paused(&e);
+
+        // CHECKS
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "resolve_violation")
+        });
+        if commitment.status != CommitmentStatus::Violated {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InvalidStatus, "resolve_violation");
+        }
+        let resolved_key = DataKey::ViolationResolved(commitment_id.clone());
+        if e.storage().persistent().has(&resolved_key) {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::AlreadyResolved, "resolve_violation");
+        }
+        if commitment.allocated_amount > 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::FundsAllocated, "resolve_violation");
+        }
+
+        // EFFECTS
+        let penalty_bps = if is_loss_violated(&e, &commitment) {
+            Self::get_violation_penalty_bps(e.clone())
+        } else {
+            0
+        };
+        let remaining_value = commitment.current_value;
+        let performance_fee = collect_performance_fee(&e, &commitment, commitment.yield_earned);
+        let net_value = SafeMath::sub(remaining_value, performance_fee);
+        let penalty_amount = fees::fee_from_bps(net_value, penalty_bps);
+        let returned_amount = SafeMath::sub(net_value, penalty_amount);
+
+        commitment.current_value = 0;
+        set_commitment(&e, &commitment);
+        e.storage()
+            .persistent()
+            .set(&resolved_key, &e.ledger().timestamp());
+        extend_persistent_ttl(&e, &resolved_key);
+
+        release_tvl(&e, &commitment, remaining_value, ValueExit::Exited);
+
+        // INTERACTIONS
+        let mut basket_payout = None;
+        if let Some(holdings) = read_holdings(&e, &commitment_id) {
+            let payout = pay_out_holdings(&e, &holdings, &commitment.owner, |amount| {
+                fees::fee_from_bps(amount, penalty_bps)
+            });
+            for (asset_address, penalty) in payout.kept.iter() {
+                credit_penalty(&e, &commitment_id, &asset_address, penalty);
+            }
+            basket_payout = Some(payout);
+        } else {
+            credit_penalty(
+                &e,
+                &commitment_id,
+                &commitment.asset_address,
+                penalty_amount,
+            );
+            if returned_amount > 0 {
+                let contract_address = e.current_contract_address();
+                transfer_assets(
+                    &e,
+                    &contract_address,
+                    &commitment.owner,
+                    &commitment.asset_address,
+                    returned_amount,
+                );
+            }
+        }
+
+        // sweep_violations already marks the NFT inactive; update_value does not
+        let nft_contract = Self::get_nft_contract(e.clone());
+        let token_arg = Vec::from_array(&e, [commitment.nft_token_id.into_val(&e)]);
+        if e.invoke_contract::<bool>(
+            &nft_contract,
+            &Symbol::new(&e, "is_active"),
+            token_arg.clone(),
+        ) {
+            e.invoke_contract::<()>(&nft_contract, &Symbol::new(&e, "mark_inactive"), token_arg);
+        }
+
+        set_reentrancy_guard(&e, false);
+
+        // Baskets report the penalty and returned amount of each asset
+        let topics = (Symbol::new(&e, "ViolationResolved"), commitment_id);
+        match basket_payout {
+            Some(payout) => e
+                .events()
+                .publish(topics, (payout.kept, payout.paid, e.ledger().timestamp())),
+            None => e.events().publish(
+                topics,
+                (penalty_amount, returned_amount, e.ledger().timestamp()),
+            ),
+        }
+    }
+
+    /// Whether `resolve_violation` has already paid out this commitment.
+    pub fn is_violation_resolved(e: Env, commitment_id: String) -> bool {
+        e.storage()
+            .persistent()
+            .has(&DataKey::ViolationResolved(commitment_id))
+    }
+
+    /// Add funds to an active commitment.
+    ///
+    /// Transfers `amount` from the owner, increases both `amount` and `current_value`,
+    /// adds it to `TotalValueLocked` and syncs the NFT's recorded value.
+    ///
+    /// # Reentrancy Protection
+    /// Uses checks-effects-interactions pattern with reentrancy guard.
+    pub fn top_up(e: Env, commitment_id: String, caller: Address, amount: i128) {
+        // Reentrancy protection
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        // Check if contract is paused
+        Pausable::require_not_paused(&e);
+        EmergencyControl::require_not_emergency(&e);
+
+        // CHECKS: Get and validate commitment
+        if amount <= 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InvalidAmount, "top_up");
+        }
+
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "top_up")
+        });
+
+        // Verify caller is the owner or an approved operator
+        require_owner_or_operator(
+            &e,
+            &commitment.owner,
+            &caller,
+            OperatorScope::TopUp,
+            "top_up",
+        );
+
+        // Verify commitment is active
+        if commitment.status != CommitmentStatus::Active {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotActive, "top_up");
+        }
+        require_single_asset(&e, &commitment_id, "top_up");
+        require_asset_limits(
+            &e,
+            &commitment.owner,
+            &commitment.asset_address,
+            amount,
+            false,
+            "top_up",
+        );
+
+        // EFFECTS: Grow principal and value together so the loss ratio is unchanged
+        commitment.amount = SafeMath::add(commitment.amount, amount);
+        commitment.current_value = SafeMath::add(commitment.current_value, amount);
+        set_commitment(&e, &commitment);
+        adjust_asset_locked(&e, &commitment.owner, &commitment.asset_address, amount);
+
+        adjust_tvl(&e, &commitment, amount);
+
+        // INTERACTIONS: Pull funds from the caller and sync the NFT
+        let contract_address = e.current_contract_address();
+        transfer_assets(
+            &e,
+            &caller,
+            &contract_address,
+            &commitment.asset_address,
+            amount,
+        );
+
+        let nft_contract = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::NftContract)
+            .unwrap_or_else(|| {
+                set_reentrancy_guard(&e, false);
+                fail(&e, CommitmentError::NotInitialized, "top_up")
+            });
+        call_nft_update_value(
+            &e,
+            &nft_contract,
+            commitment.nft_token_id,
+            commitment.current_value,
+        );
+
+        // Clear reentrancy guard
+        set_reentrancy_guard(&e, false);
+
+        e.events().publish(
+            (symbol_short!("TopUp"), commitment_id, caller),
+            (amount, commitment.current_value, e.ledger().timestamp()),
+        );
+    }
+
+    /// Withdraw part of an active commitment before maturity.
+    ///
+    /// The early-exit penalty is applied pro rata to the withdrawn `amount` and credited
+    /// to the treasury ledger. `current_value` drops by `amount` and the principal
+    /// (`amount` field) drops proportionally, so the loss ratio used for violation
+    /// checks is unchanged. Withdrawing the full value must go through `early_exit`.
+    ///
+    /// # Reentrancy Protection
+    /// Uses checks-effects-interactions pattern with reentrancy guard.
+    pub fn partial_exit(e: Env, commitment_id: String, caller: Address, amount: i128) {
+        // Reentrancy protection
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        // Check if contract is paused
+        Pausable::require_not_paused(&e);
+
+        // CHECKS: Get and validate commitment
+        if amount <= 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InvalidAmount, "partial_exit");
+        }
+
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "partial_exit")
+        });
+
+        // Verify caller is the owner or an approved operator
+        require_owner_or_operator(
+            &e,
+            &commitment.owner,
+            &caller,
+            OperatorScope::Exit,
+            "partial_exit",
+        );
+
+        // Verify commitment is active
+        if commitment.status != CommitmentStatus::Active {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotActive, "partial_exit");
+        }
+        require_single_asset(&e, &commitment_id, "partial_exit");
+
+        // Must leave something locked; a full withdrawal is an early exit
+        if amount >= commitment.current_value {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InsufficientBalance, "partial_exit");
+        }
+
+        // EFFECTS: Performance fee on the withdrawn share of yield, pro-rated penalty
+        // and proportional principal and yield reduction
+        let yield_reduction = SafeMath::div(
+            SafeMath::mul(commitment.yield_earned, amount),
+            commitment.current_value,
+        );
+        let performance_fee = collect_performance_fee(&e, &commitment, yield_reduction);
+        let net_amount = SafeMath::sub(amount, performance_fee);
+        let penalty_amount =
+            SafeMath::penalty_amount(net_amount, commitment.rules.early_exit_penalty);
+        let returned_amount = SafeMath::sub(net_amount, penalty_amount);
+        let principal_reduction = SafeMath::div(
+            SafeMath::mul(commitment.amount, amount),
+            commitment.current_value,
+        );
+        commitment.amount = SafeMath::sub(commitment.amount, principal_reduction);
+        commitment.yield_earned = SafeMath::sub(commitment.yield_earned, yield_reduction);
+        commitment.current_value = SafeMath::sub(commitment.current_value, amount);
+        set_commitment(&e, &commitment);
+        adjust_asset_locked(
+            &e,
+            &commitment.owner,
+            &commitment.asset_address,
+            -principal_reduction,
+        );
+
+        release_tvl(&e, &commitment, amount, ValueExit::Exited);
+
+        credit_penalty(
+            &e,
+            &commitment_id,
+            &commitment.asset_address,
+            penalty_amount,
+        );
+
+        // INTERACTIONS: Return funds to the owner and sync the NFT
+        let contract_address = e.current_contract_address();
+        let token_client = token::Client::new(&e, &commitment.asset_address);
+        if returned_amount > 0 {
+            token_client.transfer(&contract_address, &commitment.owner, &returned_amount);
+        }
+
+        let nft_contract = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::NftContract)
+            .unwrap_or_else(|| {
+                set_reentrancy_guard(&e, false);
+                fail(&e, CommitmentError::NotInitialized, "partial_exit")
+            });
+        call_nft_update_value(
+            &e,
+            &nft_contract,
+            commitment.nft_token_id,
+            commitment.current_value,
+        );
+
+        // Clear reentrancy guard
+        set_reentrancy_guard(&e, false);
+
+        e.events().publish(
+            (symbol_short!("PartExit"), commitment_id, caller),
+            (
+                amount,
+                penalty_amount,
+                returned_amount,
+                e.ledger().timestamp(),
+            ),
+        );
+    }
+
+    /// Allocate liquidity (called by allocation strategy)
+    ///
+    /// # Reentrancy Protection
+    /// Uses checks-effects-interactions pattern with reentrancy guard.
+    pub fn allocate(e: Env, commitment_id: String, target_pool: Address, amount: i128) {
+        // Reentrancy protection
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        // Check if contract is paused
+        Pausable::require_not_paused(&e);
+        EmergencyControl::require_not_emergency(&e);
+
+        // Rate limit allocations per target pool address
+        let fn_symbol = symbol_short!("alloc");
+        RateLimiter::check(&e, &target_pool, &fn_symbol);
+
+        // CHECKS: Validate inputs and commitment
+        if amount <= 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InvalidAmount, "allocate");
+        }
+
+        let commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "allocate")
+        });
+
+        // Verify commitment is active
+        if commitment.status != CommitmentStatus::Active {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotActive, "allocate");
+        }
+        require_single_asset(&e, &commitment_id, "allocate");
+
+        // Verify sufficient balance
+        if commitment.current_value < amount {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InsufficientBalance, "allocate");
+        }
+
+        // EFFECTS: Update commitment value before external call
+        let mut updated_commitment = commitment;
+        updated_commitment.current_value -= amount;
+        updated_commitment.allocated_amount += amount;
+        set_commitment(&e, &updated_commitment);
+        let allocated = read_allocation(
+            &e,
+            &DataKey::Allocation(commitment_id.clone(), target_pool.clone()),
+        );
+        write_allocation(&e, &commitment_id, &target_pool, allocated + amount);
+
+        // INTERACTIONS: External call (token transfer)
+        // Transfer assets to target pool
+        let contract_address = e.current_contract_address();
+        let token_client = token::Client::new(&e, &updated_commitment.asset_address);
+        token_client.transfer(&contract_address, &target_pool, &amount);
+
+        // Clear reentrancy guard
+        set_reentrancy_guard(&e, false);
+
+        // Emit allocation event
+        e.events().publish(
+            (symbol_short!("Alloc"), commitment_id, target_pool),
+            (amount, e.ledger().timestamp()),
+        );
+    }
+
+    /// Pull allocated principal back from a pool. The pool authorizes the transfer;
+    /// `amount` is capped by what this commitment allocated to that pool. Only while
+    /// the commitment still holds its funds: Active, or Violated and not yet resolved.
+    /// A pool that cannot return everything books the shortfall with `record_loss`.
+    ///
+    /// # Reentrancy Protection
+    /// Uses checks-effects-interactions pattern with reentrancy guard.
+    pub fn deallocate(e: Env, commitment_id: String, pool: Address, amount: i128) {
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        Pausable::require_not_paused(&e);
+        pool.require_auth();
+
+        // CHECKS: Validate inputs, commitment and allocation
+        if amount <= 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InvalidAmount, "deallocate");
+        }
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "deallocate")
+        });
+        if !holds_funds(&e, &commitment) {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotActive, "deallocate");
+        }
+        let allocation_key = DataKey::Allocation(commitment_id.clone(), pool.clone());
+        let allocated = read_allocation(&e, &allocation_key);
+        if amount > allocated {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InsufficientAllocation, "deallocate");
+        }
+
+        // EFFECTS: Principal moves from the pool back into the commitment
+        write_allocation(&e, &commitment_id, &pool, allocated - amount);
+        commitment.allocated_amount -= amount;
+        commitment.current_value += amount;
+        set_commitment(&e, &commitment);
+
+        // INTERACTIONS: Transfer principal back from the pool
+        let contract_address = e.current_contract_address();
+        let token_client = token::Client::new(&e, &commitment.asset_address);
+        token_client.transfer(&pool, &contract_address, &amount);
+
+        set_reentrancy_guard(&e, false);
+
+        e.events().publish(
+            (symbol_short!("Dealloc"), commitment_id, pool),
+            (amount, e.ledger().timestamp()),
+        );
+    }
+
+    /// Book a realized pool loss: `amount` of the principal allocated to `pool` is
+    /// written off instead of returned, so `allocated_amount` can reach zero and the
+    /// commitment can settle or exit with what is left. The loss leaves TVL and never
+    /// comes back into `current_value`. Caller is the pool or the admin (for pools that
+    /// no longer respond).
+    pub fn record_loss(
+        e: Env,
+        caller: Address,
+        commitment_id: String,
+        pool: Address,
+        amount: i128,
+    ) {
+        if caller == pool {
+            caller.require_auth();
         } else {
-            let mut digits = [0u8; 20];
-            let mut digit_count = 0;
-            while n > 0 {
-                digits[digit_count] = (n % 10) as u8 + b'0';
-                n /= 10;
-                digit_count += 1;
-            }
-            // Reverse digits
-            for j in 0..digit_count {
-                buf[i] = digits[digit_count - 1 - j];
-                i += 1;
-            }
+            require_admin(&e, &caller);
         }
+        Pausable::require_not_paused(&e);
 
-        String::from_str(e, core::str::from_utf8(&buf[..i]).unwrap_or("c_0"))
+        // CHECKS: Validate inputs, commitment and allocation
+        if amount <= 0 {
+            fail(&e, CommitmentError::InvalidAmount, "record_loss");
+        }
+        let mut commitment = read_commitment(&e, &commitment_id)
+            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "record_loss"));
+        if !holds_funds(&e, &commitment) {
+            fail(&e, CommitmentError::NotActive, "record_loss");
+        }
+        let allocation_key = DataKey::Allocation(commitment_id.clone(), pool.clone());
+        let allocated = read_allocation(&e, &allocation_key);
+        if amount > allocated {
+            fail(&e, CommitmentError::InsufficientAllocation, "record_loss");
+        }
+
+        // EFFECTS: The written-off principal leaves the allocation and TVL for good
+        write_allocation(&e, &commitment_id, &pool, allocated - amount);
+        commitment.allocated_amount -= amount;
+        set_commitment(&e, &commitment);
+        adjust_tvl(&e, &commitment, -amount);
+
+        e.events().publish(
+            (Symbol::new(&e, "LossRecorded"), commitment_id, pool),
+            (amount, commitment.allocated_amount, e.ledger().timestamp()),
+        );
     }
 
-    /// Initialize the core commitment contract
-    pub fn initialize(e: Env, admin: Address, nft_contract: Address) {
-        // Check if already initialized
-        if e.storage().instance().has(&DataKey::Admin) {
-            fail(&e, CommitmentError::AlreadyInitialized, "initialize");
+    /// Record realized yield returned by a pool. The pool transfers `amount` to this
+    /// contract and it is credited to `yield_earned` (and `current_value`), separate
+    /// from principal. Active commitments only.
+    ///
+    /// # Reentrancy Protection
+    /// Uses checks-effects-interactions pattern with reentrancy guard.
+    pub fn record_return(e: Env, commitment_id: String, pool: Address, amount: i128) {
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
+
+        Pausable::require_not_paused(&e);
+        pool.require_auth();
+
+        // CHECKS: Validate inputs and commitment
+        if amount <= 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::InvalidAmount, "record_return");
+        }
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "record_return")
+        });
+        if commitment.status != CommitmentStatus::Active {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotActive, "record_return");
         }
+        require_single_asset(&e, &commitment_id, "record_return");
 
-        // Store admin and NFT contract address
-        e.storage().instance().set(&DataKey::Admin, &admin);
+        // EFFECTS: Credit yield and grow TVL before the transfer
+        commitment.yield_earned += amount;
+        commitment.current_value += amount;
+        set_commitment(&e, &commitment);
+        adjust_tvl(&e, &commitment, amount);
+
+        // INTERACTIONS: Transfer the yield in from the pool
+        let contract_address = e.current_contract_address();
+        let token_client = token::Client::new(&e, &commitment.asset_address);
+        token_client.transfer(&pool, &contract_address, &amount);
+
+        set_reentrancy_guard(&e, false);
+
+        e.events().publish(
+            (Symbol::new(&e, "YieldRecorded"), commitment_id, pool),
+            (amount, commitment.yield_earned, e.ledger().timestamp()),
+        );
+    }
+
+    /// Principal a commitment currently has allocated to `pool`.
+    pub fn get_allocation(e: Env, commitment_id: String, pool: Address) -> i128 {
+        read_allocation(&e, &DataKey::Allocation(commitment_id, pool))
+    }
+
+    /// Configure rate limits for this contract's functions.
+    ///
+    /// This function is restricted to the contract admin.
+    pub fn set_rate_limit(
+        e: Env,
+        caller: Address,
+        function: Symbol,
+        window_seconds: u64,
+        max_calls: u32,
+    ) {
+        require_admin(&e, &caller);
+        RateLimiter::set_limit(&e, &function, window_seconds, max_calls);
+    }
+
+    /// Set or clear rate limit exemption for an address.
+    ///
+    /// This function is restricted to the contract admin.
+    pub fn set_rate_limit_exempt(e: Env, caller: Address, address: Address, exempt: bool) {
+        require_admin(&e, &caller);
+        RateLimiter::set_exempt(&e, &address, exempt);
+    }
+
+    /// Set the fee (basis points of the settlement amount) paid to a keeper that
+    /// settles a commitment after its grace period. Admin only; capped at
+    /// `MAX_KEEPER_FEE_BPS`.
+    pub fn set_keeper_fee_bps(e: Env, caller: Address, bps: u32) {
+        require_admin(&e, &caller);
+        if bps > MAX_KEEPER_FEE_BPS {
+            fail(&e, CommitmentError::InvalidFeeBps, "set_keeper_fee_bps");
+        }
+        e.storage().instance().set(&DataKey::KeeperFeeBps, &bps);
+        e.events().publish(
+            (Symbol::new(&e, "KeeperFeeSet"), caller),
+            (bps, e.ledger().timestamp()),
+        );
+    }
+
+    /// Get the keeper settlement fee in basis points (0 if not set).
+    pub fn get_keeper_fee_bps(e: Env) -> u32 {
         e.storage()
             .instance()
-            .set(&DataKey::NftContract, &nft_contract);
+            .get(&DataKey::KeeperFeeBps)
+            .unwrap_or(0)
+    }
 
-        // Initialize total commitments counter
+    /// Set the reward paid to `sweep_violations` callers: `amount` of `asset_address`
+    /// per commitment marked, paid from the keeper budget for that asset. An `amount`
+    /// of 0 disables it. Admin only.
+    pub fn set_sweep_reward(e: Env, caller: Address, asset_address: Address, amount: i128) {
+        require_admin(&e, &caller);
+        Validation::require_non_negative(amount);
+        if amount == 0 {
+            e.storage().instance().remove(&DataKey::SweepReward);
+        } else {
+            e.storage()
+                .instance()
+                .set(&DataKey::SweepReward, &(asset_address.clone(), amount));
+        }
+        e.events().publish(
+            (Symbol::new(&e, "SweepRewardSet"), caller),
+            (asset_address, amount, e.ledger().timestamp()),
+        );
+    }
+
+    /// Get the sweep reward as `(asset, amount per commitment)`, if configured.
+    pub fn get_sweep_reward(e: Env) -> Option<(Address, i128)> {
+        e.storage().instance().get(&DataKey::SweepReward)
+    }
+
+    /// Deposit `amount` of `asset_address` from the caller into the keeper budget that
+    /// pays sweep rewards. Kept apart from collected fees and commitment funds. Admin only.
+    pub fn fund_keeper_budget(e: Env, caller: Address, asset_address: Address, amount: i128) {
+        require_admin(&e, &caller);
+        Validation::require_positive(amount);
+
+        let key = DataKey::KeeperBudget(asset_address.clone());
+        let budget = e.storage().instance().get::<_, i128>(&key).unwrap_or(0);
         e.storage()
             .instance()
-            .set(&DataKey::TotalCommitments, &0u64);
+            .set(&key, &SafeMath::add(budget, amount));
 
-        // Initialize total value locked counter
+        let contract_address = e.current_contract_address();
+        transfer_assets(&e, &caller, &contract_address, &asset_address, amount);
+
+        e.events().publish(
+            (Symbol::new(&e, "KeeperBudgetFunded"), caller),
+            (asset_address, amount, e.ledger().timestamp()),
+        );
+    }
+
+    /// Get the remaining keeper budget for an asset (0 if never funded).
+    pub fn get_keeper_budget(e: Env, asset_address: Address) -> i128 {
         e.storage()
             .instance()
-            .set(&DataKey::TotalValueLocked, &0i128);
+            .get(&DataKey::KeeperBudget(asset_address))
+            .unwrap_or(0)
+    }
 
-        // Initialize paused state (default: not paused)
-        e.storage().instance().set(&Pausable::PAUSED_KEY, &false);
+    // ========================================================================
+    // Emergency mode
+    // ========================================================================
+
+    /// Check whether emergency mode is active.
+    pub fn is_emergency_mode(e: Env) -> bool {
+        EmergencyControl::is_emergency_mode(&e)
     }
 
-    /// Create a new commitment
-    ///
-    /// # Reentrancy Protection
-    /// This function uses checks-effects-interactions pattern:
-    /// 1. Checks: Validate inputs
-    /// 2. Effects: Update state (commitment storage, counters)
-    /// 3. Interactions: External calls (token transfer, NFT mint)
-    /// Reentrancy guard prevents recursive calls.
-    ///
-    /// # Formal Verification
-    /// **Preconditions:**
-    /// - `amount > 0`
-    /// - `rules.duration_days > 0`
-    /// - `rules.max_loss_percent <= 100`
-    /// - `rules.commitment_type ∈ {"safe", "balanced", "aggressive"}`
-    /// - Contract is initialized
-    /// - `reentrancy_guard == false`
-    ///
-    /// **Postconditions:**
-    /// - Returns unique `commitment_id`
-    /// - `get_commitment(commitment_id).owner == owner`
-    /// - `get_commitment(commitment_id).amount == amount`
-    /// - `get_commitment(commitment_id).status == "active"`
-    /// - `get_total_commitments() == old(get_total_commitments()) + 1`
-    /// - `reentrancy_guard == false`
-    ///
-    /// **Invariants Maintained:**
-    /// - INV-1: Total commitments consistency
-    /// - INV-2: Commitment balance conservation
-    /// - INV-3: Owner commitment list consistency
-    /// - INV-4: Reentrancy guard invariant
+    /// Toggle emergency mode. Admin only.
     ///
-    /// **Security Properties:**
-    /// - SP-1: Reentrancy protection
-    /// - SP-2: Access control
-    /// - SP-4: State consistency
-    /// - SP-5: Token conservation
-    pub fn create_commitment(
-        e: Env,
-        owner: Address,
-        amount: i128,
-        asset_address: Address,
-        rules: CommitmentRules,
-    ) -> String {
-        // Reentrancy protection
-        require_no_reentrancy(&e);
-        set_reentrancy_guard(&e, true);
+    /// While enabled, `create_commitment`, `top_up` and `allocate` are blocked and
+    /// owners may `emergency_exit` without penalty. Enabling (re)starts the
+    /// `emergency_sweep` timelock.
+    pub fn set_emergency_mode(e: Env, caller: Address, enabled: bool) {
+        require_admin(&e, &caller);
+        if enabled && !EmergencyControl::is_emergency_mode(&e) {
+            e.storage()
+                .instance()
+                .set(&DataKey::EmergencyActivatedAt, &e.ledger().timestamp());
+        }
+        EmergencyControl::set_emergency_mode(&e, enabled);
+    }
 
-        // Check if contract is paused
-        Pausable::require_not_paused(&e);
+    /// Set the address that receives `emergency_sweep` transfers. Admin only.
+    pub fn set_recovery_address(e: Env, caller: Address, recovery: Address) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .set(&DataKey::RecoveryAddress, &recovery);
+        e.events().publish(
+            (Symbol::new(&e, "RecoveryAddressSet"), caller),
+            (recovery, e.ledger().timestamp()),
+        );
+    }
 
-        // Rate limit: per-owner commitment creation
-        let fn_symbol = symbol_short!("create");
-        RateLimiter::check(&e, &owner, &fn_symbol);
+    /// Get the recovery address. None if not set.
+    pub fn get_recovery_address(e: Env) -> Option<Address> {
+        e.storage().instance().get(&DataKey::RecoveryAddress)
+    }
 
-        // Validate amount > 0 using shared utilities
-        Validation::require_positive(amount);
+    /// Withdraw the full current value of an active commitment without penalty; only
+    /// the performance fee on realized yield is taken. Owner only; requires emergency
+    /// mode and ignores the pause flag. Fails with `FundsAllocated` while any
+    /// principal is still out in pools.
+    pub fn emergency_exit(e: Env, commitment_id: String, caller: Address) {
+        require_no_reentrancy(&e);
+        set_reentrancy_guard(&e, true);
 
-        // Validate rules
-        Self::validate_rules(&e, &rules);
+        EmergencyControl::require_emergency(&e);
 
-        // Reject duration_days that would cause expires_at to overflow u64
-        let expires_at = TimeUtils::checked_calculate_expiration(&e, rules.duration_days)
-            .unwrap_or_else(|| {
-                set_reentrancy_guard(&e, false);
-                fail(&e, CommitmentError::ExpirationOverflow, "create_commitment")
-            });
+        // CHECKS
+        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::CommitmentNotFound, "emergency_exit")
+        });
+        caller.require_auth();
+        if commitment.owner != caller {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::Unauthorized, "emergency_exit");
+        }
+        if commitment.status != CommitmentStatus::Active {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::NotActive, "emergency_exit");
+        }
+        // Pool principal is not held here; it must be deallocated first
+        if commitment.allocated_amount > 0 {
+            set_reentrancy_guard(&e, false);
+            fail(&e, CommitmentError::FundsAllocated, "emergency_exit");
+        }
 
-        // OPTIMIZATION: Read both counters and NFT contract once to minimize storage operations
-        let (current_total, current_tvl, nft_contract) = {
-            let total = e
-                .storage()
-                .instance()
-                .get::<_, u64>(&DataKey::TotalCommitments)
-                .unwrap_or(0);
-            let tvl = e
-                .storage()
-                .instance()
-                .get::<_, i128>(&DataKey::TotalValueLocked)
-                .unwrap_or(0);
-            let nft = e
-                .storage()
-                .instance()
-                .get::<_, Address>(&DataKey::NftContract)
-                .unwrap_or_else(|| {
-                    set_reentrancy_guard(&e, false);
-                    fail(&e, CommitmentError::NotInitialized, "create_commitment")
-                });
-            (total, tvl, nft)
-        };
+        // EFFECTS
+        let remaining_value = commitment.current_value;
+        let performance_fee = collect_performance_fee(&e, &commitment, commitment.yield_earned);
+        let returned_amount = SafeMath::sub(remaining_value, performance_fee);
+        transition_status(
+            &e,
+            &mut commitment,
+            CommitmentStatus::EarlyExit,
+            "emergency_exit",
+        );
+        commitment.current_value = 0;
+        set_commitment(&e, &commitment);
 
-        // Generate unique commitment ID using counter
-        let commitment_id = Self::generate_commitment_id(&e, current_total);
+        release_tvl(&e, &commitment, remaining_value, ValueExit::Exited);
 
-        // CHECKS: Validate commitment doesn't already exist
-        if has_commitment(&e, &commitment_id) {
-            set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::InvalidStatus, "create_commitment");
+        // INTERACTIONS
+        let mut basket_payout = None;
+        if let Some(holdings) = read_holdings(&e, &commitment_id) {
+            basket_payout = Some(pay_out_holdings(&e, &holdings, &commitment.owner, |_| 0));
+        } else if returned_amount > 0 {
+            let contract_address = e.current_contract_address();
+            transfer_assets(
+                &e,
+                &contract_address,
+                &commitment.owner,
+                &commitment.asset_address,
+                returned_amount,
+            );
         }
+        let nft_contract = Self::get_nft_contract(e.clone());
+        e.invoke_contract::<()>(
+            &nft_contract,
+            &Symbol::new(&e, "mark_inactive"),
+            Vec::from_array(&e, [commitment.nft_token_id.into_val(&e)]),
+        );
 
-        // EFFECTS: Update state before external calls (expires_at already computed above with overflow check)
-        let current_timestamp = TimeUtils::now(&e);
+        set_reentrancy_guard(&e, false);
 
-        // Create commitment data
-        let commitment = Commitment {
-            commitment_id: commitment_id.clone(),
-            owner: owner.clone(),
-            nft_token_id: 0, // Will be set after NFT mint
-            rules: rules.clone(),
-            amount,
-            asset_address: asset_address.clone(),
-            created_at: current_timestamp,
-            expires_at,
-            current_value: amount, // Initially same as amount
-            status: String::from_str(&e, "active"),
-        };
+        // Baskets report the returned amount of each asset
+        let topics = (symbol_short!("EmgExit"), commitment_id, caller);
+        match basket_payout {
+            Some(payout) => e
+                .events()
+                .publish(topics, (payout.paid, e.ledger().timestamp())),
+            None => e
+                .events()
+                .publish(topics, (returned_amount, e.ledger().timestamp())),
+        }
+    }
 
-        // Store commitment data (before external calls)
-        set_commitment(&e, &commitment);
+    /// Move `amount` of `asset_address` held by the contract to the recovery address.
+    ///
+    /// Admin only; requires emergency mode to have been active for at least
+    /// `EMERGENCY_SWEEP_DELAY` seconds.
+    pub fn emergency_sweep(e: Env, caller: Address, asset_address: Address, amount: i128) {
+        require_admin(&e, &caller);
+        EmergencyControl::require_emergency(&e);
 
-        // Update owner's commitment list
-        let mut owner_commitments = e
+        let activated_at = e
             .storage()
             .instance()
-            .get::<_, Vec<String>>(&DataKey::OwnerCommitments(owner.clone()))
-            .unwrap_or(Vec::new(&e));
-        owner_commitments.push_back(commitment_id.clone());
-        e.storage().instance().set(
-            &DataKey::OwnerCommitments(owner.clone()),
-            &owner_commitments,
-        );
-
-        // OPTIMIZATION: Increment both counters using already-read values
-        e.storage()
-            .instance()
-            .set(&DataKey::TotalCommitments, &(current_total + 1));
-        e.storage()
-            .instance()
-            .set(&DataKey::TotalValueLocked, &(current_tvl + amount));
+            .get::<_, u64>(&DataKey::EmergencyActivatedAt)
+            .unwrap_or(0);
+        if e.ledger().timestamp() < activated_at.saturating_add(EMERGENCY_SWEEP_DELAY) {
+            fail(
+                &e,
+                CommitmentError::EmergencyTimelockActive,
+                "emergency_sweep",
+            );
+        }
+        if amount <= 0 {
+            fail(&e, CommitmentError::InvalidAmount, "emergency_sweep");
+        }
+        let recovery = Self::get_recovery_address(e.clone()).unwrap_or_else(|| {
+            fail(
+                &e,
+                CommitmentError::RecoveryAddressNotSet,
+                "emergency_sweep",
+            )
+        });
 
-        // INTERACTIONS: External calls (token transfer, NFT mint)
-        // Transfer assets from owner to contract
         let contract_address = e.current_contract_address();
-        transfer_assets(&e, &owner, &contract_address, &asset_address, amount);
+        transfer_assets(&e, &contract_address, &recovery, &asset_address, amount);
 
-        // Mint NFT
-        let nft_token_id = call_nft_mint(
-            &e,
-            &nft_contract,
-            &owner,
-            &commitment_id,
-            rules.duration_days,
-            rules.max_loss_percent,
-            &rules.commitment_type,
-            amount,
-            &asset_address,
-            rules.early_exit_penalty,
+        e.events().publish(
+            (symbol_short!("EmgSweep"), asset_address, recovery),
+            (amount, e.ledger().timestamp()),
         );
+    }
 
-        // Update commitment with NFT token ID
-        let mut updated_commitment = commitment;
-        updated_commitment.nft_token_id = nft_token_id;
-        set_commitment(&e, &updated_commitment);
+    // ========================================================================
+    // Upgrade & storage migration
+    // ========================================================================
 
-        // Clear reentrancy guard
-        set_reentrancy_guard(&e, false);
+    /// Get current on-chain version (0 if legacy/uninitialized).
+    pub fn get_version(e: Env) -> u32 {
+        read_version(&e)
+    }
 
-        // Emit creation event
-        e.events().publish(
-            (
-                symbol_short!("Created"),
-                commitment_id.clone(),
-                owner.clone(),
-            ),
-            (amount, rules, nft_token_id, e.ledger().timestamp()),
-        );
-        commitment_id
+    /// Update admin. Admin only. Deprecated: routed through `propose_admin`, so the
+    /// change only takes effect once `new_admin` calls `accept_admin`.
+    pub fn set_admin(e: Env, caller: Address, new_admin: Address) {
+        Self::propose_admin(e, caller, new_admin)
     }
 
-    /// Get commitment details
-    pub fn get_commitment(e: Env, commitment_id: String) -> Commitment {
-        read_commitment(&e, &commitment_id)
-            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "get_commitment"))
+    /// Nominate a new admin. Admin only; takes effect once the nominee calls `accept_admin`.
+    pub fn propose_admin(e: Env, caller: Address, new_admin: Address) {
+        require_admin(&e, &caller);
+        AccessControl::propose_admin(&e, &caller, &new_admin);
     }
 
-    /// List all commitment IDs owned by the given address.
-    ///
-    /// This is a convenience wrapper around `get_owner_commitments` with a
-    /// name optimized for off-chain indexers and UIs.
-    pub fn list_commitments_by_owner(e: Env, owner: Address) -> Vec<String> {
-        Self::get_owner_commitments(e, owner)
+    /// Accept a pending admin nomination. Caller must be the nominee.
+    pub fn accept_admin(e: Env, caller: Address) {
+        let admin = Self::get_admin(e.clone());
+        if !AccessControl::accept_admin(&e, &caller, &admin) {
+            fail(&e, CommitmentError::Unauthorized, "accept_admin");
+        }
+        e.storage().instance().set(&DataKey::Admin, &caller);
     }
 
-    /// Get all commitments for an owner
-    pub fn get_owner_commitments(e: Env, owner: Address) -> Vec<String> {
-        e.storage()
-            .instance()
-            .get::<_, Vec<String>>(&DataKey::OwnerCommitments(owner))
-            .unwrap_or(Vec::new(&e))
+    /// Withdraw a pending admin nomination. Admin only.
+    pub fn cancel_admin_proposal(e: Env, caller: Address) {
+        require_admin(&e, &caller);
+        AccessControl::cancel_admin_proposal(&e, &caller);
     }
 
-    /// Get total number of commitments
-    pub fn get_total_commitments(e: Env) -> u64 {
+    /// Get the pending admin nominee, if any.
+    pub fn get_pending_admin(e: Env) -> Option<Address> {
+        AccessControl::get_pending_admin(&e)
+    }
+
+    /// Upgrade contract WASM. Admin only; call `migrate` afterwards if the
+    /// storage layout changed.
+    pub fn upgrade(e: Env, caller: Address, new_wasm_hash: BytesN<32>) {
+        require_admin(&e, &caller);
+        require_valid_wasm_hash(&e, &new_wasm_hash);
+        e.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        e.events().publish(
+            (symbol_short!("Upgraded"), caller),
+            (new_wasm_hash, e.ledger().timestamp()),
+        );
+    }
+
+    /// Migrate storage from `from_version` to `CURRENT_VERSION`. Admin only.
+    ///
+    /// v0 -> v1: commitment statuses stored as strings are converted to `CommitmentStatus`.
+    /// v1 -> v2: commitments and owner indexes move from instance to persistent storage.
+    /// v2 -> v3: the per-status index is built from existing commitments.
+    /// v3 -> v4: commitments get `gross_amount`, backfilled from `amount`.
+    /// v4 -> v5: commitments get `allocated_amount` and `yield_earned`, both 0.
+    /// v5 -> v6: per-asset and per-owner principal counters count every active commitment.
+    /// v6 -> v7: `AssetMetrics.value_locked` is rebuilt from active and violated commitments;
+    /// cumulative volumes start at 0.
+    /// v7 -> v8: commitment rules and templates get `transfer_policy`, set to `Free`.
+    /// v8 -> v9: commitments get `is_basket`; basket aggregate values move from
+    /// `amount`/`current_value` to their `OracleValuation` and out of TVL.
+    ///
+    /// The v4, v5, v8 and v9 record rewrites share one pass that runs before the status
+    /// index is built, so every record decodes as the current `Commitment`.
+    pub fn migrate(e: Env, caller: Address, from_version: u32) {
+        require_admin(&e, &caller);
+
+        let stored_version = read_version(&e);
+        if stored_version == CURRENT_VERSION {
+            fail(&e, CommitmentError::AlreadyMigrated, "migrate");
+        }
+        if from_version != stored_version || from_version > CURRENT_VERSION {
+            fail(&e, CommitmentError::InvalidVersion, "migrate");
+        }
+
+        if from_version < 1 {
+            migrate_legacy_statuses(&e);
+        }
+        if from_version < 2 {
+            migrate_to_persistent(&e);
+        }
+        // Runs before the status index so every record decodes as `Commitment`
+        if from_version < 9 {
+            migrate_commitment_fields(&e);
+        }
+        if from_version < 8 {
+            migrate_template_fields(&e);
+        }
+        if from_version < 3 {
+            migrate_status_index(&e);
+        }
+        if from_version < 6 {
+            migrate_asset_locked(&e);
+        }
+        if from_version < 7 {
+            migrate_asset_metrics(&e);
+        }
+        if from_version < 9 {
+            migrate_basket_values(&e);
+        }
+
         e.storage()
             .instance()
-            .get::<_, u64>(&DataKey::TotalCommitments)
-            .unwrap_or(0)
+            .set(&DataKey::Version, &CURRENT_VERSION);
+        e.events().publish(
+            (symbol_short!("Migrated"),),
+            (from_version, CURRENT_VERSION, e.ledger().timestamp()),
+        );
     }
 
-    /// Get total value locked across all active commitments.
-    pub fn get_total_value_locked(e: Env) -> i128 {
+    // ========================================================================
+    // Asset registry
+    // ========================================================================
+
+    /// Register or replace the limits for an asset. Admin only.
+    pub fn set_asset_config(e: Env, caller: Address, asset_address: Address, config: AssetConfig) {
+        require_admin(&e, &caller);
+        if config.min_amount < 0 || config.tvl_cap < 0 || config.owner_cap < 0 {
+            fail(&e, CommitmentError::InvalidAmount, "set_asset_config");
+        }
         e.storage()
             .instance()
-            .get::<_, i128>(&DataKey::TotalValueLocked)
-            .unwrap_or(0)
+            .set(&DataKey::AssetConfig(asset_address.clone()), &config);
+        e.events().publish(
+            (Symbol::new(&e, "AssetConfigSet"), asset_address),
+            (config, e.ledger().timestamp()),
+        );
     }
 
-    /// Get admin address
-    pub fn get_admin(e: Env) -> Address {
+    /// Drop an asset's limits. Admin only. With the allowlist enforced, the asset
+    /// can no longer back new commitments.
+    pub fn remove_asset_config(e: Env, caller: Address, asset_address: Address) {
+        require_admin(&e, &caller);
         e.storage()
             .instance()
-            .get::<_, Address>(&DataKey::Admin)
-            .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "get_admin"))
+            .remove(&DataKey::AssetConfig(asset_address.clone()));
+        e.events().publish(
+            (Symbol::new(&e, "AssetConfigRemoved"), asset_address),
+            e.ledger().timestamp(),
+        );
     }
 
-    /// Get NFT contract address
-    pub fn get_nft_contract(e: Env) -> Address {
+    /// Require every asset to be registered before it can back a commitment.
+    /// Admin only. Registered assets are held to their limits either way.
+    pub fn set_asset_allowlist_enforced(e: Env, caller: Address, enforced: bool) {
+        require_admin(&e, &caller);
         e.storage()
             .instance()
-            .get::<_, Address>(&DataKey::NftContract)
-            .unwrap_or_else(|| fail(&e, CommitmentError::NotInitialized, "get_nft_contract"))
+            .set(&DataKey::AssetAllowlistEnforced, &enforced);
+        e.events().publish(
+            (Symbol::new(&e, "AssetAllowlistSet"), caller),
+            (enforced, e.ledger().timestamp()),
+        );
     }
 
-    /// Update commitment value (called by allocation logic or oracle-fed keeper).
-    /// Persists new_value to commitment.current_value and updates TotalValueLocked.
-    pub fn update_value(e: Env, commitment_id: String, new_value: i128) {
-        // Global per-function rate limit (per contract instance)
-        let fn_symbol = symbol_short!("upd_val");
-        let contract_address = e.current_contract_address();
-        RateLimiter::check(&e, &contract_address, &fn_symbol);
-
-        Validation::require_non_negative(new_value);
+    /// Whether unregistered assets are rejected.
+    pub fn is_asset_allowlist_enforced(e: Env) -> bool {
+        is_asset_allowlist_enforced(&e)
+    }
 
-        let mut commitment = read_commitment(&e, &commitment_id)
-            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "update_value"));
+    /// Get an asset's limits, if registered.
+    pub fn get_asset_config(e: Env, asset_address: Address) -> Option<AssetConfig> {
+        e.storage()
+            .instance()
+            .get(&DataKey::AssetConfig(asset_address))
+    }
 
-        let active_status = String::from_str(&e, "active");
-        if commitment.status != active_status {
-            fail(&e, CommitmentError::NotActive, "update_value");
+    /// Whether new commitments may currently use `asset_address`.
+    pub fn is_asset_allowed(e: Env, asset_address: Address) -> bool {
+        match Self::get_asset_config(e.clone(), asset_address) {
+            Some(config) => config.enabled,
+            None => !is_asset_allowlist_enforced(&e),
         }
+    }
 
-        let old_value = commitment.current_value;
-        commitment.current_value = new_value;
+    /// Principal locked in active commitments of `asset_address`.
+    pub fn get_asset_locked(e: Env, asset_address: Address) -> i128 {
+        asset_locked(&e, &asset_address)
+    }
 
-        // Check for violations after updating value
-        let loss_percent = if commitment.amount > 0 {
-            SafeMath::loss_percent(commitment.amount, commitment.current_value)
-        } else {
-            0
-        };
-        let max_loss = commitment.rules.max_loss_percent as i128;
-        let loss_violated = loss_percent > max_loss;
+    /// Principal `owner` has locked in active commitments of `asset_address`.
+    pub fn get_owner_asset_locked(e: Env, owner: Address, asset_address: Address) -> i128 {
+        owner_asset_locked(&e, &owner, &asset_address)
+    }
 
-        if loss_violated {
-            commitment.status = String::from_str(&e, "violated");
-            set_commitment(&e, &commitment);
-            e.events().publish(
-                (symbol_short!("Violated"), commitment_id.clone()),
-                (symbol_short!("RuleViol"), e.ledger().timestamp()),
-            );
-        } else {
-            set_commitment(&e, &commitment);
-            e.events().publish(
-                (symbol_short!("ValUpd"), commitment_id),
-                (new_value, e.ledger().timestamp()),
-            );
-        }
+    // ========================================================================
+    // Templates
+    // ========================================================================
+
+    /// Register a commitment template and return its ID. Admin only.
+    pub fn add_template(e: Env, caller: Address, template: CommitmentTemplate) -> u32 {
+        require_admin(&e, &caller);
+        validate_template(&e, &template);
 
-        // Adjust TotalValueLocked: TVL -= old_value, TVL += new_value
-        let current_tvl = e
+        let template_id = e
             .storage()
             .instance()
-            .get::<_, i128>(&DataKey::TotalValueLocked)
+            .get::<_, u32>(&DataKey::TemplateCount)
             .unwrap_or(0);
-        let new_tvl = current_tvl - old_value + new_value;
+        write_template(&e, template_id, &template);
         e.storage()
             .instance()
-            .set(&DataKey::TotalValueLocked, &new_tvl);
+            .set(&DataKey::TemplateCount, &(template_id + 1));
+
+        e.events().publish(
+            (Symbol::new(&e, "TemplateAdded"), template_id),
+            (template.name, e.ledger().timestamp()),
+        );
+        template_id
     }
 
-    /// Check if commitment rules are violated
-    /// Returns true if any rule violation is detected (loss limit or duration)
-    ///
-    /// # Formal Verification
-    /// **Preconditions:**
-    /// - `commitment_id` exists
-    ///
-    /// **Postconditions:**
-    /// - Returns `true` if `loss_percent > max_loss_percent OR current_time >= expires_at`
-    /// - Returns `false` otherwise
-    /// - Pure function (no state changes)
-    ///
-    /// **Invariants Maintained:**
-    /// - INV-2: Commitment balance conservation
-    ///
-    /// **Security Properties:**
-    /// - SP-4: State consistency (read-only)
-    pub fn check_violations(e: Env, commitment_id: String) -> bool {
-        let commitment = read_commitment(&e, &commitment_id)
-            .unwrap_or_else(|| fail(&e, CommitmentError::CommitmentNotFound, "check_violations"));
+    /// Replace an existing template. Commitments already created from it keep
+    /// their rules. Admin only.
+    pub fn update_template(
+        e: Env,
+        caller: Address,
+        template_id: u32,
+        template: CommitmentTemplate,
+    ) {
+        require_admin(&e, &caller);
+        read_template(&e, template_id, "update_template");
+        validate_template(&e, &template);
+        write_template(&e, template_id, &template);
 
-        // Skip check if already settled or violated
-        let active_status = String::from_str(&e, "active");
-        if commitment.status != active_status {
-            return false; // Already processed
-        }
+        e.events().publish(
+            (Symbol::new(&e, "TemplateUpdated"), template_id),
+            (template.name, e.ledger().timestamp()),
+        );
+    }
 
-        let current_time = e.ledger().timestamp();
+    /// Enable or disable a template for new commitments. Admin only.
+    pub fn set_template_enabled(e: Env, caller: Address, template_id: u32, enabled: bool) {
+        require_admin(&e, &caller);
+        let mut template = read_template(&e, template_id, "set_template_enabled");
+        template.enabled = enabled;
+        write_template(&e, template_id, &template);
 
-        // Check loss limit violation
-        // Calculate loss percentage using shared utilities, but handle zero-amount
-        // commitments gracefully to avoid panics. A zero-amount commitment cannot
-        // meaningfully violate a loss limit, so we treat its loss percent as 0.
-        let loss_percent = if commitment.amount > 0 {
-            SafeMath::loss_percent(commitment.amount, commitment.current_value)
-        } else {
-            0
+        e.events().publish(
+            (Symbol::new(&e, "TemplateEnabled"), template_id),
+            (enabled, e.ledger().timestamp()),
+        );
+    }
+
+    /// Get a template by ID.
+    pub fn get_template(e: Env, template_id: u32) -> CommitmentTemplate {
+        read_template(&e, template_id, "get_template")
+    }
+
+    /// Number of templates registered; IDs run from 0 to `count - 1`.
+    pub fn get_template_count(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get::<_, u32>(&DataKey::TemplateCount)
+            .unwrap_or(0)
+    }
+
+    /// Create a commitment from an enabled template. `duration_days` and
+    /// `max_loss_percent` must fall within the template's ranges, `amount` must
+    /// reach its minimum and `asset_address` must be one it accepts.
+    pub fn create_commitment_from_template(
+        e: Env,
+        owner: Address,
+        template_id: u32,
+        amount: i128,
+        asset_address: Address,
+        duration_days: u32,
+        max_loss_percent: u32,
+    ) -> String {
+        let context = "create_commitment_from_template";
+        let template = read_template(&e, template_id, context);
+        if !template.enabled {
+            fail(&e, CommitmentError::TemplateDisabled, context);
+        }
+        if duration_days < template.min_duration_days || duration_days > template.max_duration_days
+        {
+            fail(&e, CommitmentError::InvalidDuration, context);
+        }
+        if max_loss_percent < template.min_loss_percent
+            || max_loss_percent > template.max_loss_percent
+        {
+            fail(&e, CommitmentError::InvalidMaxLossPercent, context);
+        }
+        if amount < template.min_amount {
+            fail(&e, CommitmentError::InvalidAmount, context);
+        }
+        if !template.allowed_assets.is_empty() && !template.allowed_assets.contains(&asset_address)
+        {
+            fail(&e, CommitmentError::AssetNotAllowed, context);
+        }
+
+        let rules = CommitmentRules {
+            duration_days,
+            max_loss_percent,
+            commitment_type: template.commitment_type,
+            early_exit_penalty: template.early_exit_penalty,
+            min_fee_threshold: template.min_fee_threshold,
+            grace_period_days: template.grace_period_days,
+            transfer_policy: template.transfer_policy,
         };
+        let commitment_id = Self::create_funded_by(
+            e.clone(),
+            owner.clone(),
+            owner,
+            amount,
+            asset_address,
+            rules,
+        );
 
-        // Convert max_loss_percent (u32) to i128 for comparison
-        let max_loss = commitment.rules.max_loss_percent as i128;
-        let loss_violated = loss_percent > max_loss;
+        let key = DataKey::CommitmentTemplate(commitment_id.clone());
+        e.storage().persistent().set(&key, &template_id);
+        extend_persistent_ttl(&e, &key);
+        commitment_id
+    }
 
-        // Check duration violation (expired)
-        let duration_violated = current_time >= commitment.expires_at;
+    /// Get the template a commitment was created from, if any.
+    pub fn get_commitment_template(e: Env, commitment_id: String) -> Option<u32> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::CommitmentTemplate(commitment_id))
+    }
 
-        let violated = loss_violated || duration_violated;
+    // ========================================================================
+    // Operators
+    // ========================================================================
 
-        if violated {
-            // Emit violation event
-            e.events().publish(
-                (symbol_short!("Violated"), commitment_id),
-                (symbol_short!("RuleViol"), e.ledger().timestamp()),
-            );
+    /// Let `operator` act for `owner` within `scope` until `expiry` (ledger timestamp).
+    /// Replaces any earlier approval for the same operator. Owner only.
+    pub fn approve_operator(
+        e: Env,
+        owner: Address,
+        operator: Address,
+        scope: OperatorScope,
+        expiry: u64,
+    ) {
+        owner.require_auth();
+        if expiry <= e.ledger().timestamp() {
+            fail(&e, CommitmentError::InvalidExpiry, "approve_operator");
         }
+        let key = DataKey::Operator(owner.clone(), operator.clone());
+        e.storage().persistent().set(
+            &key,
+            &OperatorApproval {
+                scope,
+                expires_at: expiry,
+            },
+        );
+        extend_persistent_ttl(&e, &key);
+        e.events().publish(
+            (Symbol::new(&e, "OperatorApproved"), owner, operator),
+            (scope, expiry),
+        );
+    }
 
-        // Return true if any violation exists
-        violated
+    /// Withdraw an operator approval. Owner only; no-op if none exists.
+    pub fn revoke_operator(e: Env, owner: Address, operator: Address) {
+        owner.require_auth();
+        e.storage()
+            .persistent()
+            .remove(&DataKey::Operator(owner.clone(), operator.clone()));
+        e.events().publish(
+            (Symbol::new(&e, "OperatorRevoked"), owner, operator),
+            e.ledger().timestamp(),
+        );
+    }
+
+    /// Get the approval `owner` granted `operator`, including expired ones.
+    pub fn get_operator_approval(
+        e: Env,
+        owner: Address,
+        operator: Address,
+    ) -> Option<OperatorApproval> {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Operator(owner, operator))
+    }
+
+    /// Check whether `operator` may currently act for `owner` within `scope`.
+    pub fn is_operator(e: Env, owner: Address, operator: Address, scope: OperatorScope) -> bool {
+        is_operator(&e, &owner, &operator, scope)
+    }
+
+    // ========================================================================
+    // Valuation (updater role and price oracle)
+    // ========================================================================
+
+    /// Grant the valuation-updater role. Admin only.
+    pub fn add_valuation_updater(e: Env, caller: Address, updater: Address) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .set(&DataKey::ValuationUpdater(updater.clone()), &true);
+        e.events()
+            .publish((Symbol::new(&e, "ValuationUpdaterAdded"),), (updater,));
     }
 
-    /// Get detailed violation information
-    /// Returns a tuple: (has_violations, loss_violated, duration_violated, loss_percent, time_remaining)
-    pub fn get_violation_details(e: Env, commitment_id: String) -> (bool, bool, bool, i128, u64) {
-        let commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
-            fail(
-                &e,
-                CommitmentError::CommitmentNotFound,
-                "get_violation_details",
-            )
-        });
-
-        let current_time = e.ledger().timestamp();
+    /// Revoke the valuation-updater role. Admin only.
+    pub fn remove_valuation_updater(e: Env, caller: Address, updater: Address) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .remove(&DataKey::ValuationUpdater(updater.clone()));
+        e.events()
+            .publish((Symbol::new(&e, "ValuationUpdaterRemoved"),), (updater,));
+    }
 
-        // Calculate loss percentage
-        let loss_amount = commitment.amount - commitment.current_value;
-        let loss_percent = if commitment.amount > 0 {
-            (loss_amount * 100) / commitment.amount
-        } else {
-            0
-        };
+    /// Check whether an address may call `update_value`. Admin is implicitly authorized.
+    pub fn is_valuation_updater(e: Env, address: Address) -> bool {
+        is_valuation_updater(&e, &address)
+    }
 
-        // Check loss limit violation
-        let max_loss = commitment.rules.max_loss_percent as i128;
-        let loss_violated = loss_percent > max_loss;
+    /// Set the price oracle used by `refresh_value_from_oracle`. Admin only.
+    pub fn set_price_oracle(e: Env, caller: Address, oracle: Address) {
+        require_admin(&e, &caller);
+        e.storage().instance().set(&DataKey::PriceOracle, &oracle);
+        e.events().publish(
+            (Symbol::new(&e, "PriceOracleSet"), caller),
+            (oracle, e.ledger().timestamp()),
+        );
+    }
 
-        // Check duration violation
-        let duration_violated = current_time >= commitment.expires_at;
+    /// Get the configured price oracle. None if not set.
+    pub fn get_price_oracle(e: Env) -> Option<Address> {
+        e.storage().instance().get(&DataKey::PriceOracle)
+    }
 
-        // Calculate time remaining (0 if expired)
-        let time_remaining = if current_time < commitment.expires_at {
-            commitment.expires_at - current_time
-        } else {
-            0
-        };
+    // ========================================================================
+    // Protocol treasury (early-exit penalties)
+    // ========================================================================
 
-        let has_violations = loss_violated || duration_violated;
+    /// Set the protocol treasury that receives withdrawn early-exit penalties. Admin only.
+    pub fn set_treasury_recipient(e: Env, caller: Address, recipient: Address) {
+        require_admin(&e, &caller);
+        e.storage()
+            .instance()
+            .set(&DataKey::TreasuryRecipient, &recipient);
+        e.events().publish(
+            (Symbol::new(&e, "TreasuryRecipientSet"), caller),
+            (recipient, e.ledger().timestamp()),
+        );
+    }
 
-        (
-            has_violations,
-            loss_violated,
-            duration_violated,
-            loss_percent,
-            time_remaining,
-        )
+    /// Get the treasury recipient. None if not set.
+    pub fn get_treasury_recipient(e: Env) -> Option<Address> {
+        e.storage().instance().get(&DataKey::TreasuryRecipient)
     }
 
-    /// Settle commitment at maturity
+    /// Withdraw collected early-exit penalties for an asset to the treasury recipient. Admin only.
     ///
     /// # Reentrancy Protection
-    /// Uses checks-effects-interactions pattern with reentrancy guard.
-    pub fn settle(e: Env, commitment_id: String) {
-        // Reentrancy protection
+    /// Ledger is debited before the token transfer; guarded like other fund-moving calls.
+    pub fn withdraw_penalties(e: Env, caller: Address, asset_address: Address, amount: i128) {
         require_no_reentrancy(&e);
         set_reentrancy_guard(&e, true);
 
-        // Check if contract is paused
-        Pausable::require_not_paused(&e);
-
-        // CHECKS: Get and validate commitment
-        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
-            set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::CommitmentNotFound, "settle")
-        });
-
-        // Verify commitment is expired
-        let current_time = e.ledger().timestamp();
-        if current_time < commitment.expires_at {
-            set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::NotExpired, "settle");
-        }
+        require_admin(&e, &caller);
 
-        // Verify commitment is active
-        let active_status = String::from_str(&e, "active");
-        if commitment.status != active_status {
+        if amount <= 0 {
             set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::NotActive, "settle");
+            fail(&e, CommitmentError::InvalidAmount, "withdraw_penalties");
         }
 
-        // EFFECTS: Update state before external calls
-        let settlement_amount = commitment.current_value;
-        commitment.status = String::from_str(&e, "settled");
-        set_commitment(&e, &commitment);
-
-        // Decrease total value locked
-        let current_tvl = e
-            .storage()
-            .instance()
-            .get::<_, i128>(&DataKey::TotalValueLocked)
-            .unwrap_or(0);
-        let new_tvl = current_tvl - settlement_amount;
-        e.storage()
-            .instance()
-            .set(&DataKey::TotalValueLocked, &new_tvl);
-
-        // INTERACTIONS: External calls (token transfer, NFT settlement)
-        // Transfer assets back to owner
-        let contract_address = e.current_contract_address();
-        let token_client = token::Client::new(&e, &commitment.asset_address);
-        token_client.transfer(&contract_address, &commitment.owner, &settlement_amount);
-
-        // Call NFT contract to mark NFT as settled
-        let nft_contract = e
+        let recipient = e
             .storage()
             .instance()
-            .get::<_, Address>(&DataKey::NftContract)
+            .get::<_, Address>(&DataKey::TreasuryRecipient)
             .unwrap_or_else(|| {
                 set_reentrancy_guard(&e, false);
-                fail(&e, CommitmentError::NotInitialized, "settle")
+                fail(
+                    &e,
+                    CommitmentError::TreasuryRecipientNotSet,
+                    "withdraw_penalties",
+                )
             });
 
-        let mut args = Vec::new(&e);
-        args.push_back(commitment.nft_token_id.into_val(&e));
-        e.invoke_contract::<()>(&nft_contract, &Symbol::new(&e, "settle"), args);
+        let key = DataKey::CollectedPenalties(asset_address.clone());
+        let collected = e.storage().instance().get::<_, i128>(&key).unwrap_or(0);
+        if amount > collected {
+            set_reentrancy_guard(&e, false);
+            fail(
+                &e,
+                CommitmentError::InsufficientPenalties,
+                "withdraw_penalties",
+            );
+        }
+
+        // EFFECTS: Debit the ledger before transferring
+        e.storage().instance().set(&key, &(collected - amount));
+
+        // INTERACTIONS: Transfer penalties to the treasury
+        let contract_address = e.current_contract_address();
+        let token_client = token::Client::new(&e, &asset_address);
+        token_client.transfer(&contract_address, &recipient, &amount);
 
-        // Clear reentrancy guard
         set_reentrancy_guard(&e, false);
 
-        // Emit settlement event
         e.events().publish(
-            (symbol_short!("Settled"), commitment_id),
-            (settlement_amount, e.ledger().timestamp()),
+            (Symbol::new(&e, "PenaltiesWithdrawn"), caller, recipient),
+            (asset_address, amount, e.ledger().timestamp()),
         );
     }
 
-    pub fn early_exit(e: Env, commitment_id: String, caller: Address) {
-        // Reentrancy protection
-        require_no_reentrancy(&e);
-        set_reentrancy_guard(&e, true);
-
-        // Check if contract is paused
-        Pausable::require_not_paused(&e);
-
-        // CHECKS: Get and validate commitment
-        let mut commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
-            set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::CommitmentNotFound, "early_exit")
-        });
+    /// Get early-exit penalties collected and not yet withdrawn for an asset.
+    pub fn get_collected_penalties(e: Env, asset_address: Address) -> i128 {
+        e.storage()
+            .instance()
+            .get(&DataKey::CollectedPenalties(asset_address))
+            .unwrap_or(0)
+    }
 
-        // Verify caller is owner
-        caller.require_auth();
-        if commitment.owner != caller {
-            set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::Unauthorized, "early_exit");
-        }
+    // ========================================================================
+    // Protocol fees (creation and performance)
+    // ========================================================================
 
-        // Verify commitment is active
-        let active_status = String::from_str(&e, "active");
-        if commitment.status != active_status {
-            set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::NotActive, "early_exit");
+    /// Set the fee (basis points of the deposit) taken on `create_commitment`.
+    /// Admin only; capped at `MAX_CREATION_FEE_BPS`.
+    pub fn set_creation_fee_bps(e: Env, caller: Address, bps: u32) {
+        require_admin(&e, &caller);
+        if bps > MAX_CREATION_FEE_BPS {
+            fail(&e, CommitmentError::InvalidFeeBps, "set_creation_fee_bps");
         }
-
-        // EFFECTS: Calculate penalty using shared utilities
-        let penalty_amount = SafeMath::penalty_amount(
-            commitment.current_value,
-            commitment.rules.early_exit_penalty,
+        e.storage().instance().set(&DataKey::CreationFeeBps, &bps);
+        e.events().publish(
+            (Symbol::new(&e, "CreationFeeSet"), caller),
+            (bps, e.ledger().timestamp()),
         );
-        let returned_amount = SafeMath::sub(commitment.current_value, penalty_amount);
-        let original_value = commitment.current_value;
-
-        // Update commitment status to early_exit
-        commitment.status = String::from_str(&e, "early_exit");
-        commitment.current_value = 0; // All value has been distributed
-        set_commitment(&e, &commitment);
+    }
 
-        // Decrease total value locked by full current value (no longer locked)
-        let current_tvl = e
-            .storage()
-            .instance()
-            .get::<_, i128>(&DataKey::TotalValueLocked)
-            .unwrap_or(0);
-        let new_tvl = current_tvl - original_value;
+    /// Get the creation fee in basis points (0 if not set).
+    pub fn get_creation_fee_bps(e: Env) -> u32 {
         e.storage()
             .instance()
-            .set(&DataKey::TotalValueLocked, &new_tvl);
-
-        // INTERACTIONS: External calls (token transfer)
-        // Transfer remaining amount (after penalty) to owner
-        let contract_address = e.current_contract_address();
-        let token_client = token::Client::new(&e, &commitment.asset_address);
-
-        if returned_amount > 0 {
-            token_client.transfer(&contract_address, &commitment.owner, &returned_amount);
-        }
+            .get(&DataKey::CreationFeeBps)
+            .unwrap_or(0)
+    }
 
-        // Call NFT contract to mark as inactive (early exited, not settled)
-        let nft_contract = e
-            .storage()
+    /// Set the address that receives withdrawn creation fees. Admin only.
+    pub fn set_fee_recipient(e: Env, caller: Address, recipient: Address) {
+        require_admin(&e, &caller);
+        e.storage()
             .instance()
-            .get::<_, Address>(&DataKey::NftContract)
-            .unwrap_or_else(|| {
-                set_reentrancy_guard(&e, false);
-                fail(&e, CommitmentError::NotInitialized, "early_exit")
-            });
-        
-        // Call mark_inactive on NFT instead of settle (since not expired)
-        let mut args = Vec::new(&e);
-        args.push_back(commitment.nft_token_id.into_val(&e));
-        e.invoke_contract::<()>(&nft_contract, &Symbol::new(&e, "mark_inactive"), args);
-
-        // Clear reentrancy guard
-        set_reentrancy_guard(&e, false);
-
-        // Emit early exit event with detailed information
+            .set(&DataKey::FeeRecipient, &recipient);
         e.events().publish(
-            (
-                symbol_short!("EarlyExt"),
-                commitment_id.clone(),
-                caller.clone(),
-            ),
-            (penalty_amount, returned_amount, e.ledger().timestamp()),
+            (Symbol::new(&e, "FeeRecipientSet"), caller),
+            (recipient, e.ledger().timestamp()),
         );
     }
 
-    /// Allocate liquidity (called by allocation strategy)
+    /// Get the fee recipient. None if not set.
+    pub fn get_fee_recipient(e: Env) -> Option<Address> {
+        e.storage().instance().get(&DataKey::FeeRecipient)
+    }
+
+    /// Withdraw collected creation fees for an asset to the fee recipient. Admin only.
     ///
     /// # Reentrancy Protection
-    /// Uses checks-effects-interactions pattern with reentrancy guard.
-    pub fn allocate(e: Env, commitment_id: String, target_pool: Address, amount: i128) {
-        // Reentrancy protection
+    /// Ledger is debited before the token transfer; guarded like other fund-moving calls.
+    pub fn withdraw_fees(e: Env, caller: Address, asset_address: Address, amount: i128) {
         require_no_reentrancy(&e);
         set_reentrancy_guard(&e, true);
 
-        // Check if contract is paused
-        Pausable::require_not_paused(&e);
-
-        // Rate limit allocations per target pool address
-        let fn_symbol = symbol_short!("alloc");
-        RateLimiter::check(&e, &target_pool, &fn_symbol);
+        require_admin(&e, &caller);
 
-        // CHECKS: Validate inputs and commitment
         if amount <= 0 {
             set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::InvalidAmount, "allocate");
+            fail(&e, CommitmentError::InvalidAmount, "withdraw_fees");
         }
 
-        let commitment = read_commitment(&e, &commitment_id).unwrap_or_else(|| {
-            set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::CommitmentNotFound, "allocate")
-        });
-
-        // Verify commitment is active
-        let active_status = String::from_str(&e, "active");
-        if commitment.status != active_status {
-            set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::NotActive, "allocate");
-        }
+        let recipient = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::FeeRecipient)
+            .unwrap_or_else(|| {
+                set_reentrancy_guard(&e, false);
+                fail(&e, CommitmentError::FeeRecipientNotSet, "withdraw_fees")
+            });
 
-        // Verify sufficient balance
-        if commitment.current_value < amount {
+        let key = DataKey::CollectedFees(asset_address.clone());
+        let collected = e.storage().instance().get::<_, i128>(&key).unwrap_or(0);
+        if amount > collected {
             set_reentrancy_guard(&e, false);
-            fail(&e, CommitmentError::InsufficientBalance, "allocate");
+            fail(&e, CommitmentError::InsufficientFees, "withdraw_fees");
         }
 
-        // EFFECTS: Update commitment value before external call
-        let mut updated_commitment = commitment;
-        updated_commitment.current_value = updated_commitment.current_value - amount;
-        set_commitment(&e, &updated_commitment);
+        // EFFECTS: Debit the ledger before transferring
+        e.storage().instance().set(&key, &(collected - amount));
 
-        // INTERACTIONS: External call (token transfer)
-        // Transfer assets to target pool
+        // INTERACTIONS: Transfer fees to the recipient
         let contract_address = e.current_contract_address();
-        let token_client = token::Client::new(&e, &updated_commitment.asset_address);
-        token_client.transfer(&contract_address, &target_pool, &amount);
+        let token_client = token::Client::new(&e, &asset_address);
+        token_client.transfer(&contract_address, &recipient, &amount);
 
-        // Clear reentrancy guard
         set_reentrancy_guard(&e, false);
 
-        // Emit allocation event
         e.events().publish(
-            (symbol_short!("Alloc"), commitment_id, target_pool),
-            (amount, e.ledger().timestamp()),
+            (Symbol::new(&e, "FeesWithdrawn"), caller, recipient),
+            (asset_address, amount, e.ledger().timestamp()),
         );
     }
 
-    /// Configure rate limits for this contract's functions.
-    ///
-    /// This function is restricted to the contract admin.
-    pub fn set_rate_limit(
-        e: Env,
-        caller: Address,
-        function: Symbol,
-        window_seconds: u64,
-        max_calls: u32,
-    ) {
+    /// Set the fee (basis points of realized yield) taken whenever yield is paid out or
+    /// rolled over. Admin only; capped at `MAX_PERFORMANCE_FEE_BPS`.
+    pub fn set_performance_fee_bps(e: Env, caller: Address, bps: u32) {
         require_admin(&e, &caller);
-        RateLimiter::set_limit(&e, &function, window_seconds, max_calls);
+        if bps > MAX_PERFORMANCE_FEE_BPS {
+            fail(
+                &e,
+                CommitmentError::InvalidFeeBps,
+                "set_performance_fee_bps",
+            );
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::PerformanceFeeBps, &bps);
+        e.events().publish(
+            (Symbol::new(&e, "PerformanceFeeSet"), caller),
+            (bps, e.ledger().timestamp()),
+        );
     }
 
-    /// Set or clear rate limit exemption for an address.
-    ///
-    /// This function is restricted to the contract admin.
-    pub fn set_rate_limit_exempt(e: Env, caller: Address, address: Address, exempt: bool) {
+    /// Get the performance fee in basis points (0 if not set).
+    pub fn get_performance_fee_bps(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::PerformanceFeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Set the penalty (basis points of remaining value) withheld by
+    /// `resolve_violation` from loss-limit breaches. Admin only; capped at
+    /// `MAX_VIOLATION_PENALTY_BPS`.
+    pub fn set_violation_penalty_bps(e: Env, caller: Address, bps: u32) {
         require_admin(&e, &caller);
-        RateLimiter::set_exempt(&e, &address, exempt);
+        if bps > MAX_VIOLATION_PENALTY_BPS {
+            fail(
+                &e,
+                CommitmentError::InvalidFeeBps,
+                "set_violation_penalty_bps",
+            );
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::ViolationPenaltyBps, &bps);
+        e.events().publish(
+            (Symbol::new(&e, "ViolationPenaltySet"), caller),
+            (bps, e.ledger().timestamp()),
+        );
+    }
+
+    /// Get the violation penalty in basis points (0 if not set).
+    pub fn get_violation_penalty_bps(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&DataKey::ViolationPenaltyBps)
+            .unwrap_or(0)
+    }
+
+    /// Get protocol fees (creation and performance) collected and not yet withdrawn for an asset.
+    pub fn get_collected_fees(e: Env, asset_address: Address) -> i128 {
+        e.storage()
+            .instance()
+            .get(&DataKey::CollectedFees(asset_address))
+            .unwrap_or(0)
     }
 }
 
+#[cfg(test)]
+mod emergency_tests;
 #[cfg(test)]
 mod tests;
 
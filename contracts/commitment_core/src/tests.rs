@@ -3,10 +3,10 @@
 use super::*;
 use shared_utils::TimeUtils;
 use soroban_sdk::{
-    contract, contractimpl, symbol_short,
+    contract, contracterror, contractimpl, symbol_short,
     testutils::{Address as _, Events, Ledger},
     token::StellarAssetClient,
-    vec, Address, Env, IntoVal, String,
+    vec, Address, Bytes, BytesN, Env, IntoVal, String,
 };
 
 #[contract]
@@ -14,8 +14,10 @@ struct MockNftContract;
 
 #[contractimpl]
 impl MockNftContract {
+    #[allow(clippy::too_many_arguments)]
     pub fn mint(
         _e: Env,
+        _caller: Address,
         _owner: Address,
         _commitment_id: String,
         _duration_days: u32,
@@ -24,9 +26,98 @@ impl MockNftContract {
         _initial_amount: i128,
         _asset_address: Address,
         _early_exit_penalty: u32,
+        _transfer_policy: TransferPolicy,
     ) -> u32 {
         1
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_basket(
+        _e: Env,
+        _caller: Address,
+        _owner: Address,
+        _commitment_id: String,
+        _duration_days: u32,
+        _max_loss_percent: u32,
+        _commitment_type: String,
+        _early_exit_penalty: u32,
+        _transfer_policy: TransferPolicy,
+    ) -> u32 {
+        1
+    }
+
+    pub fn settle(_e: Env, _token_id: u32) {}
+
+    pub fn mark_inactive(_e: Env, _token_id: u32) {}
+
+    pub fn is_active(_e: Env, _token_id: u32) -> bool {
+        true
+    }
+
+    pub fn update_value(_e: Env, _token_id: u32, _new_value: i128) {}
+
+    pub fn set_owner(e: Env, owner: Address) {
+        e.storage().instance().set(&symbol_short!("owner"), &owner);
+    }
+
+    pub fn owner_of(e: Env, _token_id: u32) -> Address {
+        e.storage().instance().get(&symbol_short!("owner")).unwrap()
+    }
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+enum MockOracleError {
+    PriceNotFound = 5,
+    StalePrice = 6,
+}
+
+/// Stand-in for `price_oracle` exposing only `get_price_valid`.
+#[contract]
+struct MockOracleContract;
+
+#[contractimpl]
+impl MockOracleContract {
+    pub fn set_price(e: Env, price: i128, decimals: u32, stale: bool) {
+        e.storage().instance().set(
+            &symbol_short!("price"),
+            &OraclePrice {
+                price,
+                updated_at: e.ledger().timestamp(),
+                decimals,
+            },
+        );
+        e.storage().instance().set(&symbol_short!("stale"), &stale);
+    }
+
+    /// Per-asset override of the price set by `set_price` (same decimals).
+    pub fn set_asset_price(e: Env, asset: Address, price: i128) {
+        e.storage().instance().set(&asset, &price);
+    }
+
+    pub fn get_price_valid(
+        e: Env,
+        asset: Address,
+        _max_staleness_override: Option<u64>,
+    ) -> Result<OraclePrice, MockOracleError> {
+        let mut price = e
+            .storage()
+            .instance()
+            .get::<_, OraclePrice>(&symbol_short!("price"))
+            .ok_or(MockOracleError::PriceNotFound)?;
+        if let Some(asset_price) = e.storage().instance().get::<_, i128>(&asset) {
+            price.price = asset_price;
+        }
+        if e.storage()
+            .instance()
+            .get::<_, bool>(&symbol_short!("stale"))
+            .unwrap_or(false)
+        {
+            return Err(MockOracleError::StalePrice);
+        }
+        Ok(price)
+    }
 }
 
 fn test_rules(e: &Env) -> CommitmentRules {
@@ -37,6 +128,7 @@ fn test_rules(e: &Env) -> CommitmentRules {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     }
 }
 
@@ -67,6 +159,7 @@ fn test_create_commitment_i128_max() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     let amount = i128::MAX;
@@ -107,6 +200,7 @@ fn test_create_commitment_amount_one() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     let amount = 1i128;
@@ -144,7 +238,7 @@ fn test_update_value_zero_behaves() {
     });
 
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
-    client.update_value(&commitment_id, &0);
+    client.update_value(&admin, &commitment_id, &0);
     let updated = client.get_commitment(&commitment_id);
     assert_eq!(updated.current_value, 0);
 }
@@ -171,7 +265,7 @@ fn test_update_value_negative_panics() {
     });
 
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
-    client.update_value(&commitment_id, &-100);
+    client.update_value(&admin, &commitment_id, &-100);
 }
 
 #[test]
@@ -183,7 +277,7 @@ fn test_early_exit_and_settle_large_amounts() {
     let admin = Address::generate(&e);
     let nft_contract = Address::generate(&e);
     let owner = Address::generate(&e);
-    let asset_address = Address::generate(&e);
+    let _asset_address = Address::generate(&e);
     let commitment_id = String::from_str(&e, "large_amount");
 
     e.as_contract(&contract_id, || {
@@ -206,9 +300,10 @@ fn test_early_exit_and_settle_large_amounts() {
 
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
     // Early exit and settle should compile and run without overflow
-    let _ = client.early_exit(&commitment_id, &owner);
-    let _ = client.settle(&commitment_id);
+    client.early_exit(&commitment_id, &owner);
+    client.settle(&commitment_id, &owner);
 }
+#[allow(clippy::too_many_arguments)]
 fn create_test_commitment(
     e: &Env,
     commitment_id: &str,
@@ -232,13 +327,18 @@ fn create_test_commitment(
             early_exit_penalty: 10,
             min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
         },
         amount,
+        gross_amount: amount,
         asset_address: Address::generate(e),
         created_at,
         expires_at,
         current_value,
-        status: String::from_str(e, "active"),
+        status: CommitmentStatus::Active,
+        allocated_amount: 0,
+        yield_earned: 0,
+        is_basket: false,
     }
 }
 
@@ -308,6 +408,7 @@ fn test_create_commitment_valid() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     let _amount = 1000i128;
@@ -332,6 +433,7 @@ fn test_validate_rules_invalid_duration() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     // Test invalid duration - should panic
@@ -353,6 +455,7 @@ fn test_validate_rules_invalid_max_loss() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     // Test invalid max loss percent - should panic
@@ -374,6 +477,7 @@ fn test_validate_rules_invalid_type() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     // Test invalid commitment type - should panic
@@ -409,6 +513,7 @@ fn test_create_commitment_duration_zero() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     e.as_contract(&contract_id, || {
@@ -439,6 +544,7 @@ fn test_create_commitment_max_loss_over_100() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     e.as_contract(&contract_id, || {
@@ -469,6 +575,7 @@ fn test_create_commitment_amount_zero() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     e.as_contract(&contract_id, || {
@@ -505,6 +612,7 @@ fn test_create_commitment_expiration_overflow() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     e.as_contract(&contract_id, || {
@@ -548,6 +656,7 @@ fn test_create_commitment_amount_negative() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     e.as_contract(&contract_id, || {
@@ -579,6 +688,7 @@ fn test_create_commitment_invalid_type() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     e.as_contract(&contract_id, || {
@@ -594,8 +704,8 @@ fn test_create_commitment_valid_rules() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let admin = Address::generate(&e);
     let nft_contract = Address::generate(&e);
-    let owner = Address::generate(&e);
-    let asset_address = Address::generate(&e);
+    let _owner = Address::generate(&e);
+    let _asset_address = Address::generate(&e);
 
     e.as_contract(&contract_id, || {
         CommitmentCoreContract::initialize(e.clone(), admin, nft_contract);
@@ -608,6 +718,7 @@ fn test_create_commitment_valid_rules() {
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     // This will fail at NFT minting since we don't have a real NFT contract,
@@ -650,13 +761,9 @@ fn test_list_commitments_by_owner_matches_get_owner_commitments() {
         CommitmentCoreContract::initialize(e.clone(), admin.clone(), nft_contract.clone());
 
         // Manually seed owner commitments to avoid full token setup.
-        let ids = vec![
-            &e,
-            String::from_str(&e, "c_1"),
-            String::from_str(&e, "c_2"),
-        ];
+        let ids = vec![&e, String::from_str(&e, "c_1"), String::from_str(&e, "c_2")];
         e.storage()
-            .instance()
+            .persistent()
             .set(&DataKey::OwnerCommitments(owner.clone()), &ids);
     });
 
@@ -807,7 +914,7 @@ fn test_get_commitment_returns_created_commitment_data() {
     assert_eq!(fetched.amount, amount);
     assert_eq!(fetched.current_value, amount);
     assert_eq!(fetched.asset_address, asset_address);
-    assert_eq!(fetched.status, String::from_str(&e, "active"));
+    assert_eq!(fetched.status, CommitmentStatus::Active);
 }
 
 #[test]
@@ -881,7 +988,7 @@ fn test_check_violations_loss_limit_exceeded() {
 }
 
 #[test]
-fn test_check_violations_duration_expired() {
+fn test_check_violations_ignores_expiry() {
     let e = Env::default();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let owner = Address::generate(&e);
@@ -911,7 +1018,8 @@ fn test_check_violations_duration_expired() {
         CommitmentCoreContract::check_violations(e.clone(), String::from_str(&e, commitment_id))
     });
 
-    assert!(has_violations, "Should have duration violation");
+    // Matured commitments are settled, not violated
+    assert!(!has_violations, "Expiry alone should not violate");
 }
 
 #[test]
@@ -945,7 +1053,7 @@ fn test_check_violations_both_violations() {
         CommitmentCoreContract::check_violations(e.clone(), String::from_str(&e, commitment_id))
     });
 
-    assert!(has_violations, "Should have both violations");
+    assert!(has_violations, "Loss breach should violate after expiry");
 }
 
 #[test]
@@ -974,19 +1082,19 @@ fn test_get_violation_details_no_violations() {
         l.timestamp = created_at + (15 * 86400);
     });
 
-    let (has_violations, loss_violated, duration_violated, loss_percent, time_remaining) = e
-        .as_contract(&contract_id, || {
-            CommitmentCoreContract::get_violation_details(
-                e.clone(),
-                String::from_str(&e, commitment_id),
-            )
-        });
+    let details = e.as_contract(&contract_id, || {
+        CommitmentCoreContract::get_violation_details(
+            e.clone(),
+            String::from_str(&e, commitment_id),
+        )
+    });
 
-    assert!(!has_violations, "Should not have violations");
-    assert!(!loss_violated, "Loss should not be violated");
-    assert!(!duration_violated, "Duration should not be violated");
-    assert_eq!(loss_percent, 5, "Loss percent should be 5%");
-    assert!(time_remaining > 0, "Time should remain");
+    assert!(!details.has_violations, "Should not have violations");
+    assert!(!details.loss_violated, "Loss should not be violated");
+    assert!(!details.expired, "Should not be expired");
+    assert_eq!(details.loss_percent, 5, "Loss percent should be 5%");
+    assert!(details.time_remaining > 0, "Time should remain");
+    assert_eq!(details.grace_state, GraceState::NotExpired);
 }
 
 #[test]
@@ -1015,19 +1123,18 @@ fn test_get_violation_details_loss_violation() {
     });
 
     let commitment_id_str = String::from_str(&e, commitment_id);
-    let (has_violations, loss_violated, duration_violated, loss_percent, _time_remaining) = e
-        .as_contract(&contract_id, || {
-            CommitmentCoreContract::get_violation_details(e.clone(), commitment_id_str.clone())
-        });
+    let details = e.as_contract(&contract_id, || {
+        CommitmentCoreContract::get_violation_details(e.clone(), commitment_id_str.clone())
+    });
 
-    assert!(has_violations, "Should have violations");
-    assert!(loss_violated, "Loss should be violated");
-    assert!(!duration_violated, "Duration should not be violated");
-    assert_eq!(loss_percent, 15, "Loss percent should be 15%");
+    assert!(details.has_violations, "Should have violations");
+    assert!(details.loss_violated, "Loss should be violated");
+    assert!(!details.expired, "Should not be expired");
+    assert_eq!(details.loss_percent, 15, "Loss percent should be 15%");
 }
 
 #[test]
-fn test_get_violation_details_duration_violation() {
+fn test_get_violation_details_expired_without_violation() {
     let e = Env::default();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let owner = Address::generate(&e);
@@ -1052,18 +1159,19 @@ fn test_get_violation_details_duration_violation() {
         l.timestamp = created_at + (31 * 86400);
     });
 
-    let (has_violations, loss_violated, duration_violated, _loss_percent, time_remaining) = e
-        .as_contract(&contract_id, || {
-            CommitmentCoreContract::get_violation_details(
-                e.clone(),
-                String::from_str(&e, commitment_id),
-            )
-        });
+    let details = e.as_contract(&contract_id, || {
+        CommitmentCoreContract::get_violation_details(
+            e.clone(),
+            String::from_str(&e, commitment_id),
+        )
+    });
 
-    assert!(has_violations, "Should have violations");
-    assert!(!loss_violated, "Loss should not be violated");
-    assert!(duration_violated, "Duration should be violated");
-    assert_eq!(time_remaining, 0, "Time remaining should be 0");
+    assert!(!details.has_violations, "Expiry alone should not violate");
+    assert!(!details.loss_violated, "Loss should not be violated");
+    assert!(details.expired, "Should be expired");
+    assert_eq!(details.time_remaining, 0, "Time remaining should be 0");
+    // No grace period configured: settlement is open to keepers immediately
+    assert_eq!(details.grace_state, GraceState::GraceElapsed);
 }
 
 #[test]
@@ -1134,8 +1242,8 @@ fn test_check_violations_edge_case_exact_expiry() {
         CommitmentCoreContract::check_violations(e.clone(), String::from_str(&e, commitment_id))
     });
 
-    // At expiry time, should be violated (uses >=)
-    assert!(has_violations, "At expiry time should violate");
+    // Within the loss limit, reaching expiry does not violate
+    assert!(!has_violations, "At expiry time should not violate");
 }
 
 #[test]
@@ -1179,19 +1287,20 @@ fn test_create_commitment_event() {
     let e = Env::default();
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
-    let owner = Address::generate(&e);
+    let _owner = Address::generate(&e);
     let admin = Address::generate(&e);
     let nft_contract = Address::generate(&e);
 
     client.initialize(&admin, &nft_contract);
 
-    let rules = CommitmentRules {
+    let _rules = CommitmentRules {
         duration_days: 30,
         max_loss_percent: 10,
         commitment_type: String::from_str(&e, "safe"),
         early_exit_penalty: 5,
         min_fee_threshold: 100,
         grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
     };
 
     // Note: This might panic if mock token transfers are not set up, but we are testing events.
@@ -1218,7 +1327,7 @@ fn test_update_value_event() {
     let admin = Address::generate(&e);
     let nft_contract = Address::generate(&e);
     let owner = Address::generate(&e);
-    let updater = Address::generate(&e);
+    let _updater = Address::generate(&e);
     let commitment_id = String::from_str(&e, "test_id");
 
     e.as_contract(&contract_id, || {
@@ -1240,7 +1349,7 @@ fn test_update_value_event() {
     });
 
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
-    client.update_value(&commitment_id, &1100);
+    client.update_value(&admin, &commitment_id, &1100);
 
     let updated = client.get_commitment(&commitment_id);
     assert_eq!(updated.current_value, 1100);
@@ -1256,7 +1365,7 @@ fn test_update_value_rate_limit_enforced() {
     let admin = Address::generate(&e);
     let nft_contract = Address::generate(&e);
     let owner = Address::generate(&e);
-    let updater = Address::generate(&e);
+    let _updater = Address::generate(&e);
     let commitment_id = String::from_str(&e, "rl_test");
 
     e.as_contract(&contract_id, || {
@@ -1286,9 +1395,9 @@ fn test_update_value_rate_limit_enforced() {
 
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
     // First call — allowed
-    client.update_value(&commitment_id, &100);
+    client.update_value(&admin, &commitment_id, &100);
     // Second call — should hit rate limit and panic
-    client.update_value(&commitment_id, &200);
+    client.update_value(&admin, &commitment_id, &200);
 }
 
 #[test]
@@ -1298,10 +1407,13 @@ fn test_settle_event() {
     let contract_id = e.register_contract(None, CommitmentCoreContract);
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
 
+    let caller = Address::generate(&e);
+    e.mock_all_auths();
+
     let commitment_id = String::from_str(&e, "test_id");
     // This will panic because commitment doesn't exist
     // The test verifies that the function properly validates preconditions
-    client.settle(&commitment_id);
+    client.settle(&commitment_id, &caller);
 }
 
 /// settle must only succeed when commitment has reached expiration (Issue #115).
@@ -1340,7 +1452,11 @@ fn test_settle_rejects_when_not_expired() {
     });
 
     e.as_contract(&contract_id, || {
-        CommitmentCoreContract::settle(e.clone(), String::from_str(&e, commitment_id));
+        CommitmentCoreContract::settle(
+            e.clone(),
+            String::from_str(&e, commitment_id),
+            owner.clone(),
+        );
     });
 }
 
@@ -1419,7 +1535,7 @@ fn test_allocate_when_settled_fails() {
 
     let mut commitment =
         create_test_commitment(&e, commitment_id, &owner, 1000, 1000, 10, 30, 1000);
-    commitment.status = String::from_str(&e, "settled");
+    commitment.status = CommitmentStatus::Settled;
     store_commitment(&e, &contract_id, &commitment);
 
     e.as_contract(&contract_id, || {
@@ -1451,7 +1567,7 @@ fn test_allocate_when_violated_fails() {
 
     let mut commitment =
         create_test_commitment(&e, commitment_id, &owner, 1000, 1000, 10, 30, 1000);
-    commitment.status = String::from_str(&e, "violated");
+    commitment.status = CommitmentStatus::Violated;
     store_commitment(&e, &contract_id, &commitment);
 
     e.as_contract(&contract_id, || {
@@ -1483,7 +1599,7 @@ fn test_allocate_when_early_exit_fails() {
 
     let mut commitment =
         create_test_commitment(&e, commitment_id, &owner, 1000, 1000, 10, 30, 1000);
-    commitment.status = String::from_str(&e, "early_exit");
+    commitment.status = CommitmentStatus::EarlyExit;
     store_commitment(&e, &contract_id, &commitment);
 
     e.as_contract(&contract_id, || {
@@ -1534,10 +1650,11 @@ fn test_allocate_when_active_succeeds() {
 
     let updated = client.get_commitment(&String::from_str(&e, commitment_id));
     assert_eq!(updated.current_value, 750);
-    assert_eq!(updated.status, String::from_str(&e, "active"));
+    assert_eq!(updated.status, CommitmentStatus::Active);
 }
 
 /// Helper function to create a test commitment with custom penalty
+#[allow(clippy::too_many_arguments)]
 fn create_test_commitment_with_penalty(
     e: &Env,
     commitment_id: &str,
@@ -1562,13 +1679,18 @@ fn create_test_commitment_with_penalty(
             early_exit_penalty,
             min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
         },
         amount,
+        gross_amount: amount,
         asset_address: Address::generate(e),
         created_at,
         expires_at,
         current_value,
-        status: String::from_str(e, "active"),
+        status: CommitmentStatus::Active,
+        allocated_amount: 0,
+        yield_earned: 0,
+        is_basket: false,
     }
 }
 
@@ -1651,7 +1773,7 @@ fn test_early_exit_already_settled() {
         create_test_commitment(&e, commitment_id, &owner, 1000, 1000, 10, 30, 1000);
 
     // Mark as settled
-    commitment.status = String::from_str(&e, "settled");
+    commitment.status = CommitmentStatus::Settled;
     store_commitment(&e, &contract_id, &commitment);
 
     // Try to exit already settled commitment
@@ -1684,7 +1806,7 @@ fn test_early_exit_already_violated() {
         create_test_commitment(&e, commitment_id, &owner, 1000, 1000, 10, 30, 1000);
 
     // Mark as violated
-    commitment.status = String::from_str(&e, "violated");
+    commitment.status = CommitmentStatus::Violated;
     store_commitment(&e, &contract_id, &commitment);
 
     // Try to exit violated commitment
@@ -1717,7 +1839,7 @@ fn test_early_exit_already_exited() {
         create_test_commitment(&e, commitment_id, &owner, 1000, 1000, 10, 30, 1000);
 
     // Mark as early_exit
-    commitment.status = String::from_str(&e, "early_exit");
+    commitment.status = CommitmentStatus::EarlyExit;
     store_commitment(&e, &contract_id, &commitment);
 
     // Try to exit again
@@ -1759,7 +1881,7 @@ fn test_early_exit_state_update() {
         CommitmentCoreContract::get_commitment(e.clone(), String::from_str(&e, commitment_id))
     });
 
-    assert_eq!(initial_commitment.status, String::from_str(&e, "active"));
+    assert_eq!(initial_commitment.status, CommitmentStatus::Active);
     assert_eq!(initial_commitment.current_value, 1000);
 }
 
@@ -1805,14 +1927,14 @@ fn test_early_exit_penalty_zero_current_value() {
 
 #[test]
 fn test_early_exit_penalty_with_loss() {
-    let e = Env::default();
+    let _e = Env::default();
 
     // Simulate commitment that has lost value
     // Initial: 1000, Current: 800 (20% loss)
     // Penalty on current: 800 * 10% = 80
     // Returned: 800 - 80 = 720
 
-    let initial_amount = 1000i128;
+    let _initial_amount = 1000i128;
     let current_value = 800i128;
     let penalty_percent = 10u32;
 
@@ -1826,7 +1948,7 @@ fn test_early_exit_penalty_with_loss() {
 
 #[test]
 fn test_early_exit_penalty_small_amounts() {
-    let e = Env::default();
+    let _e = Env::default();
 
     // Test with small amounts where rounding might occur
     let current_value = 10i128;
@@ -1869,11 +1991,11 @@ fn test_early_exit_event_emission() {
 
 #[test]
 fn test_early_exit_after_value_reduction() {
-    let e = Env::default();
+    let _e = Env::default();
 
     // Simulate a commitment where current_value has been reduced
     // (e.g., through allocation or loss)
-    let initial_amount = 1000i128;
+    let _initial_amount = 1000i128;
     let current_value = 700i128; // Reduced from 1000
     let penalty_percent = 10u32;
 
@@ -1963,7 +2085,7 @@ fn test_early_exit_high_penalty() {
 
 #[test]
 fn test_early_exit_conservation_invariant() {
-    let e = Env::default();
+    let _e = Env::default();
 
     // Test that penalty + returned always equals current_value (token conservation)
     let test_values = [
@@ -2007,7 +2129,7 @@ fn test_early_exit_status_transition() {
         CommitmentCoreContract::get_commitment(e.clone(), String::from_str(&e, commitment_id))
     });
 
-    assert_eq!(before.status, String::from_str(&e, "active"));
+    assert_eq!(before.status, CommitmentStatus::Active);
 }
 #[test]
 fn test_update_value_updates_without_updater_param() {
@@ -2021,7 +2143,12 @@ fn test_update_value_updates_without_updater_param() {
         CommitmentCoreContract::initialize(e.clone(), admin.clone(), nft_contract.clone());
         let commitment = create_test_commitment(&e, "test_id", &owner, 1000, 1000, 10, 30, 1000);
         set_commitment(&e, &commitment);
-        CommitmentCoreContract::update_value(e.clone(), String::from_str(&e, "test_id"), 900);
+        CommitmentCoreContract::update_value(
+            e.clone(),
+            admin.clone(),
+            String::from_str(&e, "test_id"),
+            900,
+        );
     });
 }
 
@@ -2042,19 +2169,19 @@ fn test_update_value_no_violation() {
             .set(&DataKey::TotalValueLocked, &1000i128);
     });
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
-    client.update_value(&String::from_str(&e, "test_id"), &950);
+    client.update_value(&admin, &String::from_str(&e, "test_id"), &950);
 
     let updated = client.get_commitment(&String::from_str(&e, "test_id"));
     assert_eq!(updated.current_value, 950);
-    assert_eq!(updated.status, String::from_str(&e, "active"));
+    assert_eq!(updated.status, CommitmentStatus::Active);
     assert_eq!(client.get_total_value_locked(), 950);
-    
+
     // Verify ValueUpdated event was emitted
     let events = e.events().all();
     let val_upd_symbol = symbol_short!("ValUpd").into_val(&e);
-    let has_val_upd = events.iter().any(|ev| {
-        ev.1.first().map_or(false, |t| t.shallow_eq(&val_upd_symbol))
-    });
+    let has_val_upd = events
+        .iter()
+        .any(|ev| ev.1.first().is_some_and(|t| t.shallow_eq(&val_upd_symbol)));
     assert!(has_val_upd, "ValueUpdated event should be emitted");
 }
 
@@ -2077,18 +2204,18 @@ fn test_update_value_triggers_violation() {
 
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
     // Update to 850: loss = (1000-850)/1000 = 15% > 10% max_loss_percent
-    client.update_value(&String::from_str(&e, "test_id"), &850);
+    client.update_value(&admin, &String::from_str(&e, "test_id"), &850);
 
     let updated = client.get_commitment(&String::from_str(&e, "test_id"));
     assert_eq!(updated.current_value, 850);
-    assert_eq!(updated.status, String::from_str(&e, "violated"));
-    
+    assert_eq!(updated.status, CommitmentStatus::Violated);
+
     // Verify ViolationDetected event was emitted
     let events = e.events().all();
     let violated_symbol = symbol_short!("Violated").into_val(&e);
-    let has_violation = events.iter().any(|ev| {
-        ev.1.first().map_or(false, |t| t.shallow_eq(&violated_symbol))
-    });
+    let has_violation = events
+        .iter()
+        .any(|ev| ev.1.first().is_some_and(|t| t.shallow_eq(&violated_symbol)));
     assert!(has_violation, "ViolationDetected event should be emitted");
 }
 
@@ -2110,17 +2237,2471 @@ fn test_check_violations_after_update_value() {
     });
 
     let client = CommitmentCoreContractClient::new(&e, &contract_id);
-    
+
     // Before update, no violations
     assert!(!client.check_violations(&String::from_str(&e, "test_id")));
-    
+
     // Manually update value to trigger violation without auto-detection
     e.as_contract(&contract_id, || {
         let mut commitment = read_commitment(&e, &String::from_str(&e, "test_id")).unwrap();
         commitment.current_value = 850; // 15% loss > 10% max
         set_commitment(&e, &commitment);
     });
-    
+
     // After manual update, check_violations should return true
     assert!(client.check_violations(&String::from_str(&e, "test_id")));
 }
+
+// ============================================================================
+// Protocol Treasury Tests (early-exit penalties)
+// ============================================================================
+
+/// Sets up an initialized core contract holding a funded, active commitment
+/// backed by a real token and the mock NFT contract.
+pub(crate) fn setup_funded_commitment(
+    e: &Env,
+    commitment_id: &str,
+    amount: i128,
+    penalty_percent: u32,
+) -> (Address, Address, Address, Address) {
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let nft_contract = e.register_contract(None, MockNftContract);
+    let admin = Address::generate(e);
+    let owner = Address::generate(e);
+    let token_admin = Address::generate(e);
+
+    let asset_address = e.register_stellar_asset_contract_v2(token_admin).address();
+    StellarAssetClient::new(e, &asset_address).mint(&contract_id, &amount);
+
+    e.as_contract(&contract_id, || {
+        CommitmentCoreContract::initialize(e.clone(), admin.clone(), nft_contract.clone());
+        let mut commitment =
+            create_test_commitment(e, commitment_id, &owner, amount, amount, 10, 30, 1000);
+        commitment.asset_address = asset_address.clone();
+        commitment.rules.early_exit_penalty = penalty_percent;
+        set_commitment(e, &commitment);
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalValueLocked, &amount);
+    });
+
+    (contract_id, admin, owner, asset_address)
+}
+
+#[test]
+fn test_early_exit_credits_collected_penalties() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, owner, asset_address) =
+        setup_funded_commitment(&e, "treasury_exit", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    assert_eq!(client.get_collected_penalties(&asset_address), 0);
+    client.early_exit(&String::from_str(&e, "treasury_exit"), &owner);
+
+    assert_eq!(client.get_collected_penalties(&asset_address), 100);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&owner), 900);
+    assert_eq!(token.balance(&contract_id), 100);
+}
+
+#[test]
+fn test_withdraw_penalties_to_treasury() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, owner, asset_address) =
+        setup_funded_commitment(&e, "treasury_withdraw", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let treasury = Address::generate(&e);
+
+    client.set_treasury_recipient(&admin, &treasury);
+    assert_eq!(client.get_treasury_recipient(), Some(treasury.clone()));
+
+    client.early_exit(&String::from_str(&e, "treasury_withdraw"), &owner);
+    client.withdraw_penalties(&admin, &asset_address, &60);
+
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&treasury), 60);
+    assert_eq!(client.get_collected_penalties(&asset_address), 40);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient collected penalties for withdrawal")]
+fn test_withdraw_penalties_exceeding_collected_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, owner, asset_address) =
+        setup_funded_commitment(&e, "treasury_excess", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_treasury_recipient(&admin, &Address::generate(&e));
+    client.early_exit(&String::from_str(&e, "treasury_excess"), &owner);
+    client.withdraw_penalties(&admin, &asset_address, &101);
+}
+
+#[test]
+#[should_panic(expected = "Treasury recipient not set")]
+fn test_withdraw_penalties_without_recipient_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, owner, asset_address) =
+        setup_funded_commitment(&e, "treasury_no_recipient", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.early_exit(&String::from_str(&e, "treasury_no_recipient"), &owner);
+    client.withdraw_penalties(&admin, &asset_address, &50);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_set_treasury_recipient_non_admin_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, owner, _asset_address) =
+        setup_funded_commitment(&e, "treasury_auth", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_treasury_recipient(&owner, &owner);
+}
+
+// ============================================================================
+// Grace Period & Keeper Settlement Tests
+// ============================================================================
+
+fn set_grace_period_days(e: &Env, contract_id: &Address, commitment_id: &str, days: u32) {
+    e.as_contract(contract_id, || {
+        let mut commitment = read_commitment(e, &String::from_str(e, commitment_id)).unwrap();
+        commitment.rules.grace_period_days = days;
+        set_commitment(e, &commitment);
+    });
+}
+
+#[test]
+fn test_settle_by_owner_during_grace_period() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, owner, asset_address) =
+        setup_funded_commitment(&e, "grace_owner", 1000, 10);
+    set_grace_period_days(&e, &contract_id, "grace_owner", 3);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "grace_owner");
+
+    let expires_at = client.get_commitment(&commitment_id).expires_at;
+    e.ledger().with_mut(|l| l.timestamp = expires_at + 86400);
+
+    let details = client.get_violation_details(&commitment_id);
+    assert_eq!(details.grace_state, GraceState::InGracePeriod);
+    assert_eq!(details.grace_ends_at, expires_at + 3 * 86400);
+
+    client.settle(&commitment_id, &owner);
+
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&owner), 1000);
+    assert_eq!(
+        client.get_commitment(&commitment_id).status,
+        CommitmentStatus::Settled
+    );
+}
+
+#[test]
+#[should_panic(expected = "Grace period active: only owner may settle")]
+fn test_settle_by_keeper_during_grace_period_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, _owner, _asset_address) =
+        setup_funded_commitment(&e, "grace_keeper_early", 1000, 10);
+    set_grace_period_days(&e, &contract_id, "grace_keeper_early", 3);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "grace_keeper_early");
+
+    let expires_at = client.get_commitment(&commitment_id).expires_at;
+    e.ledger().with_mut(|l| l.timestamp = expires_at + 86400);
+
+    client.settle(&commitment_id, &Address::generate(&e));
+}
+
+#[test]
+fn test_settle_by_keeper_after_grace_period_earns_fee() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, owner, asset_address) =
+        setup_funded_commitment(&e, "grace_keeper", 1000, 10);
+    set_grace_period_days(&e, &contract_id, "grace_keeper", 3);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "grace_keeper");
+    let keeper = Address::generate(&e);
+
+    client.set_keeper_fee_bps(&admin, &100); // 1%
+    let expires_at = client.get_commitment(&commitment_id).expires_at;
+    e.ledger()
+        .with_mut(|l| l.timestamp = expires_at + 3 * 86400);
+    assert_eq!(
+        client.get_violation_details(&commitment_id).grace_state,
+        GraceState::GraceElapsed
+    );
+
+    client.settle(&commitment_id, &keeper);
+
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&keeper), 10);
+    assert_eq!(token.balance(&owner), 990);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Invalid fee: basis points out of range")]
+fn test_set_keeper_fee_above_max_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, _owner, _asset_address) =
+        setup_funded_commitment(&e, "grace_fee_cap", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_keeper_fee_bps(&admin, &(MAX_KEEPER_FEE_BPS + 1));
+}
+
+// ============================================================================
+// Top-up & Partial Exit Tests
+// ============================================================================
+
+#[test]
+fn test_top_up_increases_amount_value_and_tvl() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, owner, asset_address) =
+        setup_funded_commitment(&e, "top_up", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "top_up");
+    StellarAssetClient::new(&e, &asset_address).mint(&owner, &500);
+
+    client.top_up(&commitment_id, &owner, &500);
+
+    let updated = client.get_commitment(&commitment_id);
+    assert_eq!(updated.amount, 1500);
+    assert_eq!(updated.current_value, 1500);
+    assert_eq!(client.get_total_value_locked(), 1500);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&owner), 0);
+    assert_eq!(token.balance(&contract_id), 1500);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_top_up_by_non_owner_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, _owner, _asset_address) =
+        setup_funded_commitment(&e, "top_up_auth", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.top_up(
+        &String::from_str(&e, "top_up_auth"),
+        &Address::generate(&e),
+        &500,
+    );
+}
+
+#[test]
+fn test_partial_exit_applies_pro_rated_penalty() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, owner, asset_address) =
+        setup_funded_commitment(&e, "partial_exit", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "partial_exit");
+
+    client.partial_exit(&commitment_id, &owner, &400);
+
+    let updated = client.get_commitment(&commitment_id);
+    assert_eq!(updated.current_value, 600);
+    assert_eq!(updated.amount, 600);
+    assert_eq!(updated.status, CommitmentStatus::Active);
+    assert_eq!(client.get_total_value_locked(), 600);
+    // 10% penalty on the withdrawn 400
+    assert_eq!(client.get_collected_penalties(&asset_address), 40);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&owner), 360);
+}
+
+#[test]
+fn test_partial_exit_keeps_loss_ratio() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, owner, _asset_address) =
+        setup_funded_commitment(&e, "partial_loss", 1000, 0);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "partial_loss");
+
+    // 5% loss, then withdraw half of the remaining value
+    client.update_value(&admin, &commitment_id, &950);
+    client.partial_exit(&commitment_id, &owner, &475);
+
+    let updated = client.get_commitment(&commitment_id);
+    assert_eq!(updated.current_value, 475);
+    assert_eq!(updated.amount, 500);
+    assert_eq!(client.get_violation_details(&commitment_id).loss_percent, 5);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient balance")]
+fn test_partial_exit_full_value_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, owner, _asset_address) =
+        setup_funded_commitment(&e, "partial_full", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.partial_exit(&String::from_str(&e, "partial_full"), &owner, &1000);
+}
+
+// ============================================================================
+// Valuation Role & Oracle Refresh Tests
+// ============================================================================
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_update_value_by_unregistered_caller_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, _owner, _asset_address) =
+        setup_funded_commitment(&e, "val_role", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.update_value(
+        &Address::generate(&e),
+        &String::from_str(&e, "val_role"),
+        &500,
+    );
+}
+
+#[test]
+fn test_valuation_updater_role_lifecycle() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, _owner, _asset_address) =
+        setup_funded_commitment(&e, "val_role", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "val_role");
+    let updater = Address::generate(&e);
+
+    assert!(client.is_valuation_updater(&admin));
+    assert!(!client.is_valuation_updater(&updater));
+
+    client.add_valuation_updater(&admin, &updater);
+    assert!(client.is_valuation_updater(&updater));
+    client.update_value(&updater, &commitment_id, &980);
+    assert_eq!(client.get_commitment(&commitment_id).current_value, 980);
+
+    client.remove_valuation_updater(&admin, &updater);
+    assert!(!client.is_valuation_updater(&updater));
+    let result = client.try_update_value(&updater, &commitment_id, &970);
+    assert!(result.is_err());
+}
+
+fn setup_oracle(
+    e: &Env,
+    contract_id: &Address,
+    admin: &Address,
+) -> MockOracleContractClient<'static> {
+    let oracle_id = e.register_contract(None, MockOracleContract);
+    CommitmentCoreContractClient::new(e, contract_id).set_price_oracle(admin, &oracle_id);
+    MockOracleContractClient::new(e, &oracle_id)
+}
+
+#[test]
+fn test_refresh_value_from_oracle() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, _owner, _asset_address) =
+        setup_funded_commitment(&e, "oracle_val", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let commitment_id = String::from_str(&e, "oracle_val");
+    let oracle = setup_oracle(&e, &contract_id, &admin);
+
+    // No entry valuation yet: the first refresh records 2.50 per unit as the reference
+    oracle.set_price(&250, &2, &false);
+    assert_eq!(client.refresh_value_from_oracle(&commitment_id), 2500);
+    let valuation = client.get_oracle_valuation(&commitment_id).unwrap();
+    assert_eq!(valuation.entry_value, 2500);
+
+    // 2.30 is an 8% drop: within the 10% max loss
+    oracle.set_price(&230, &2, &false);
+    assert_eq!(client.refresh_value_from_oracle(&commitment_id), 2300);
+    let updated = client.get_commitment(&commitment_id);
+    assert_eq!(updated.status, CommitmentStatus::Active);
+    // Quote valuations never reach the token-denominated value or TVL
+    assert_eq!(updated.current_value, 1000);
+    assert_eq!(client.get_total_value_locked(), 1000);
+    assert_eq!(client.get_violation_details(&commitment_id).loss_percent, 8);
+
+    // 2.00 is a 20% drop and breaches the limit
+    oracle.set_price(&200, &2, &false);
+    client.refresh_value_from_oracle(&commitment_id);
+    let violated = client.get_commitment(&commitment_id);
+    assert_eq!(violated.status, CommitmentStatus::Violated);
+    assert_eq!(violated.current_value, 1000);
+}
+
+#[test]
+fn test_oracle_entry_valuation_recorded_at_creation() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let oracle = setup_oracle(&e, &contract_id, &admin);
+    oracle.set_price(&4_000, &3, &false);
+
+    let owner = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    assert_eq!(client.get_oracle_valuation(&id).unwrap().entry_value, 4_000);
+
+    // 4.000 -> 3.800 is a 5% drop against the creation price
+    oracle.set_price(&3_800, &3, &false);
+    assert_eq!(client.refresh_value_from_oracle(&id), 3_800);
+    assert_eq!(client.get_commitment(&id).status, CommitmentStatus::Active);
+
+    // Settlement pays the committed tokens, not the quote valuation
+    advance_past_expiry(&e, 30);
+    client.settle(&id, &owner);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&owner), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Oracle price is stale")]
+fn test_refresh_value_from_oracle_rejects_stale_price() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, _owner, _asset_address) =
+        setup_funded_commitment(&e, "oracle_stale", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let oracle = setup_oracle(&e, &contract_id, &admin);
+
+    oracle.set_price(&100, &2, &true);
+    client.refresh_value_from_oracle(&String::from_str(&e, "oracle_stale"));
+}
+
+#[test]
+#[should_panic(expected = "Price oracle not configured")]
+fn test_refresh_value_from_oracle_without_oracle_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, _owner, _asset_address) =
+        setup_funded_commitment(&e, "oracle_unset", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.refresh_value_from_oracle(&String::from_str(&e, "oracle_unset"));
+}
+
+// ============================================================================
+// Status State Machine & Migration Tests
+// ============================================================================
+
+#[test]
+fn test_status_transitions() {
+    use CommitmentStatus::*;
+    for next in [Settled, Violated, EarlyExit] {
+        assert!(Active.can_transition_to(next));
+    }
+    for terminal in [Settled, Violated, EarlyExit] {
+        for next in [Active, Settled, Violated, EarlyExit] {
+            assert!(!terminal.can_transition_to(next));
+        }
+    }
+    assert!(!Active.can_transition_to(Active));
+}
+
+fn store_legacy_commitment(e: &Env, id: &str, status: &str) {
+    let owner = Address::generate(e);
+    let legacy = LegacyCommitment {
+        commitment_id: String::from_str(e, id),
+        owner,
+        nft_token_id: 1,
+        rules: test_rules(e),
+        amount: 1000,
+        asset_address: Address::generate(e),
+        created_at: 0,
+        expires_at: 86400 * 30,
+        current_value: 1000,
+        status: String::from_str(e, status),
+    };
+    e.storage()
+        .instance()
+        .set(&DataKey::Commitment(String::from_str(e, id)), &legacy);
+}
+
+#[test]
+fn test_migrate_converts_legacy_string_statuses() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let admin = Address::generate(&e);
+    let nft_contract = Address::generate(&e);
+
+    e.as_contract(&contract_id, || {
+        CommitmentCoreContract::initialize(e.clone(), admin.clone(), nft_contract.clone());
+        // Simulate a pre-versioning deployment
+        e.storage().instance().remove(&DataKey::Version);
+        store_legacy_commitment(&e, "c_0", "active");
+        store_legacy_commitment(&e, "c_1", "early_exit");
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalCommitments, &2u64);
+    });
+
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.migrate(&admin, &0);
+
+    assert_eq!(
+        client.get_commitment(&String::from_str(&e, "c_0")).status,
+        CommitmentStatus::Active
+    );
+    assert_eq!(
+        client.get_commitment(&String::from_str(&e, "c_1")).status,
+        CommitmentStatus::EarlyExit
+    );
+}
+
+#[test]
+#[should_panic(expected = "Contract storage already migrated")]
+fn test_migrate_on_current_version_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let admin = Address::generate(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.initialize(&admin, &Address::generate(&e));
+
+    client.migrate(&admin, &0);
+}
+
+// ============================================================================
+// Persistent Storage & TTL Tests
+// ============================================================================
+
+fn commitment_ttl(e: &Env, contract_id: &Address, id: &str) -> u32 {
+    use soroban_sdk::testutils::storage::Persistent as _;
+    e.as_contract(contract_id, || {
+        e.storage()
+            .persistent()
+            .get_ttl(&DataKey::Commitment(String::from_str(e, id)))
+    })
+}
+
+#[test]
+fn test_commitment_written_to_persistent_with_ttl() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, _owner, _asset_address) =
+        setup_funded_commitment(&e, "ttl_write", 1000, 10);
+
+    e.as_contract(&contract_id, || {
+        let key = DataKey::Commitment(String::from_str(&e, "ttl_write"));
+        assert!(e.storage().persistent().has(&key));
+        assert!(!e.storage().instance().has(&key));
+    });
+    assert!(commitment_ttl(&e, &contract_id, "ttl_write") >= COMMITMENT_TTL_THRESHOLD);
+}
+
+#[test]
+fn test_bump_commitment_extends_ttl() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, _owner, _asset_address) =
+        setup_funded_commitment(&e, "ttl_bump", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    // Let most of the TTL run down, then bump
+    e.as_contract(&contract_id, || {
+        e.storage()
+            .instance()
+            .extend_ttl(COMMITMENT_TTL_EXTEND_TO, COMMITMENT_TTL_EXTEND_TO)
+    });
+    e.ledger()
+        .with_mut(|l| l.sequence_number += COMMITMENT_TTL_THRESHOLD);
+    let before = commitment_ttl(&e, &contract_id, "ttl_bump");
+    assert!(before < COMMITMENT_TTL_THRESHOLD);
+
+    client.bump_commitment(&String::from_str(&e, "ttl_bump"));
+    assert_eq!(
+        commitment_ttl(&e, &contract_id, "ttl_bump"),
+        COMMITMENT_TTL_EXTEND_TO
+    );
+}
+
+#[test]
+fn test_bump_commitment_keeps_settle_working_past_ttl() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    client.update_value(&admin, &id, &1_000);
+    let nft_contract = client.get_nft_contract();
+
+    // Keep the contracts themselves alive while the commitment's entries run down.
+    let keep_alive = |e: &Env| {
+        for contract in [&contract_id, &nft_contract, &asset_address] {
+            e.as_contract(contract, || {
+                e.storage()
+                    .instance()
+                    .extend_ttl(COMMITMENT_TTL_EXTEND_TO, COMMITMENT_TTL_EXTEND_TO)
+            });
+        }
+        // Refreshes the token balance entries settle pays from and to
+        StellarAssetClient::new(e, &asset_address).mint(&contract_id, &1);
+        StellarAssetClient::new(e, &asset_address).mint(&owner, &1);
+    };
+    keep_alive(&e);
+
+    e.ledger()
+        .with_mut(|l| l.sequence_number += COMMITMENT_TTL_THRESHOLD);
+    keep_alive(&e);
+    client.bump_commitment(&id);
+
+    // Past the original TTL of everything written at creation
+    e.ledger()
+        .with_mut(|l| l.sequence_number += 2 * DAY_IN_LEDGERS);
+    keep_alive(&e);
+    advance_past_expiry(&e, 30);
+
+    client.settle(&id, &owner);
+    assert_eq!(client.get_commitment(&id).status, CommitmentStatus::Settled);
+}
+
+#[test]
+#[should_panic(expected = "Commitment not found")]
+fn test_bump_missing_commitment_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.initialize(&Address::generate(&e), &Address::generate(&e));
+
+    client.bump_commitment(&String::from_str(&e, "missing"));
+}
+
+#[test]
+fn test_migrate_moves_instance_commitments_to_persistent() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let admin = Address::generate(&e);
+    let owner = Address::generate(&e);
+
+    e.as_contract(&contract_id, || {
+        CommitmentCoreContract::initialize(e.clone(), admin.clone(), Address::generate(&e));
+        // Simulate a v1 deployment that kept everything in instance storage
+        e.storage().instance().set(&DataKey::Version, &1u32);
+        let commitment = create_test_commitment(&e, "c_0", &owner, 1000, 1000, 10, 30, 0);
+        e.storage().instance().set(
+            &DataKey::Commitment(String::from_str(&e, "c_0")),
+            &pre_fee_layout(&commitment),
+        );
+        e.storage().instance().set(
+            &DataKey::OwnerCommitments(owner.clone()),
+            &vec![&e, String::from_str(&e, "c_0")],
+        );
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalCommitments, &1u64);
+    });
+
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.migrate(&admin, &1);
+
+    e.as_contract(&contract_id, || {
+        assert!(!e
+            .storage()
+            .instance()
+            .has(&DataKey::Commitment(String::from_str(&e, "c_0"))));
+        assert!(!e
+            .storage()
+            .instance()
+            .has(&DataKey::OwnerCommitments(owner.clone())));
+    });
+    assert_eq!(
+        client.get_commitment(&String::from_str(&e, "c_0")).owner,
+        owner
+    );
+    assert_eq!(client.get_owner_commitments(&owner).len(), 1);
+}
+
+/// The v1-v3 record layout: a current `Commitment` without the fields added since.
+fn pre_fee_layout(c: &Commitment) -> Map<Symbol, Val> {
+    let e = c.owner.env();
+    let raw: Val = c.into_val(e);
+    let mut fields = Map::<Symbol, Val>::try_from_val(e, &raw).unwrap();
+    for name in [
+        "gross_amount",
+        "allocated_amount",
+        "yield_earned",
+        "is_basket",
+    ] {
+        fields.remove(Symbol::new(e, name));
+    }
+    let rules_key = Symbol::new(e, "rules");
+    let mut rules =
+        Map::<Symbol, Val>::try_from_val(e, &fields.get(rules_key.clone()).unwrap()).unwrap();
+    rules.remove(Symbol::new(e, "transfer_policy"));
+    fields.set(rules_key, rules.to_val());
+    fields
+}
+
+#[test]
+fn test_migrate_adds_new_commitment_fields() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let admin = Address::generate(&e);
+    let owner = Address::generate(&e);
+
+    e.as_contract(&contract_id, || {
+        CommitmentCoreContract::initialize(e.clone(), admin.clone(), Address::generate(&e));
+        e.storage().instance().set(&DataKey::Version, &3u32);
+        let commitment = create_test_commitment(&e, "c_0", &owner, 750, 800, 10, 30, 0);
+        e.storage().persistent().set(
+            &DataKey::Commitment(String::from_str(&e, "c_0")),
+            &pre_fee_layout(&commitment),
+        );
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalCommitments, &1u64);
+    });
+
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.migrate(&admin, &3);
+
+    let migrated = client.get_commitment(&String::from_str(&e, "c_0"));
+    assert_eq!(migrated.amount, 750);
+    assert_eq!(migrated.gross_amount, 750);
+    assert_eq!(migrated.current_value, 800);
+    assert_eq!(migrated.allocated_amount, 0);
+    assert_eq!(migrated.yield_earned, 0);
+    assert_eq!(migrated.rules.transfer_policy, TransferPolicy::Free);
+    assert_eq!(client.get_asset_locked(&migrated.asset_address), 750);
+    assert_eq!(
+        client
+            .get_asset_metrics(&migrated.asset_address)
+            .value_locked,
+        800
+    );
+    assert_eq!(client.get_version(), CURRENT_VERSION);
+}
+
+#[test]
+fn test_migrate_moves_basket_value_to_oracle_valuation() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let admin = Address::generate(&e);
+    let owner = Address::generate(&e);
+    let id = String::from_str(&e, "c_0");
+
+    e.as_contract(&contract_id, || {
+        CommitmentCoreContract::initialize(e.clone(), admin.clone(), Address::generate(&e));
+        e.storage().instance().set(&DataKey::Version, &8u32);
+        // v8 baskets kept their quote-unit value in amount and current_value
+        let commitment = create_test_commitment(&e, "c_0", &owner, 2_000, 1_900, 10, 30, 0);
+        let raw: Val = commitment.clone().into_val(&e);
+        let mut fields = Map::<Symbol, Val>::try_from_val(&e, &raw).unwrap();
+        fields.remove(Symbol::new(&e, "is_basket"));
+        e.storage()
+            .persistent()
+            .set(&DataKey::Commitment(id.clone()), &fields);
+        let holdings = vec![
+            &e,
+            (commitment.asset_address.clone(), 1_000i128),
+            (Address::generate(&e), 450i128),
+        ];
+        e.storage()
+            .persistent()
+            .set(&DataKey::Holdings(id.clone()), &holdings);
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalCommitments, &1u64);
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalValueLocked, &1_900i128);
+    });
+
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.migrate(&admin, &8);
+
+    let migrated = client.get_commitment(&id);
+    assert!(migrated.is_basket);
+    assert_eq!(migrated.amount, 0);
+    assert_eq!(migrated.current_value, 0);
+    let valuation = client.get_oracle_valuation(&id).unwrap();
+    assert_eq!(valuation.entry_value, 2_000);
+    assert_eq!(valuation.value, 1_900);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+// ============================================================================
+// Upgrade & Admin Tests
+// ============================================================================
+
+fn upload_wasm(e: &Env) -> BytesN<32> {
+    // Empty WASM is accepted in testutils and is sufficient for upgrade tests.
+    e.deployer().upload_contract_wasm(Bytes::new(e))
+}
+
+#[test]
+fn test_initialize_sets_current_version() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    assert_eq!(client.get_version(), 0);
+
+    client.initialize(&Address::generate(&e), &Address::generate(&e));
+    assert_eq!(client.get_version(), CURRENT_VERSION);
+}
+
+#[test]
+fn test_upgrade_preserves_state() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, owner, _asset_address) =
+        setup_funded_commitment(&e, "upgrade_state", 1000, 10);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.upgrade(&admin, &upload_wasm(&e));
+
+    let commitment = client.get_commitment(&String::from_str(&e, "upgrade_state"));
+    assert_eq!(commitment.owner, owner);
+    assert_eq!(client.get_total_value_locked(), 1000);
+    assert_eq!(client.get_version(), CURRENT_VERSION);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_upgrade_by_non_admin_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.initialize(&Address::generate(&e), &Address::generate(&e));
+
+    client.upgrade(&Address::generate(&e), &upload_wasm(&e));
+}
+
+#[test]
+#[should_panic(expected = "Invalid WASM hash")]
+fn test_upgrade_with_zero_hash_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    client.initialize(&admin, &Address::generate(&e));
+
+    client.upgrade(&admin, &BytesN::from_array(&e, &[0; 32]));
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    let new_admin = Address::generate(&e);
+    client.initialize(&admin, &Address::generate(&e));
+
+    client.propose_admin(&admin, &new_admin);
+    // Proposal alone does not hand over rights
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert!(client.try_accept_admin(&Address::generate(&e)).is_err());
+
+    client.accept_admin(&new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+    assert!(client.try_set_keeper_fee_bps(&admin, &10).is_err());
+    client.set_keeper_fee_bps(&new_admin, &10);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_accept_admin_after_cancel_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    let new_admin = Address::generate(&e);
+    client.initialize(&admin, &Address::generate(&e));
+
+    client.propose_admin(&admin, &new_admin);
+    client.cancel_admin_proposal(&admin);
+    client.accept_admin(&new_admin);
+}
+
+#[test]
+fn test_set_admin_only_nominates() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let admin = Address::generate(&e);
+    let new_admin = Address::generate(&e);
+    client.initialize(&admin, &Address::generate(&e));
+
+    client.set_admin(&admin, &new_admin);
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+
+    client.accept_admin(&new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+}
+
+// ============================================================================
+// Paginated Query Tests
+// ============================================================================
+
+/// Core wired to a mock NFT and a real token; returns (contract_id, admin, asset_address).
+pub(crate) fn setup_core_with_token(e: &Env) -> (Address, Address, Address) {
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let nft_contract = e.register_contract(None, MockNftContract);
+    let admin = Address::generate(e);
+    let asset_address = e
+        .register_stellar_asset_contract_v2(Address::generate(e))
+        .address();
+    CommitmentCoreContractClient::new(e, &contract_id).initialize(&admin, &nft_contract);
+    (contract_id, admin, asset_address)
+}
+
+fn create_funded(
+    e: &Env,
+    client: &CommitmentCoreContractClient,
+    owner: &Address,
+    asset_address: &Address,
+    amount: i128,
+) -> String {
+    StellarAssetClient::new(e, asset_address).mint(owner, &amount);
+    client.create_commitment(owner, &amount, asset_address, &test_rules(e))
+}
+
+#[test]
+fn test_list_commitments_pages_in_creation_order() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    for amount in [100, 200, 300] {
+        create_funded(&e, &client, &owner, &asset_address, amount);
+    }
+
+    let first = client.list_commitments(&0, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(0).unwrap().amount, 100);
+    assert_eq!(first.get(1).unwrap().amount, 200);
+
+    let rest = client.list_commitments(&2, &10);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest.get(0).unwrap().amount, 300);
+    assert_eq!(client.list_commitments(&5, &10).len(), 0);
+}
+
+#[test]
+fn test_list_commitments_by_owner_paged() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let alice = Address::generate(&e);
+    let bob = Address::generate(&e);
+    create_funded(&e, &client, &alice, &asset_address, 100);
+    create_funded(&e, &client, &bob, &asset_address, 200);
+    create_funded(&e, &client, &alice, &asset_address, 300);
+
+    let page = client.list_commitments_by_owner_paged(&alice, &1, &5);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().amount, 300);
+    assert_eq!(
+        client.list_commitments_by_owner_paged(&bob, &0, &5).len(),
+        1
+    );
+}
+
+#[test]
+fn test_list_commitments_by_status_tracks_transitions() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let first = create_funded(&e, &client, &owner, &asset_address, 100);
+    let second = create_funded(&e, &client, &owner, &asset_address, 200);
+    create_funded(&e, &client, &owner, &asset_address, 300);
+    assert_eq!(client.get_status_count(&CommitmentStatus::Active), 3);
+
+    client.early_exit(&first, &owner);
+    client.early_exit(&second, &owner);
+
+    let active = client.list_commitments_by_status(&CommitmentStatus::Active, &0, &10);
+    assert_eq!(active.len(), 1);
+    assert_eq!(active.get(0).unwrap().amount, 300);
+    let exited = client.list_commitments_by_status(&CommitmentStatus::EarlyExit, &0, &10);
+    assert_eq!(exited.len(), 2);
+    assert!(exited
+        .iter()
+        .all(|c| c.status == CommitmentStatus::EarlyExit));
+    assert_eq!(client.get_status_count(&CommitmentStatus::Settled), 0);
+}
+
+#[test]
+fn test_list_commitments_caps_page_size() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, CommitmentCoreContract);
+    let owner = Address::generate(&e);
+
+    e.as_contract(&contract_id, || {
+        CommitmentCoreContract::initialize(e.clone(), Address::generate(&e), owner.clone());
+        for counter in 0..(MAX_PAGE_SIZE as u64 + 5) {
+            let id = CommitmentCoreContract::generate_commitment_id(&e, counter);
+            let mut commitment = create_test_commitment(&e, "x", &owner, 1, 1, 10, 30, 0);
+            commitment.commitment_id = id;
+            set_commitment(&e, &commitment);
+        }
+        e.storage()
+            .instance()
+            .set(&DataKey::TotalCommitments, &(MAX_PAGE_SIZE as u64 + 5));
+    });
+
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    assert_eq!(client.list_commitments(&0, &u32::MAX).len(), MAX_PAGE_SIZE);
+}
+
+// ============================================================================
+// Creation Fee Tests
+// ============================================================================
+
+#[test]
+fn test_create_commitment_deducts_creation_fee() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+
+    client.set_creation_fee_bps(&admin, &100); // 1%
+    let id = create_funded(&e, &client, &owner, &asset_address, 10_000);
+
+    let commitment = client.get_commitment(&id);
+    assert_eq!(commitment.gross_amount, 10_000);
+    assert_eq!(commitment.amount, 9_900);
+    assert_eq!(commitment.current_value, 9_900);
+    assert_eq!(client.get_collected_fees(&asset_address), 100);
+    assert_eq!(client.get_total_value_locked(), 9_900);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&contract_id), 10_000);
+}
+
+#[test]
+fn test_create_commitment_without_fee_locks_full_amount() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 5_000);
+
+    let commitment = client.get_commitment(&id);
+    assert_eq!(commitment.amount, commitment.gross_amount);
+    assert_eq!(client.get_collected_fees(&asset_address), 0);
+}
+
+#[test]
+fn test_withdraw_fees_to_recipient() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let recipient = Address::generate(&e);
+
+    client.set_creation_fee_bps(&admin, &250);
+    client.set_fee_recipient(&admin, &recipient);
+    assert_eq!(client.get_fee_recipient(), Some(recipient.clone()));
+    create_funded(&e, &client, &Address::generate(&e), &asset_address, 4_000);
+
+    client.withdraw_fees(&admin, &asset_address, &60);
+
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&recipient), 60);
+    assert_eq!(client.get_collected_fees(&asset_address), 40);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient collected fees for withdrawal")]
+fn test_withdraw_fees_exceeding_collected_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_creation_fee_bps(&admin, &100);
+    client.set_fee_recipient(&admin, &Address::generate(&e));
+    create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+    client.withdraw_fees(&admin, &asset_address, &11);
+}
+
+#[test]
+#[should_panic(expected = "Fee recipient not set")]
+fn test_withdraw_fees_without_recipient_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_creation_fee_bps(&admin, &100);
+    create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+    client.withdraw_fees(&admin, &asset_address, &10);
+}
+
+#[test]
+#[should_panic(expected = "Invalid fee: basis points out of range")]
+fn test_set_creation_fee_above_max_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, _asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_creation_fee_bps(&admin, &(MAX_CREATION_FEE_BPS + 1));
+}
+
+// ============================================================================
+// Rollover Tests
+// ============================================================================
+
+fn advance_past_expiry(e: &Env, days: u64) {
+    e.ledger().with_mut(|l| {
+        l.timestamp += days * 86400 + 1;
+    });
+}
+
+#[test]
+fn test_rollover_links_successor_without_moving_tokens() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let old_id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    client.update_value(&admin, &old_id, &1_050);
+    advance_past_expiry(&e, 30);
+
+    let mut new_rules = test_rules(&e);
+    new_rules.duration_days = 60;
+    let new_id = client.rollover(&old_id, &new_rules);
+
+    let old = client.get_commitment(&old_id);
+    let successor = client.get_commitment(&new_id);
+    assert_eq!(old.status, CommitmentStatus::Settled);
+    assert_eq!(successor.status, CommitmentStatus::Active);
+    assert_eq!(successor.owner, owner);
+    assert_eq!(successor.amount, 1_050);
+    assert_eq!(successor.rules.duration_days, 60);
+    assert_eq!(client.get_successor(&old_id), Some(new_id.clone()));
+    assert_eq!(client.get_predecessor(&new_id), Some(old_id.clone()));
+    assert_eq!(client.get_predecessor(&old_id), None);
+    assert_eq!(client.get_owner_commitments(&owner).len(), 2);
+    assert_eq!(client.get_status_count(&CommitmentStatus::Active), 1);
+    assert_eq!(client.get_total_value_locked(), 1_050);
+
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&contract_id), 1_000);
+    assert_eq!(token.balance(&owner), 0);
+}
+
+#[test]
+#[should_panic(expected = "Commitment has not expired yet")]
+fn test_rollover_before_expiry_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+
+    client.rollover(&id, &test_rules(&e));
+}
+
+#[test]
+#[should_panic(expected = "Commitment is not active")]
+fn test_rollover_twice_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+    advance_past_expiry(&e, 30);
+
+    client.rollover(&id, &test_rules(&e));
+    client.rollover(&id, &test_rules(&e));
+}
+
+// ============================================================================
+// Allocation Return & Yield Tests
+// ============================================================================
+
+#[test]
+fn test_deallocate_returns_principal_from_pool() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let pool = Address::generate(&e);
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+
+    client.allocate(&id, &pool, &600);
+    assert_eq!(client.get_allocation(&id, &pool), 600);
+    assert_eq!(client.get_commitment(&id).allocated_amount, 600);
+
+    client.deallocate(&id, &pool, &400);
+
+    let commitment = client.get_commitment(&id);
+    assert_eq!(commitment.allocated_amount, 200);
+    assert_eq!(commitment.current_value, 800);
+    assert_eq!(client.get_allocation(&id, &pool), 200);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&pool), 200);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient allocation in pool for deallocation")]
+fn test_deallocate_more_than_allocated_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let pool = Address::generate(&e);
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+
+    client.allocate(&id, &pool, &300);
+    client.deallocate(&id, &pool, &301);
+}
+
+#[test]
+#[should_panic(expected = "Commitment is not active")]
+fn test_deallocate_after_settle_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let pool = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    client.allocate(&id, &pool, &400);
+    client.deallocate(&id, &pool, &400);
+    advance_past_expiry(&e, 30);
+    client.settle(&id, &owner);
+
+    client.deallocate(&id, &pool, &1);
+}
+
+#[test]
+fn test_record_loss_unblocks_settlement() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let pool = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+
+    // The pool can only return 50 of the 80 allocated
+    client.allocate(&id, &pool, &80);
+    client.deallocate(&id, &pool, &50);
+    client.record_loss(&pool, &id, &pool, &30);
+
+    let commitment = client.get_commitment(&id);
+    assert_eq!(commitment.allocated_amount, 0);
+    assert_eq!(commitment.current_value, 970);
+    assert_eq!(client.get_allocation(&id, &pool), 0);
+    assert_eq!(client.get_total_value_locked(), 970);
+
+    advance_past_expiry(&e, 30);
+    client.settle(&id, &owner);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&owner), 970);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_record_loss_by_stranger_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let pool = Address::generate(&e);
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+    client.allocate(&id, &pool, &80);
+
+    client.record_loss(&Address::generate(&e), &id, &pool, &80);
+}
+
+#[test]
+fn test_settle_pays_principal_plus_yield_less_performance_fee() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let pool = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    client.set_performance_fee_bps(&admin, &1_000); // 10% of yield
+
+    client.allocate(&id, &pool, &1_000);
+    StellarAssetClient::new(&e, &asset_address).mint(&pool, &200);
+    client.record_return(&id, &pool, &200);
+    client.deallocate(&id, &pool, &1_000);
+
+    let commitment = client.get_commitment(&id);
+    assert_eq!(commitment.amount, 1_000);
+    assert_eq!(commitment.yield_earned, 200);
+    assert_eq!(commitment.current_value, 1_200);
+    assert_eq!(client.get_total_value_locked(), 1_200);
+
+    advance_past_expiry(&e, 30);
+    client.settle(&id, &owner);
+
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&owner), 1_180);
+    assert_eq!(client.get_collected_fees(&asset_address), 20);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+/// Run a commitment's principal through a pool that returns `yield_amount`.
+fn earn_yield(
+    e: &Env,
+    client: &CommitmentCoreContractClient,
+    asset_address: &Address,
+    commitment_id: &String,
+    yield_amount: i128,
+) {
+    let pool = Address::generate(e);
+    let principal = client.get_commitment(commitment_id).amount;
+    client.allocate(commitment_id, &pool, &principal);
+    StellarAssetClient::new(e, asset_address).mint(&pool, &yield_amount);
+    client.record_return(commitment_id, &pool, &yield_amount);
+    client.deallocate(commitment_id, &pool, &principal);
+}
+
+#[test]
+fn test_early_exit_takes_performance_fee_before_penalty() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    client.set_performance_fee_bps(&admin, &1_000); // 10% of yield
+    earn_yield(&e, &client, &asset_address, &id, 200);
+
+    // 1_200 less a 20 performance fee, then the 5% penalty on the 1_180 left
+    client.early_exit(&id, &owner);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(client.get_collected_fees(&asset_address), 20);
+    assert_eq!(client.get_collected_penalties(&asset_address), 59);
+    assert_eq!(token.balance(&owner), 1_121);
+}
+
+#[test]
+fn test_partial_exit_takes_performance_fee_on_withdrawn_yield() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    client.set_performance_fee_bps(&admin, &1_000); // 10% of yield
+    earn_yield(&e, &client, &asset_address, &id, 200);
+
+    // Half the value carries half the yield: a 10 fee, then 5% of the 590 left
+    client.partial_exit(&id, &owner, &600);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(client.get_collected_fees(&asset_address), 10);
+    assert_eq!(client.get_collected_penalties(&asset_address), 29);
+    assert_eq!(token.balance(&owner), 561);
+    let commitment = client.get_commitment(&id);
+    assert_eq!(commitment.amount, 500);
+    assert_eq!(commitment.yield_earned, 100);
+    assert_eq!(commitment.current_value, 600);
+
+    // The remaining yield is charged once, at settlement
+    advance_past_expiry(&e, 30);
+    client.settle(&id, &owner);
+    assert_eq!(client.get_collected_fees(&asset_address), 20);
+    assert_eq!(token.balance(&owner), 561 + 590);
+}
+
+#[test]
+fn test_emergency_exit_takes_performance_fee() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    client.set_performance_fee_bps(&admin, &1_000); // 10% of yield
+    earn_yield(&e, &client, &asset_address, &id, 200);
+
+    client.set_emergency_mode(&admin, &true);
+    client.emergency_exit(&id, &owner);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(client.get_collected_fees(&asset_address), 20);
+    assert_eq!(client.get_collected_penalties(&asset_address), 0);
+    assert_eq!(token.balance(&owner), 1_180);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Commitment has funds allocated to pools")]
+fn test_settle_with_outstanding_allocation_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+
+    client.allocate(&id, &Address::generate(&e), &500);
+    advance_past_expiry(&e, 30);
+    client.settle(&id, &owner);
+}
+
+#[test]
+#[should_panic(expected = "Invalid fee: basis points out of range")]
+fn test_set_performance_fee_above_max_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, _asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_performance_fee_bps(&admin, &(MAX_PERFORMANCE_FEE_BPS + 1));
+}
+
+// ============================================================================
+// Multi-Asset Commitment Tests
+// ============================================================================
+
+/// Basket of 1000 A at 1.00 and 500 B at 2.00 (aggregate value 2000).
+fn setup_basket(
+    e: &Env,
+) -> (
+    CommitmentCoreContractClient<'static>,
+    Address,
+    String,
+    Address,
+    Address,
+    MockOracleContractClient<'static>,
+) {
+    let (contract_id, admin, asset_a) = setup_core_with_token(e);
+    let asset_b = e
+        .register_stellar_asset_contract_v2(Address::generate(e))
+        .address();
+    let client = CommitmentCoreContractClient::new(e, &contract_id);
+    let oracle = setup_oracle(e, &contract_id, &admin);
+    oracle.set_price(&100, &2, &false);
+    oracle.set_asset_price(&asset_b, &200);
+
+    let owner = Address::generate(e);
+    StellarAssetClient::new(e, &asset_a).mint(&owner, &1_000);
+    StellarAssetClient::new(e, &asset_b).mint(&owner, &500);
+    let holdings = vec![e, (asset_a.clone(), 1_000i128), (asset_b.clone(), 500i128)];
+    let id = client.create_multi_asset_commitment(&owner, &holdings, &test_rules(e));
+    (client, owner, id, asset_a, asset_b, oracle)
+}
+
+/// Per-asset amounts reported by basket payout events.
+type AssetAmounts = Vec<(Address, i128)>;
+
+/// Data of the last event `contract` published under `topic`.
+fn event_data(e: &Env, contract: &Address, topic: Symbol) -> soroban_sdk::Val {
+    e.events()
+        .all()
+        .iter()
+        .filter(|ev| {
+            ev.0 == *contract
+                && ev
+                    .1
+                    .first()
+                    .is_some_and(|t| Symbol::try_from_val(e, &t) == Ok(topic.clone()))
+        })
+        .last()
+        .expect("event not published")
+        .2
+}
+
+#[test]
+fn test_create_multi_asset_commitment_values_basket() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (client, owner, id, asset_a, asset_b, _oracle) = setup_basket(&e);
+
+    let commitment = client.get_commitment(&id);
+    assert_eq!(commitment.owner, owner);
+    assert!(commitment.is_basket);
+    // The aggregate value lives in the oracle valuation, not the single-asset fields
+    assert_eq!(commitment.amount, 0);
+    assert_eq!(commitment.current_value, 0);
+    assert_eq!(client.get_oracle_valuation(&id).unwrap().entry_value, 2_000);
+    assert_eq!(
+        client.get_holdings(&id),
+        vec![&e, (asset_a.clone(), 1_000i128), (asset_b.clone(), 500i128)]
+    );
+    let token_b = soroban_sdk::token::Client::new(&e, &asset_b);
+    assert_eq!(token_b.balance(&client.address), 500);
+
+    // Basket value is in quote units, so it stays out of TVL and the per-asset metrics
+    let metrics = client.get_protocol_metrics();
+    assert_eq!(metrics.basket.value_locked, 2_000);
+    assert_eq!(client.get_asset_metrics(&asset_a).value_locked, 0);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+#[test]
+fn test_multi_asset_early_exit_returns_each_asset_less_penalty() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (client, owner, id, asset_a, asset_b, _oracle) = setup_basket(&e);
+
+    client.early_exit(&id, &owner);
+
+    // The event reports each asset's penalty and returned amount
+    let (penalties, returned, _): (AssetAmounts, AssetAmounts, u64) =
+        event_data(&e, &client.address, symbol_short!("EarlyExt")).into_val(&e);
+    assert_eq!(
+        penalties,
+        vec![&e, (asset_a.clone(), 50i128), (asset_b.clone(), 25i128)]
+    );
+    assert_eq!(
+        returned,
+        vec![&e, (asset_a.clone(), 950i128), (asset_b.clone(), 475i128)]
+    );
+
+    let token_a = soroban_sdk::token::Client::new(&e, &asset_a);
+    let token_b = soroban_sdk::token::Client::new(&e, &asset_b);
+    assert_eq!(token_a.balance(&owner), 950);
+    assert_eq!(token_b.balance(&owner), 475);
+    assert_eq!(client.get_collected_penalties(&asset_a), 50);
+    assert_eq!(client.get_collected_penalties(&asset_b), 25);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+#[test]
+fn test_multi_asset_settle_returns_every_asset() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (client, owner, id, asset_a, asset_b, _oracle) = setup_basket(&e);
+
+    advance_past_expiry(&e, 30);
+    client.settle(&id, &owner);
+
+    let (settled, _): (AssetAmounts, u64) =
+        event_data(&e, &client.address, symbol_short!("Settled")).into_val(&e);
+    assert_eq!(settled, client.get_holdings(&id));
+
+    let token_a = soroban_sdk::token::Client::new(&e, &asset_a);
+    let token_b = soroban_sdk::token::Client::new(&e, &asset_b);
+    assert_eq!(token_a.balance(&owner), 1_000);
+    assert_eq!(token_b.balance(&owner), 500);
+    assert_eq!(client.get_commitment(&id).status, CommitmentStatus::Settled);
+}
+
+#[test]
+fn test_multi_asset_resolve_violation_reports_each_asset() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (client, owner, id, asset_a, asset_b, oracle) = setup_basket(&e);
+    let admin = client.get_admin();
+    client.set_violation_penalty_bps(&admin, &1_000);
+
+    oracle.set_asset_price(&asset_b, &100);
+    client.refresh_value_from_oracle(&id);
+    client.resolve_violation(&id);
+
+    let (penalties, returned, _): (AssetAmounts, AssetAmounts, u64) =
+        event_data(&e, &client.address, Symbol::new(&e, "ViolationResolved")).into_val(&e);
+    assert_eq!(
+        penalties,
+        vec![&e, (asset_a.clone(), 100i128), (asset_b.clone(), 50i128)]
+    );
+    assert_eq!(
+        returned,
+        vec![&e, (asset_a.clone(), 900i128), (asset_b.clone(), 450i128)]
+    );
+    let token_b = soroban_sdk::token::Client::new(&e, &asset_b);
+    assert_eq!(token_b.balance(&owner), 450);
+}
+
+#[test]
+fn test_multi_asset_oracle_valuation_enforces_loss_limit() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (client, _owner, id, _asset_a, asset_b, oracle) = setup_basket(&e);
+
+    // B halves: basket drops from 2000 to 1500, past the 10% max loss
+    oracle.set_asset_price(&asset_b, &100);
+    assert_eq!(client.refresh_value_from_oracle(&id), 1_500);
+    assert_eq!(client.get_protocol_metrics().basket.value_locked, 1_500);
+    assert_eq!(
+        client.get_commitment(&id).status,
+        CommitmentStatus::Violated
+    );
+}
+
+#[test]
+#[should_panic(expected = "Invalid basket: need 2 or more distinct assets")]
+fn test_multi_asset_duplicate_asset_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    setup_oracle(&e, &contract_id, &admin).set_price(&100, &2, &false);
+
+    let holdings = vec![
+        &e,
+        (asset_address.clone(), 100i128),
+        (asset_address.clone(), 200i128),
+    ];
+    client.create_multi_asset_commitment(&Address::generate(&e), &holdings, &test_rules(&e));
+}
+
+#[test]
+#[should_panic(expected = "Operation not supported for multi-asset commitments")]
+fn test_multi_asset_top_up_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (client, owner, id, _asset_a, _asset_b, _oracle) = setup_basket(&e);
+
+    client.top_up(&id, &owner, &100);
+}
+
+// ============================================================================
+// Operator & Delegated Creation Tests
+// ============================================================================
+
+#[test]
+fn test_operator_creates_commitment_for_beneficiary() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let custodian = Address::generate(&e);
+    StellarAssetClient::new(&e, &asset_address).mint(&custodian, &1_000);
+
+    client.approve_operator(&owner, &custodian, &OperatorScope::Create, &1_000);
+    assert!(client.is_operator(&owner, &custodian, &OperatorScope::Create));
+    assert!(!client.is_operator(&owner, &custodian, &OperatorScope::Exit));
+
+    let id =
+        client.create_commitment_for(&custodian, &owner, &1_000, &asset_address, &test_rules(&e));
+
+    assert_eq!(client.get_commitment(&id).owner, owner);
+    assert_eq!(client.get_owner_commitments(&owner).len(), 1);
+    assert_eq!(client.get_owner_commitments(&custodian).len(), 0);
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&custodian), 0);
+    assert_eq!(token.balance(&contract_id), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_create_commitment_for_without_approval_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let payer = Address::generate(&e);
+    StellarAssetClient::new(&e, &asset_address).mint(&payer, &1_000);
+
+    client.create_commitment_for(
+        &payer,
+        &Address::generate(&e),
+        &1_000,
+        &asset_address,
+        &test_rules(&e),
+    );
+}
+
+#[test]
+fn test_operator_early_exit_pays_owner() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let custodian = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+
+    client.approve_operator(&owner, &custodian, &OperatorScope::All, &1_000);
+    client.early_exit(&id, &custodian);
+
+    let token = soroban_sdk::token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&owner), 950);
+    assert_eq!(token.balance(&custodian), 0);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_operator_outside_scope_cannot_exit() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let custodian = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+
+    client.approve_operator(&owner, &custodian, &OperatorScope::TopUp, &1_000);
+    client.early_exit(&id, &custodian);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_expired_operator_approval_is_rejected() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let custodian = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    StellarAssetClient::new(&e, &asset_address).mint(&custodian, &100);
+
+    client.approve_operator(&owner, &custodian, &OperatorScope::TopUp, &500);
+    e.ledger().with_mut(|l| l.timestamp = 500);
+    client.top_up(&id, &custodian, &100);
+}
+
+#[test]
+fn test_revoke_operator() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, _asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let custodian = Address::generate(&e);
+
+    client.approve_operator(&owner, &custodian, &OperatorScope::All, &1_000);
+    assert_eq!(
+        client.get_operator_approval(&owner, &custodian),
+        Some(OperatorApproval {
+            scope: OperatorScope::All,
+            expires_at: 1_000,
+        })
+    );
+    client.revoke_operator(&owner, &custodian);
+    assert_eq!(client.get_operator_approval(&owner, &custodian), None);
+    assert!(!client.is_operator(&owner, &custodian, &OperatorScope::Exit));
+}
+
+#[test]
+#[should_panic(expected = "Invalid expiry: must be in the future")]
+fn test_approve_operator_with_past_expiry_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, _admin, _asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    e.ledger().with_mut(|l| l.timestamp = 100);
+
+    client.approve_operator(
+        &Address::generate(&e),
+        &Address::generate(&e),
+        &OperatorScope::All,
+        &100,
+    );
+}
+
+// ============================================================================
+// Violation Sweep Tests
+// ============================================================================
+
+#[test]
+fn test_sweep_violations_marks_loss_breaches_only() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let keeper = Address::generate(&e);
+    let lossy = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    let healthy = create_funded(&e, &client, &owner, &asset_address, 1_000);
+
+    // Simulate a drawdown recorded without going through update_value
+    e.as_contract(&contract_id, || {
+        let mut commitment = read_commitment(&e, &lossy).unwrap();
+        commitment.current_value = 800;
+        set_commitment(&e, &commitment);
+    });
+
+    let report = client.sweep_violations(&keeper, &0, &10);
+    assert!(report.success);
+    assert_eq!(report.success_count, 1);
+    assert_eq!(
+        client.get_commitment(&lossy).status,
+        CommitmentStatus::Violated
+    );
+    assert_eq!(
+        client.get_commitment(&healthy).status,
+        CommitmentStatus::Active
+    );
+
+    // Matured commitments are left for keeper settlement, not marked violated
+    advance_past_expiry(&e, 30);
+    let report = client.sweep_violations(&keeper, &0, &10);
+    assert_eq!(report.success_count, 0);
+    assert_eq!(
+        client.get_commitment(&healthy).status,
+        CommitmentStatus::Active
+    );
+    client.settle(&healthy, &keeper);
+    assert_eq!(
+        client.get_commitment(&healthy).status,
+        CommitmentStatus::Settled
+    );
+}
+
+#[test]
+fn test_sweep_violations_counts_allocated_principal() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+
+    // Half the principal is out in a pool, well past the 10% loss limit of what is held
+    client.allocate(&id, &Address::generate(&e), &500);
+    assert!(!client.check_violations(&id));
+
+    let report = client.sweep_violations(&Address::generate(&e), &0, &10);
+    assert_eq!(report.success_count, 0);
+    assert_eq!(client.get_commitment(&id).status, CommitmentStatus::Active);
+}
+
+#[test]
+fn test_sweep_violations_leaves_commitments_in_grace_period() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let mut rules = test_rules(&e);
+    rules.grace_period_days = 7;
+    StellarAssetClient::new(&e, &asset_address).mint(&owner, &1_000);
+    let id = client.create_commitment(&owner, &1_000, &asset_address, &rules);
+
+    advance_past_expiry(&e, 30);
+    let report = client.sweep_violations(&Address::generate(&e), &0, &10);
+    assert_eq!(report.success_count, 0);
+    assert_eq!(client.get_commitment(&id).status, CommitmentStatus::Active);
+}
+
+#[test]
+fn test_sweep_violations_pays_reward_from_keeper_budget() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let keeper = Address::generate(&e);
+    client.set_creation_fee_bps(&admin, &100);
+    let mut ids = Vec::new(&e);
+    for _ in 0..3 {
+        ids.push_back(create_funded(&e, &client, &owner, &asset_address, 1_000));
+    }
+    assert_eq!(client.get_collected_fees(&asset_address), 30);
+
+    StellarAssetClient::new(&e, &asset_address).mint(&admin, &20);
+    client.fund_keeper_budget(&admin, &asset_address, &20);
+    assert_eq!(client.get_keeper_budget(&asset_address), 20);
+    client.set_sweep_reward(&admin, &asset_address, &8);
+    assert_eq!(client.get_sweep_reward(), Some((asset_address.clone(), 8)));
+
+    // Simulate drawdowns recorded without going through update_value
+    e.as_contract(&contract_id, || {
+        for id in ids.iter() {
+            let mut commitment = read_commitment(&e, &id).unwrap();
+            commitment.current_value = 800;
+            set_commitment(&e, &commitment);
+        }
+    });
+
+    let report = client.sweep_violations(&keeper, &0, &10);
+    assert_eq!(report.success_count, 3);
+    // Capped by the budget; collected fees are untouched
+    assert_eq!(token::Client::new(&e, &asset_address).balance(&keeper), 20);
+    assert_eq!(client.get_keeper_budget(&asset_address), 0);
+    assert_eq!(client.get_collected_fees(&asset_address), 30);
+}
+
+// ============================================================================
+// Violation Resolution Tests
+// ============================================================================
+
+#[test]
+fn test_resolve_violation_withholds_penalty_on_loss_breach() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    client.set_violation_penalty_bps(&admin, &1_000);
+
+    client.update_value(&admin, &id, &800);
+    assert_eq!(
+        client.get_commitment(&id).status,
+        CommitmentStatus::Violated
+    );
+    assert_eq!(client.get_total_value_locked(), 800);
+
+    client.resolve_violation(&id);
+    let token = token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&owner), 720);
+    assert_eq!(client.get_collected_penalties(&asset_address), 80);
+    assert_eq!(client.get_total_value_locked(), 0);
+    assert_eq!(client.get_commitment(&id).current_value, 0);
+    assert!(client.is_violation_resolved(&id));
+}
+
+#[test]
+fn test_resolve_violation_takes_performance_fee_before_penalty() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let pool = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    client.set_performance_fee_bps(&admin, &1_000); // 10% of yield
+    client.set_violation_penalty_bps(&admin, &1_000);
+
+    client.allocate(&id, &pool, &1_000);
+    StellarAssetClient::new(&e, &asset_address).mint(&pool, &200);
+    client.record_return(&id, &pool, &200);
+    client.deallocate(&id, &pool, &1_000);
+    assert_eq!(client.get_commitment(&id).yield_earned, 200);
+
+    client.update_value(&admin, &id, &800);
+    assert_eq!(
+        client.get_commitment(&id).status,
+        CommitmentStatus::Violated
+    );
+
+    // 800 less a 20 performance fee, then a 10% penalty on the 780 left
+    client.resolve_violation(&id);
+    let token = token::Client::new(&e, &asset_address);
+    assert_eq!(client.get_collected_fees(&asset_address), 20);
+    assert_eq!(client.get_collected_penalties(&asset_address), 78);
+    assert_eq!(token.balance(&owner), 702);
+    assert_eq!(client.get_total_value_locked(), 0);
+}
+
+#[test]
+fn test_resolve_violation_returns_expired_commitment_in_full() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    client.set_violation_penalty_bps(&admin, &1_000);
+
+    // Earlier versions of sweep_violations marked commitments past their grace window
+    advance_past_expiry(&e, 30);
+    e.as_contract(&contract_id, || {
+        let mut commitment = read_commitment(&e, &id).unwrap();
+        transition_status(&e, &mut commitment, CommitmentStatus::Violated, "test");
+        set_commitment(&e, &commitment);
+    });
+    client.resolve_violation(&id);
+
+    assert_eq!(
+        token::Client::new(&e, &asset_address).balance(&owner),
+        1_000
+    );
+    assert_eq!(client.get_collected_penalties(&asset_address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Violation already resolved")]
+fn test_resolve_violation_twice_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+
+    client.update_value(&admin, &id, &500);
+    client.resolve_violation(&id);
+    client.resolve_violation(&id);
+}
+
+#[test]
+#[should_panic(expected = "Invalid commitment status for this operation")]
+fn test_resolve_violation_on_active_commitment_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+
+    client.resolve_violation(&id);
+}
+
+#[test]
+#[should_panic(expected = "Invalid fee")]
+fn test_set_violation_penalty_above_cap_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, _asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+
+    client.set_violation_penalty_bps(&admin, &(MAX_VIOLATION_PENALTY_BPS + 1));
+}
+
+// ============================================================================
+// Template Tests
+// ============================================================================
+
+fn test_template(e: &Env, allowed_assets: Vec<Address>) -> CommitmentTemplate {
+    CommitmentTemplate {
+        name: String::from_str(e, "Balanced 30-90d"),
+        commitment_type: String::from_str(e, "balanced"),
+        min_duration_days: 30,
+        max_duration_days: 90,
+        min_loss_percent: 5,
+        max_loss_percent: 20,
+        early_exit_penalty: 7,
+        min_fee_threshold: 100,
+        grace_period_days: 3,
+        transfer_policy: TransferPolicy::Free,
+        min_amount: 500,
+        allowed_assets,
+        enabled: true,
+    }
+}
+
+#[test]
+fn test_create_commitment_from_template_applies_fixed_rules() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let template_id =
+        client.add_template(&admin, &test_template(&e, vec![&e, asset_address.clone()]));
+    assert_eq!(template_id, 0);
+    assert_eq!(client.get_template_count(), 1);
+
+    StellarAssetClient::new(&e, &asset_address).mint(&owner, &1_000);
+    let id = client.create_commitment_from_template(
+        &owner,
+        &template_id,
+        &1_000,
+        &asset_address,
+        &60,
+        &10,
+    );
+
+    let rules = client.get_commitment(&id).rules;
+    assert_eq!(rules.duration_days, 60);
+    assert_eq!(rules.max_loss_percent, 10);
+    assert_eq!(rules.early_exit_penalty, 7);
+    assert_eq!(rules.grace_period_days, 3);
+    assert_eq!(rules.commitment_type, String::from_str(&e, "balanced"));
+    assert_eq!(client.get_commitment_template(&id), Some(template_id));
+}
+
+#[test]
+#[should_panic(expected = "Invalid duration")]
+fn test_create_commitment_from_template_duration_out_of_range_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let template_id = client.add_template(&admin, &test_template(&e, Vec::new(&e)));
+
+    StellarAssetClient::new(&e, &asset_address).mint(&owner, &1_000);
+    client.create_commitment_from_template(&owner, &template_id, &1_000, &asset_address, &120, &10);
+}
+
+#[test]
+#[should_panic(expected = "Asset not allowed")]
+fn test_create_commitment_from_template_rejects_unlisted_asset() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let template_id =
+        client.add_template(&admin, &test_template(&e, vec![&e, Address::generate(&e)]));
+
+    StellarAssetClient::new(&e, &asset_address).mint(&owner, &1_000);
+    client.create_commitment_from_template(&owner, &template_id, &1_000, &asset_address, &60, &10);
+}
+
+#[test]
+#[should_panic(expected = "Template is disabled")]
+fn test_create_commitment_from_disabled_template_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let template_id = client.add_template(&admin, &test_template(&e, Vec::new(&e)));
+    client.set_template_enabled(&admin, &template_id, &false);
+    assert!(!client.get_template(&template_id).enabled);
+
+    StellarAssetClient::new(&e, &asset_address).mint(&owner, &1_000);
+    client.create_commitment_from_template(&owner, &template_id, &1_000, &asset_address, &60, &10);
+}
+
+#[test]
+#[should_panic(expected = "Invalid template")]
+fn test_add_template_with_inverted_range_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (contract_id, admin, _asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let mut template = test_template(&e, Vec::new(&e));
+    template.min_loss_percent = 30;
+
+    client.add_template(&admin, &template);
+}
+
+// ============================================================================
+// Asset Registry Tests
+// ============================================================================
+
+fn asset_config(min_amount: i128, tvl_cap: i128, owner_cap: i128) -> AssetConfig {
+    AssetConfig {
+        enabled: true,
+        min_amount,
+        tvl_cap,
+        owner_cap,
+    }
+}
+
+#[test]
+#[should_panic(expected = "Asset not allowed")]
+fn test_allowlist_rejects_unregistered_asset() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    assert!(client.is_asset_allowed(&asset_address));
+
+    client.set_asset_allowlist_enforced(&admin, &true);
+    assert!(!client.is_asset_allowed(&asset_address));
+    create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+}
+
+#[test]
+fn test_registered_asset_tracks_locked_principal() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    client.set_asset_allowlist_enforced(&admin, &true);
+    client.set_asset_config(&admin, &asset_address, &asset_config(100, 0, 0));
+    assert!(client.is_asset_allowed(&asset_address));
+
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    create_funded(&e, &client, &owner, &asset_address, 500);
+    assert_eq!(client.get_asset_locked(&asset_address), 1_500);
+    assert_eq!(client.get_owner_asset_locked(&owner, &asset_address), 1_500);
+
+    client.early_exit(&id, &owner);
+    assert_eq!(client.get_asset_locked(&asset_address), 500);
+    assert_eq!(client.get_owner_asset_locked(&owner, &asset_address), 500);
+}
+
+#[test]
+#[should_panic(expected = "Invalid amount")]
+fn test_asset_min_amount_enforced() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.set_asset_config(&admin, &asset_address, &asset_config(1_000, 0, 0));
+
+    create_funded(&e, &client, &Address::generate(&e), &asset_address, 999);
+}
+
+#[test]
+#[should_panic(expected = "Asset not allowed")]
+fn test_disabled_asset_rejected() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let mut config = asset_config(0, 0, 0);
+    config.enabled = false;
+    client.set_asset_config(&admin, &asset_address, &config);
+
+    create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Asset cap exceeded")]
+fn test_asset_tvl_cap_spans_owners() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    client.set_asset_config(&admin, &asset_address, &asset_config(0, 1_500, 0));
+
+    create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+    create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Asset cap exceeded")]
+fn test_asset_owner_cap_applies_to_top_up() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    client.set_asset_config(&admin, &asset_address, &asset_config(0, 0, 1_200));
+
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    StellarAssetClient::new(&e, &asset_address).mint(&owner, &500);
+    client.top_up(&id, &owner, &500);
+}
+
+#[test]
+fn test_rollover_within_owner_cap_replaces_old_principal() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    client.set_asset_config(&admin, &asset_address, &asset_config(0, 0, 1_000));
+
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    advance_past_expiry(&e, 30);
+    client.rollover(&id, &test_rules(&e));
+    assert_eq!(client.get_owner_asset_locked(&owner, &asset_address), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Asset cap exceeded")]
+fn test_asset_owner_cap_applies_to_rollover() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    client.set_asset_config(&admin, &asset_address, &asset_config(0, 0, 1_000));
+
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    client.update_value(&admin, &id, &1_050);
+    advance_past_expiry(&e, 30);
+    client.rollover(&id, &test_rules(&e));
+}
+
+#[test]
+#[should_panic(expected = "Asset not allowed")]
+fn test_disabled_asset_blocks_rollover() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+    let mut config = asset_config(0, 0, 0);
+    config.enabled = false;
+    client.set_asset_config(&admin, &asset_address, &config);
+
+    advance_past_expiry(&e, 30);
+    client.rollover(&id, &test_rules(&e));
+}
+
+// ============================================================================
+// Protocol Metrics Tests
+// ============================================================================
+
+#[test]
+fn test_protocol_metrics_track_value_per_asset() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_a) = setup_core_with_token(&e);
+    let asset_b = e
+        .register_stellar_asset_contract_v2(Address::generate(&e))
+        .address();
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let settled_id = create_funded(&e, &client, &owner, &asset_a, 1_000);
+    create_funded(&e, &client, &owner, &asset_a, 400);
+    let exited_id = create_funded(&e, &client, &owner, &asset_b, 50);
+
+    client.early_exit(&exited_id, &owner);
+    advance_past_expiry(&e, 30);
+    client.settle(&settled_id, &owner);
+
+    assert_eq!(
+        client.get_asset_metrics(&asset_a),
+        AssetMetrics {
+            value_locked: 400,
+            settled_volume: 1_000,
+            exited_volume: 0,
+        }
+    );
+    assert_eq!(
+        client.get_asset_metrics(&asset_b),
+        AssetMetrics {
+            value_locked: 0,
+            settled_volume: 0,
+            exited_volume: 50,
+        }
+    );
+
+    let metrics = client.get_protocol_metrics();
+    assert_eq!(metrics.total_commitments, 3);
+    assert_eq!(metrics.active_count, 1);
+    assert_eq!(metrics.settled_count, 1);
+    assert_eq!(metrics.early_exit_count, 1);
+    assert_eq!(metrics.violated_count, 0);
+    assert_eq!(metrics.assets.len(), 2);
+    assert_eq!(metrics.assets.get(0).unwrap().0, asset_a);
+    assert_eq!(metrics.assets.get(1).unwrap().1.exited_volume, 50);
+}
+
+#[test]
+fn test_asset_metrics_follow_valuation_and_partial_exit() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+
+    client.update_value(&admin, &id, &1_100);
+    client.partial_exit(&id, &owner, &100);
+
+    let metrics = client.get_asset_metrics(&asset_address);
+    assert_eq!(metrics.value_locked, 1_000);
+    assert_eq!(metrics.exited_volume, 100);
+    assert_eq!(client.get_total_value_locked(), 1_000);
+}
+
+// ============================================================================
+// NFT Ownership Tests
+// ============================================================================
+
+#[test]
+fn test_on_nft_transfer_moves_commitment_to_new_holder() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let seller = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let id = create_funded(&e, &client, &seller, &asset_address, 1_000);
+    let token_id = client.get_commitment(&id).nft_token_id;
+
+    client.on_nft_transfer(&id, &token_id, &seller, &buyer);
+
+    assert_eq!(client.get_commitment(&id).owner, buyer);
+    assert_eq!(client.get_owner_commitments(&seller).len(), 0);
+    assert_eq!(client.get_owner_commitments(&buyer), vec![&e, id.clone()]);
+    assert_eq!(client.get_owner_asset_locked(&seller, &asset_address), 0);
+    assert_eq!(client.get_owner_asset_locked(&buyer, &asset_address), 1_000);
+
+    // Exit proceeds now go to the buyer
+    client.early_exit(&id, &buyer);
+    let token = token::Client::new(&e, &asset_address);
+    assert_eq!(token.balance(&buyer), 950);
+    assert_eq!(token.balance(&seller), 0);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_on_nft_transfer_from_non_owner_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+    let token_id = client.get_commitment(&id).nft_token_id;
+
+    client.on_nft_transfer(
+        &id,
+        &token_id,
+        &Address::generate(&e),
+        &Address::generate(&e),
+    );
+}
+
+#[test]
+fn test_on_nft_transfer_ignores_tokens_core_did_not_mint() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let id = create_funded(&e, &client, &owner, &asset_address, 1_000);
+    let token_id = client.get_commitment(&id).nft_token_id;
+
+    // Unknown commitment IDs, and another token reusing a core commitment ID
+    client.on_nft_transfer(&String::from_str(&e, "external"), &token_id, &owner, &buyer);
+    client.on_nft_transfer(&id, &(token_id + 1), &owner, &buyer);
+
+    assert_eq!(client.get_commitment(&id).owner, owner);
+    assert_eq!(client.get_owner_commitments(&buyer).len(), 0);
+}
+
+#[test]
+fn test_resync_owner_follows_nft_holder() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let seller = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let id = create_funded(&e, &client, &seller, &asset_address, 1_000);
+
+    // The NFT moved before core tracked transfers
+    let nft = MockNftContractClient::new(&e, &client.get_nft_contract());
+    nft.set_owner(&buyer);
+    client.resync_owner(&admin, &id);
+
+    assert_eq!(client.get_commitment(&id).owner, buyer);
+    assert_eq!(client.get_owner_commitments(&seller).len(), 0);
+    assert_eq!(client.get_owner_commitments(&buyer), vec![&e, id.clone()]);
+    assert_eq!(client.get_owner_asset_locked(&seller, &asset_address), 0);
+    assert_eq!(client.get_owner_asset_locked(&buyer, &asset_address), 1_000);
+
+    // Later transfers from the holder go through again
+    let next = Address::generate(&e);
+    client.on_nft_transfer(&id, &client.get_commitment(&id).nft_token_id, &buyer, &next);
+    assert_eq!(client.get_commitment(&id).owner, next);
+
+    // Already in sync: nothing changes
+    nft.set_owner(&next);
+    client.resync_owner(&admin, &id);
+    assert_eq!(client.get_owner_commitments(&next), vec![&e, id]);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: caller not allowed")]
+fn test_resync_owner_by_non_admin_fails() {
+    let e = Env::default();
+    e.mock_all_auths_allowing_non_root_auth();
+    let (contract_id, _admin, asset_address) = setup_core_with_token(&e);
+    let client = CommitmentCoreContractClient::new(&e, &contract_id);
+    let id = create_funded(&e, &client, &Address::generate(&e), &asset_address, 1_000);
+
+    client.resync_owner(&Address::generate(&e), &id);
+}
@@ -8,22 +8,22 @@ use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Symbol};
 use crate::error::Error;
 use crate::types::CommitmentSpec;
 
-/// =======================
-/// Interface Metadata
-/// =======================
+// =======================
+// Interface Metadata
+// =======================
 
 pub const INTERFACE_VERSION: u32 = 1;
 
-/// =======================
-/// Events
-/// =======================
+// =======================
+// Events
+// =======================
 
 pub const COMMITMENT_CREATED: Symbol = symbol_short!("created");
 pub const COMMITMENT_REVOKED: Symbol = symbol_short!("revoked");
 
-/// =======================
-/// Interface Contract
-/// =======================
+// =======================
+// Interface Contract
+// =======================
 
 #[contract]
 pub struct CommitmentInterface;
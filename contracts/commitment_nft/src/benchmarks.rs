@@ -44,6 +44,7 @@ fn setup_test_env(e: &Env) -> Address {
 
     e.as_contract(&contract_id, || {
         CommitmentNFTContract::initialize(e.clone(), admin.clone()).unwrap();
+        CommitmentNFTContract::add_minter(e.clone(), contract_id.clone()).unwrap();
     });
 
     contract_id
@@ -80,6 +81,7 @@ fn benchmark_mint() {
         let start = e.ledger().sequence();
         let _ = CommitmentNFTContract::mint(
             e.clone(),
+            e.current_contract_address(),
             owner.clone(),
             String::from_str(&e, "commitment_1"),
             30,
@@ -88,6 +90,7 @@ fn benchmark_mint() {
             1000_0000000,
             Address::generate(&e),
             10,
+            TransferPolicy::Free,
         )
         .unwrap();
         let end = e.ledger().sequence();
@@ -106,6 +109,7 @@ fn benchmark_get_metadata() {
     let token_id = e.as_contract(&contract_id, || {
         CommitmentNFTContract::mint(
             e.clone(),
+            e.current_contract_address(),
             owner.clone(),
             String::from_str(&e, "commitment_1"),
             30,
@@ -114,6 +118,7 @@ fn benchmark_get_metadata() {
             1000_0000000,
             Address::generate(&e),
             10,
+            TransferPolicy::Free,
         )
         .unwrap()
     });
@@ -139,6 +144,7 @@ fn benchmark_owner_of() {
     let token_id = e.as_contract(&contract_id, || {
         CommitmentNFTContract::mint(
             e.clone(),
+            e.current_contract_address(),
             owner.clone(),
             String::from_str(&e, "commitment_1"),
             30,
@@ -147,6 +153,7 @@ fn benchmark_owner_of() {
             1000_0000000,
             Address::generate(&e),
             10,
+            TransferPolicy::Free,
         )
         .unwrap()
     });
@@ -172,6 +179,7 @@ fn benchmark_balance_of() {
     e.as_contract(&contract_id, || {
         CommitmentNFTContract::mint(
             e.clone(),
+            e.current_contract_address(),
             owner.clone(),
             String::from_str(&e, "commitment_1"),
             30,
@@ -180,6 +188,7 @@ fn benchmark_balance_of() {
             1000_0000000,
             Address::generate(&e),
             10,
+            TransferPolicy::Free,
         )
         .unwrap();
     });
@@ -221,6 +230,7 @@ fn benchmark_batch_mint() {
         e.as_contract(&contract_id, || {
             let _ = CommitmentNFTContract::mint(
                 e.clone(),
+                e.current_contract_address(),
                 owner.clone(),
                 String::from_str(&e, commitment_id),
                 30,
@@ -229,6 +239,7 @@ fn benchmark_batch_mint() {
                 1000_0000000,
                 Address::generate(&e),
                 10,
+                TransferPolicy::Free,
             )
             .unwrap();
         });
@@ -1,12 +1,28 @@
 #![no_std]
-use shared_utils::{EmergencyControl, Pausable};
+use shared_utils::{AccessControl, EmergencyControl, Pausable};
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
-    String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env,
+    IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
 // Current storage version for migration checks.
-const CURRENT_VERSION: u32 = 1;
+// v2: `CommitmentNFT::transfer_policy`; v3: `CommitmentMetadata::is_basket`;
+// v4: `CommitmentMetadata::current_value` on tokens minted before it was tracked;
+// v5: `CommitmentMetadata::asset_address` unset on basket tokens.
+const CURRENT_VERSION: u32 = 5;
+
+// Collection metadata reported to wallets and indexers.
+const COLLECTION_NAME: &str = "Commitment NFT";
+const COLLECTION_SYMBOL: &str = "COMMIT";
+
+// Longest base URI accepted by set_base_uri, leaving room for the token id.
+const MAX_BASE_URI_LEN: u32 = 200;
+// Capacity of the buffer token JSON is rendered into: the longest name, commitment
+// type, expiry, amount and strkey asset fit with room to spare.
+const TOKEN_JSON_CAPACITY: usize = 512;
+const TOKEN_URI_PREFIX: &[u8] = b"data:application/json;base64,";
+// Capacity of the buffer token URIs are rendered into: the prefix plus base64 JSON.
+const TOKEN_URI_CAPACITY: usize = TOKEN_URI_PREFIX.len() + TOKEN_JSON_CAPACITY.div_ceil(3) * 4;
 
 // ============================================================================
 // Error Types
@@ -57,6 +73,18 @@ pub enum ContractError {
     NFTLocked = 19,
     /// Duration would cause expires_at to overflow u64
     ExpirationOverflow = 20,
+    /// Approval `live_until_ledger` is already in the past
+    InvalidExpiration = 21,
+    /// Caller is not an authorized minter
+    NotAuthorizedMinter = 22,
+    /// No commitment_core contract has been configured
+    CoreContractNotSet = 23,
+    /// Base URI is longer than MAX_BASE_URI_LEN
+    InvalidBaseUri = 24,
+    /// NFT is soulbound and can never be transferred
+    TokenSoulbound = 25,
+    /// Rendered token URI does not fit its buffer
+    TokenUriTooLong = 26,
 }
 
 // ============================================================================
@@ -74,7 +102,13 @@ pub struct CommitmentMetadata {
     pub created_at: u64,
     pub expires_at: u64,
     pub initial_amount: i128,
-    pub asset_address: Address,
+    /// Value currently locked in the commitment; tracks top-ups and partial exits
+    pub current_value: i128,
+    /// Asset locked by the commitment; None for baskets
+    pub asset_address: Option<Address>,
+    /// Multi-asset commitment: `initial_amount` and `current_value` are 0 and
+    /// `asset_address` is None; the minting contract holds the basket
+    pub is_basket: bool,
 }
 
 /// The Commitment NFT structure
@@ -86,6 +120,27 @@ pub struct CommitmentNFT {
     pub metadata: CommitmentMetadata,
     pub is_active: bool,
     pub early_exit_penalty: u32,
+    pub transfer_policy: TransferPolicy,
+}
+
+/// Transferability of an NFT, fixed at mint from the commitment rules.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransferPolicy {
+    /// Transferable at any time
+    Free,
+    /// Not transferable while active and before `expires_at`
+    LockedUntilExpiry,
+    /// Never transferable
+    Soulbound,
+}
+
+/// A single-token transfer approval, live through `live_until_ledger`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenApproval {
+    pub approved: Address,
+    pub live_until_ledger: u32,
 }
 
 /// Parameters for batch NFT transfer operations
@@ -114,7 +169,7 @@ pub enum DataKey {
     TokenIds,
     /// Authorized commitment_core contract address (for settlement)
     CoreContract,
-    /// Authorized minter addresses (from upstream)
+    /// Authorized minter addresses (Address -> bool)
     AuthorizedMinter(Address),
     /// Active status (token_id -> bool)
     ActiveStatus(u32),
@@ -122,6 +177,12 @@ pub enum DataKey {
     ReentrancyGuard,
     /// Contract version
     Version,
+    /// Single-token approval (token_id -> TokenApproval)
+    Approval(u32),
+    /// Approval for all of an owner's tokens ((owner, operator) -> live_until_ledger)
+    OperatorApproval(Address, Address),
+    /// Base URI for token_uri; unset renders on-chain JSON (String)
+    BaseUri,
 }
 
 // Events
@@ -208,9 +269,22 @@ impl CommitmentNFTContract {
             .ok_or(ContractError::NotInitialized)?;
         admin.require_auth();
 
+        // The core contract mints on commitment creation; a replaced core loses that right
+        if let Some(previous) = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::CoreContract)
+        {
+            e.storage()
+                .instance()
+                .remove(&DataKey::AuthorizedMinter(previous));
+        }
         e.storage()
             .instance()
             .set(&DataKey::CoreContract, &core_contract);
+        e.storage()
+            .instance()
+            .set(&DataKey::AuthorizedMinter(core_contract.clone()), &true);
 
         // Emit event for access control change
         e.events()
@@ -219,6 +293,47 @@ impl CommitmentNFTContract {
         Ok(())
     }
 
+    /// Authorize `minter` to mint NFTs (admin-only).
+    pub fn add_minter(e: Env, minter: Address) -> Result<(), ContractError> {
+        let admin: Address = e
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::NotInitialized)?;
+        admin.require_auth();
+
+        e.storage()
+            .instance()
+            .set(&DataKey::AuthorizedMinter(minter.clone()), &true);
+        e.events()
+            .publish((Symbol::new(&e, "MinterAdded"),), (minter,));
+        Ok(())
+    }
+
+    /// Revoke `minter`'s right to mint NFTs (admin-only).
+    pub fn remove_minter(e: Env, minter: Address) -> Result<(), ContractError> {
+        let admin: Address = e
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::NotInitialized)?;
+        admin.require_auth();
+
+        e.storage()
+            .instance()
+            .remove(&DataKey::AuthorizedMinter(minter.clone()));
+        e.events()
+            .publish((Symbol::new(&e, "MinterRemoved"),), (minter,));
+        Ok(())
+    }
+
+    /// Check whether `minter` may mint NFTs.
+    pub fn is_minter(e: Env, minter: Address) -> bool {
+        e.storage()
+            .instance()
+            .has(&DataKey::AuthorizedMinter(minter))
+    }
+
     /// Get the authorized commitment_core contract address
     pub fn get_core_contract(e: Env) -> Result<Address, ContractError> {
         e.storage()
@@ -240,13 +355,41 @@ impl CommitmentNFTContract {
         read_version(&e)
     }
 
-    /// Update admin (admin-only).
+    /// Update admin (admin-only). Deprecated: routed through `propose_admin`, so the change
+    /// only takes effect once `new_admin` calls `accept_admin`.
     pub fn set_admin(e: Env, caller: Address, new_admin: Address) -> Result<(), ContractError> {
+        Self::propose_admin(e, caller, new_admin)
+    }
+
+    /// Nominate a new admin (admin-only). Takes effect once the nominee calls `accept_admin`.
+    pub fn propose_admin(e: Env, caller: Address, new_admin: Address) -> Result<(), ContractError> {
         require_admin(&e, &caller)?;
-        e.storage().instance().set(&DataKey::Admin, &new_admin);
+        AccessControl::propose_admin(&e, &caller, &new_admin);
         Ok(())
     }
 
+    /// Accept a pending admin nomination. Caller must be the nominee.
+    pub fn accept_admin(e: Env, caller: Address) -> Result<(), ContractError> {
+        let admin = Self::get_admin(e.clone())?;
+        if !AccessControl::accept_admin(&e, &caller, &admin) {
+            return Err(ContractError::NotAuthorized);
+        }
+        e.storage().instance().set(&DataKey::Admin, &caller);
+        Ok(())
+    }
+
+    /// Withdraw a pending admin nomination (admin-only).
+    pub fn cancel_admin_proposal(e: Env, caller: Address) -> Result<(), ContractError> {
+        require_admin(&e, &caller)?;
+        AccessControl::cancel_admin_proposal(&e, &caller);
+        Ok(())
+    }
+
+    /// Get the pending admin nominee, if any.
+    pub fn get_pending_admin(e: Env) -> Option<Address> {
+        AccessControl::get_pending_admin(&e)
+    }
+
     /// Upgrade contract WASM (admin-only).
     pub fn upgrade(
         e: Env,
@@ -284,6 +427,12 @@ impl CommitmentNFTContract {
                 .instance()
                 .set(&DataKey::ReentrancyGuard, &false);
         }
+        if from_version < 2 {
+            migrate_transfer_policies(&e);
+        }
+        if from_version < 5 {
+            migrate_metadata_fields(&e);
+        }
 
         e.storage()
             .instance()
@@ -306,6 +455,8 @@ impl CommitmentNFTContract {
     /// * `commitment_type` - Type of commitment ("safe", "balanced", "aggressive")
     /// * `initial_amount` - Initial amount committed
     /// * `asset_address` - Address of the asset contract
+    /// * `early_exit_penalty` - Early exit penalty percentage
+    /// * `transfer_policy` - Whether and when the NFT may be transferred
     ///
     /// # Returns
     /// The token_id of the newly minted NFT
@@ -313,8 +464,10 @@ impl CommitmentNFTContract {
     /// # Reentrancy Protection
     /// Uses checks-effects-interactions pattern. This function only writes to storage
     /// and doesn't make external calls, but still protected for consistency.
+    #[allow(clippy::too_many_arguments)]
     pub fn mint(
         e: Env,
+        caller: Address,
         owner: Address,
         commitment_id: String,
         duration_days: u32,
@@ -323,6 +476,71 @@ impl CommitmentNFTContract {
         initial_amount: i128,
         asset_address: Address,
         early_exit_penalty: u32,
+        transfer_policy: TransferPolicy,
+    ) -> Result<u32, ContractError> {
+        Self::mint_token(
+            e,
+            caller,
+            owner,
+            commitment_id,
+            duration_days,
+            max_loss_percent,
+            commitment_type,
+            initial_amount,
+            Some(asset_address),
+            early_exit_penalty,
+            transfer_policy,
+            false,
+        )
+    }
+
+    /// Mint the NFT for a multi-asset (basket) commitment.
+    ///
+    /// Same checks as `mint`, but the token carries no amount or asset: the basket's
+    /// holdings and valuation are read from the minting contract.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_basket(
+        e: Env,
+        caller: Address,
+        owner: Address,
+        commitment_id: String,
+        duration_days: u32,
+        max_loss_percent: u32,
+        commitment_type: String,
+        early_exit_penalty: u32,
+        transfer_policy: TransferPolicy,
+    ) -> Result<u32, ContractError> {
+        Self::mint_token(
+            e,
+            caller.clone(),
+            owner,
+            commitment_id,
+            duration_days,
+            max_loss_percent,
+            commitment_type,
+            0,
+            None,
+            early_exit_penalty,
+            transfer_policy,
+            true,
+        )
+    }
+
+    /// Shared body of `mint` and `mint_basket`.
+    #[allow(clippy::too_many_arguments)]
+    fn mint_token(
+        e: Env,
+        caller: Address,
+        owner: Address,
+        commitment_id: String,
+        duration_days: u32,
+        max_loss_percent: u32,
+        commitment_type: String,
+        initial_amount: i128,
+        asset_address: Option<Address>,
+        early_exit_penalty: u32,
+        transfer_policy: TransferPolicy,
+        is_basket: bool,
     ) -> Result<u32, ContractError> {
         // Reentrancy protection
         let guard: bool = e
@@ -348,6 +566,14 @@ impl CommitmentNFTContract {
             return Err(ContractError::NotInitialized);
         }
 
+        caller.require_auth();
+        if !Self::is_minter(e.clone(), caller) {
+            e.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(ContractError::NotAuthorizedMinter);
+        }
+
         // Validate inputs
         if duration_days == 0 {
             e.storage()
@@ -367,7 +593,7 @@ impl CommitmentNFTContract {
                 .set(&DataKey::ReentrancyGuard, &false);
             return Err(ContractError::InvalidCommitmentType);
         }
-        if initial_amount <= 0 {
+        if !is_basket && initial_amount <= 0 {
             e.storage()
                 .instance()
                 .set(&DataKey::ReentrancyGuard, &false);
@@ -417,7 +643,9 @@ impl CommitmentNFTContract {
             created_at,
             expires_at,
             initial_amount,
+            current_value: initial_amount,
             asset_address,
+            is_basket,
         };
 
         // Create CommitmentNFT
@@ -427,6 +655,7 @@ impl CommitmentNFTContract {
             metadata,
             is_active: true,
             early_exit_penalty,
+            transfer_policy,
         };
 
         // Store NFT data
@@ -477,6 +706,102 @@ impl CommitmentNFTContract {
         Ok(token_id)
     }
 
+    // ========================================================================
+    // Collection Metadata
+    // ========================================================================
+
+    /// Collection name.
+    pub fn name(e: Env) -> String {
+        String::from_str(&e, COLLECTION_NAME)
+    }
+
+    /// Collection symbol.
+    pub fn symbol(e: Env) -> String {
+        String::from_str(&e, COLLECTION_SYMBOL)
+    }
+
+    /// Set the base URI that token_uri appends token ids to (admin-only).
+    /// An empty base URI switches token_uri back to on-chain JSON.
+    pub fn set_base_uri(e: Env, base_uri: String) -> Result<(), ContractError> {
+        let admin: Address = e
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(ContractError::NotInitialized)?;
+        admin.require_auth();
+
+        if base_uri.len() > MAX_BASE_URI_LEN {
+            return Err(ContractError::InvalidBaseUri);
+        }
+        if base_uri.is_empty() {
+            e.storage().instance().remove(&DataKey::BaseUri);
+        } else {
+            e.storage().instance().set(&DataKey::BaseUri, &base_uri);
+        }
+
+        e.events()
+            .publish((Symbol::new(&e, "BaseUriSet"),), (base_uri,));
+        Ok(())
+    }
+
+    /// Get the configured base URI, if any.
+    pub fn get_base_uri(e: Env) -> Option<String> {
+        e.storage().instance().get(&DataKey::BaseUri)
+    }
+
+    /// URI describing `token_id`.
+    ///
+    /// With a base URI configured this is `<base_uri><token_id>`. Otherwise the
+    /// metadata is rendered on-chain as a base64 `data:application/json;base64,` URI
+    /// with type, status, expiry, initial amount and asset attributes (a basket flag
+    /// instead of the last two for multi-asset commitments).
+    pub fn token_uri(e: Env, token_id: u32) -> Result<String, ContractError> {
+        let nft: CommitmentNFT = e
+            .storage()
+            .persistent()
+            .get(&DataKey::NFT(token_id))
+            .ok_or(ContractError::TokenNotFound)?;
+
+        let mut uri = UriBuffer::<TOKEN_URI_CAPACITY>::new();
+        if let Some(base_uri) = Self::get_base_uri(e.clone()) {
+            uri.push_string(&base_uri)?;
+            uri.push_u64(token_id as u64)?;
+            return Ok(uri.to_string(&e));
+        }
+
+        let metadata = nft.metadata;
+        let status: &[u8] = if nft.is_active {
+            b"active"
+        } else {
+            b"inactive"
+        };
+        let mut json = UriBuffer::<TOKEN_JSON_CAPACITY>::new();
+        json.push(b"{\"name\":\"")?;
+        json.push(COLLECTION_NAME.as_bytes())?;
+        json.push(b" #")?;
+        json.push_u64(token_id as u64)?;
+        json.push(b"\",\"attributes\":[{\"trait_type\":\"type\",\"value\":\"")?;
+        json.push_string(&metadata.commitment_type)?;
+        json.push(b"\"},{\"trait_type\":\"status\",\"value\":\"")?;
+        json.push(status)?;
+        json.push(b"\"},{\"display_type\":\"date\",\"trait_type\":\"expiry\",\"value\":")?;
+        json.push_u64(metadata.expires_at)?;
+        match &metadata.asset_address {
+            None => json.push(b"},{\"trait_type\":\"basket\",\"value\":\"true\"}]}")?,
+            Some(asset_address) => {
+                json.push(b"},{\"trait_type\":\"initial_amount\",\"value\":\"")?;
+                json.push_i128(metadata.initial_amount)?;
+                json.push(b"\"},{\"trait_type\":\"asset\",\"value\":\"")?;
+                json.push_string(&asset_address.to_string())?;
+                json.push(b"\"}]}")?;
+            }
+        }
+
+        uri.push(TOKEN_URI_PREFIX)?;
+        uri.push_base64(json.as_bytes())?;
+        Ok(uri.to_string(&e))
+    }
+
     // ========================================================================
     // NFT Query Functions
     // ========================================================================
@@ -503,8 +828,8 @@ impl CommitmentNFTContract {
     /// Transfer NFT to new owner
     ///
     /// # Reentrancy Protection
-    /// Uses checks-effects-interactions pattern. This function only writes to storage
-    /// and doesn't make external calls, but still protected for consistency.
+    /// Uses checks-effects-interactions pattern; the only external call is the
+    /// ownership notification to the core contract, made after all state updates.
     pub fn transfer(
         e: Env,
         from: Address,
@@ -530,104 +855,140 @@ impl CommitmentNFTContract {
         // CHECKS: Require authorization from the sender
         from.require_auth();
 
-        // Validate 'to' address is not the same as 'from' (prevent self-transfer)
-        if to == from {
-            e.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(ContractError::TransferToZeroAddress);
-        }
+        let result = move_token(&e, &from, &to, token_id);
 
-        // Get the NFT
-        let mut nft: CommitmentNFT = e
+        // Clear reentrancy guard
+        e.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &false);
+
+        result
+    }
+
+    /// Transfer `token_id` from `from` to `to` on behalf of its owner. `spender`
+    /// must hold a live approval for the token or for all of `from`'s tokens.
+    pub fn transfer_from(
+        e: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        token_id: u32,
+    ) -> Result<(), ContractError> {
+        // Reentrancy protection
+        let guard: bool = e
             .storage()
-            .persistent()
-            .get(&DataKey::NFT(token_id))
-            .ok_or_else(|| {
-                e.storage()
-                    .instance()
-                    .set(&DataKey::ReentrancyGuard, &false);
-                ContractError::TokenNotFound
-            })?;
+            .instance()
+            .get(&DataKey::ReentrancyGuard)
+            .unwrap_or(false);
 
-        // Verify ownership
-        if nft.owner != from {
-            e.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(ContractError::NotOwner);
+        if guard {
+            return Err(ContractError::ReentrancyDetected);
         }
+        e.storage().instance().set(&DataKey::ReentrancyGuard, &true);
+        EmergencyControl::require_not_emergency(&e);
+        Pausable::require_not_paused(&e);
 
-        // Note: Active commitments CAN be transferred (secondary market)
-        // The commitment_core contract maintains the commitment state separately
+        // CHECKS: The spender signs; the owner's approval stands in for their auth
+        spender.require_auth();
+        let result = if Self::get_approved(e.clone(), token_id) == Some(spender.clone())
+            || Self::is_approved_for_all(e.clone(), from.clone(), spender.clone())
+        {
+            move_token(&e, &from, &to, token_id)
+        } else {
+            Err(ContractError::NotAuthorized)
+        };
 
-        // EFFECTS: Update state
-        // Update owner
-        nft.owner = to.clone();
-        e.storage().persistent().set(&DataKey::NFT(token_id), &nft);
+        e.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &false);
 
-        // OPTIMIZATION: Batch read balances before updating
-        let (from_balance, to_balance) = {
-            let from_bal = e
-                .storage()
-                .persistent()
-                .get(&DataKey::OwnerBalance(from.clone()))
-                .unwrap_or(0u32);
-            let to_bal = e
-                .storage()
-                .persistent()
-                .get(&DataKey::OwnerBalance(to.clone()))
-                .unwrap_or(0u32);
-            (from_bal, to_bal)
-        };
+        result
+    }
 
-        // Update balance counts
-        if from_balance > 0 {
-            e.storage()
-                .persistent()
-                .set(&DataKey::OwnerBalance(from.clone()), &(from_balance - 1));
+    /// Let `approved` transfer `token_id` until ledger `live_until_ledger`
+    /// (inclusive). `approver` must be the owner or one of the owner's operators.
+    /// A `live_until_ledger` of 0 revokes the approval. Transfers clear it.
+    /// The entry's TTL is extended to cover `live_until_ledger`, which may not be
+    /// past the network's maximum.
+    pub fn approve(
+        e: Env,
+        approver: Address,
+        approved: Address,
+        token_id: u32,
+        live_until_ledger: u32,
+    ) -> Result<(), ContractError> {
+        approver.require_auth();
+        let owner = Self::owner_of(e.clone(), token_id)?;
+        if approver != owner && !Self::is_approved_for_all(e.clone(), owner, approver.clone()) {
+            return Err(ContractError::NotAuthorized);
         }
-        e.storage()
-            .persistent()
-            .set(&DataKey::OwnerBalance(to.clone()), &(to_balance + 1));
 
-        // Update owner tokens lists
-        let mut from_tokens: Vec<u32> = e
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(from.clone()))
-            .unwrap_or(Vec::new(&e));
-        if let Some(index) = from_tokens.iter().position(|id| id == token_id) {
-            from_tokens.remove(index as u32);
+        let key = DataKey::Approval(token_id);
+        if live_until_ledger == 0 {
+            e.storage().persistent().remove(&key);
+        } else {
+            require_live_ledger(&e, live_until_ledger)?;
+            e.storage().persistent().set(
+                &key,
+                &TokenApproval {
+                    approved: approved.clone(),
+                    live_until_ledger,
+                },
+            );
+            extend_approval_ttl(&e, &key, live_until_ledger);
         }
-        e.storage()
-            .persistent()
-            .set(&DataKey::OwnerTokens(from.clone()), &from_tokens);
 
-        let mut to_tokens: Vec<u32> = e
-            .storage()
-            .persistent()
-            .get(&DataKey::OwnerTokens(to.clone()))
-            .unwrap_or(Vec::new(&e));
-        to_tokens.push_back(token_id);
+        e.events().publish(
+            (symbol_short!("Approve"), approver, token_id),
+            (approved, live_until_ledger),
+        );
+        Ok(())
+    }
+
+    /// Get the address approved for `token_id`, if its approval is still live.
+    pub fn get_approved(e: Env, token_id: u32) -> Option<Address> {
         e.storage()
             .persistent()
-            .set(&DataKey::OwnerTokens(to.clone()), &to_tokens);
+            .get::<_, TokenApproval>(&DataKey::Approval(token_id))
+            .filter(|approval| approval.live_until_ledger >= e.ledger().sequence())
+            .map(|approval| approval.approved)
+    }
 
-        // Clear reentrancy guard
-        e.storage()
-            .instance()
-            .set(&DataKey::ReentrancyGuard, &false);
+    /// Let `operator` transfer and approve any of `owner`'s tokens until ledger
+    /// `live_until_ledger` (inclusive). A `live_until_ledger` of 0 revokes.
+    /// The entry's TTL is extended to cover `live_until_ledger`.
+    pub fn set_approval_for_all(
+        e: Env,
+        owner: Address,
+        operator: Address,
+        live_until_ledger: u32,
+    ) -> Result<(), ContractError> {
+        owner.require_auth();
+
+        let key = DataKey::OperatorApproval(owner.clone(), operator.clone());
+        if live_until_ledger == 0 {
+            e.storage().persistent().remove(&key);
+        } else {
+            require_live_ledger(&e, live_until_ledger)?;
+            e.storage().persistent().set(&key, &live_until_ledger);
+            extend_approval_ttl(&e, &key, live_until_ledger);
+        }
 
-        // Emit transfer event
         e.events().publish(
-            (symbol_short!("Transfer"), from, to),
-            (token_id, e.ledger().timestamp()),
+            (symbol_short!("ApprAll"), owner),
+            (operator, live_until_ledger),
         );
-
         Ok(())
     }
 
+    /// Whether `operator` currently holds a live approval for all of `owner`'s tokens.
+    pub fn is_approved_for_all(e: Env, owner: Address, operator: Address) -> bool {
+        e.storage()
+            .persistent()
+            .get::<_, u32>(&DataKey::OperatorApproval(owner, operator))
+            .is_some_and(|live_until_ledger| live_until_ledger >= e.ledger().sequence())
+    }
+
     /// Check if NFT is active
     pub fn is_active(e: Env, token_id: u32) -> Result<bool, ContractError> {
         let nft: CommitmentNFT = e
@@ -705,7 +1066,8 @@ impl CommitmentNFTContract {
     // Settlement (Issue #5 - Main Implementation)
     // ========================================================================
 
-    /// Mark NFT as inactive (for early exit or other non-expiry scenarios)
+    /// Mark NFT as inactive (for early exit or other non-expiry scenarios).
+    /// Only the configured core contract may call this.
     ///
     /// # Reentrancy Protection
     /// Uses checks-effects-interactions pattern.
@@ -726,6 +1088,14 @@ impl CommitmentNFTContract {
         // Check if contract is paused
         Pausable::require_not_paused(&e);
 
+        // CHECKS: Only the core contract drives the commitment lifecycle
+        if let Err(err) = require_core_contract(&e) {
+            e.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(err);
+        }
+
         // CHECKS: Get the NFT
         let mut nft: CommitmentNFT = e
             .storage()
@@ -757,13 +1127,16 @@ impl CommitmentNFTContract {
             .set(&DataKey::ReentrancyGuard, &false);
 
         // Emit event
-        e.events()
-            .publish((symbol_short!("Inactive"), token_id), e.ledger().timestamp());
+        e.events().publish(
+            (symbol_short!("Inactive"), token_id),
+            e.ledger().timestamp(),
+        );
 
         Ok(())
     }
 
-    /// Mark NFT as settled (after maturity)
+    /// Mark NFT as settled (after maturity). Only the configured core contract
+    /// may call this.
     ///
     /// # Reentrancy Protection
     /// Uses checks-effects-interactions pattern. This function only writes to storage
@@ -785,6 +1158,14 @@ impl CommitmentNFTContract {
         // Check if contract is paused
         Pausable::require_not_paused(&e);
 
+        // CHECKS: Only the core contract drives the commitment lifecycle
+        if let Err(err) = require_core_contract(&e) {
+            e.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(err);
+        }
+
         // CHECKS: Get the NFT
         let mut nft: CommitmentNFT = e
             .storage()
@@ -831,6 +1212,35 @@ impl CommitmentNFTContract {
         Ok(())
     }
 
+    /// Update the value recorded on an NFT after its commitment is topped up or
+    /// partially exited. Only the configured core contract may call this.
+    pub fn update_value(e: Env, token_id: u32, new_value: i128) -> Result<(), ContractError> {
+        require_core_contract(&e)?;
+
+        if new_value < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut nft: CommitmentNFT = e
+            .storage()
+            .persistent()
+            .get(&DataKey::NFT(token_id))
+            .ok_or(ContractError::TokenNotFound)?;
+        if !nft.is_active {
+            return Err(ContractError::AlreadySettled);
+        }
+
+        nft.metadata.current_value = new_value;
+        e.storage().persistent().set(&DataKey::NFT(token_id), &nft);
+
+        e.events().publish(
+            (symbol_short!("ValUpd"), token_id),
+            (new_value, e.ledger().timestamp()),
+        );
+
+        Ok(())
+    }
+
     /// Check if an NFT has expired (based on time)
     pub fn is_expired(e: Env, token_id: u32) -> Result<bool, ContractError> {
         let nft: CommitmentNFT = e
@@ -866,6 +1276,326 @@ impl CommitmentNFTContract {
     }
 }
 
+/// Move `token_id` from `from` to `to` once the caller has been authorized:
+/// updates ownership indexes, clears the token's approval and notifies core.
+fn move_token(e: &Env, from: &Address, to: &Address, token_id: u32) -> Result<(), ContractError> {
+    // Validate 'to' address is not the same as 'from' (prevent self-transfer)
+    if to == from {
+        return Err(ContractError::TransferToZeroAddress);
+    }
+
+    // Get the NFT
+    let mut nft: CommitmentNFT = e
+        .storage()
+        .persistent()
+        .get(&DataKey::NFT(token_id))
+        .ok_or(ContractError::TokenNotFound)?;
+
+    // Verify ownership
+    if nft.owner != *from {
+        return Err(ContractError::NotOwner);
+    }
+
+    // Active commitments are transferable (secondary market) unless their policy
+    // forbids it; commitment_core is notified below so the new holder owns the commitment
+    match nft.transfer_policy {
+        TransferPolicy::Free => {}
+        TransferPolicy::LockedUntilExpiry => {
+            if nft.is_active && e.ledger().timestamp() < nft.metadata.expires_at {
+                return Err(ContractError::NFTLocked);
+            }
+        }
+        TransferPolicy::Soulbound => return Err(ContractError::TokenSoulbound),
+    }
+
+    // EFFECTS: Update state
+    // Update owner; a single-token approval does not survive a transfer
+    nft.owner = to.clone();
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Approval(token_id));
+    e.storage().persistent().set(&DataKey::NFT(token_id), &nft);
+
+    // OPTIMIZATION: Batch read balances before updating
+    let (from_balance, to_balance) = {
+        let from_bal = e
+            .storage()
+            .persistent()
+            .get(&DataKey::OwnerBalance(from.clone()))
+            .unwrap_or(0u32);
+        let to_bal = e
+            .storage()
+            .persistent()
+            .get(&DataKey::OwnerBalance(to.clone()))
+            .unwrap_or(0u32);
+        (from_bal, to_bal)
+    };
+
+    // Update balance counts
+    if from_balance > 0 {
+        e.storage()
+            .persistent()
+            .set(&DataKey::OwnerBalance(from.clone()), &(from_balance - 1));
+    }
+    e.storage()
+        .persistent()
+        .set(&DataKey::OwnerBalance(to.clone()), &(to_balance + 1));
+
+    // Update owner tokens lists
+    let mut from_tokens: Vec<u32> = e
+        .storage()
+        .persistent()
+        .get(&DataKey::OwnerTokens(from.clone()))
+        .unwrap_or(Vec::new(e));
+    if let Some(index) = from_tokens.iter().position(|id| id == token_id) {
+        from_tokens.remove(index as u32);
+    }
+    e.storage()
+        .persistent()
+        .set(&DataKey::OwnerTokens(from.clone()), &from_tokens);
+
+    let mut to_tokens: Vec<u32> = e
+        .storage()
+        .persistent()
+        .get(&DataKey::OwnerTokens(to.clone()))
+        .unwrap_or(Vec::new(e));
+    to_tokens.push_back(token_id);
+    e.storage()
+        .persistent()
+        .set(&DataKey::OwnerTokens(to.clone()), &to_tokens);
+
+    // INTERACTIONS: Move beneficial ownership of the commitment in core
+    if let Some(core_contract) = e
+        .storage()
+        .instance()
+        .get::<_, Address>(&DataKey::CoreContract)
+    {
+        e.invoke_contract::<()>(
+            &core_contract,
+            &Symbol::new(e, "on_nft_transfer"),
+            vec![
+                e,
+                nft.metadata.commitment_id.into_val(e),
+                token_id.into_val(e),
+                from.into_val(e),
+                to.into_val(e),
+            ],
+        );
+    }
+
+    // Emit transfer event
+    e.events().publish(
+        (symbol_short!("Transfer"), from, to),
+        (token_id, e.ledger().timestamp()),
+    );
+
+    Ok(())
+}
+
+/// Fixed-capacity byte buffer token URIs are rendered into. Capacities are sized
+/// for the bounded inputs (validated commitment types, strkey addresses,
+/// MAX_BASE_URI_LEN); a push that would overflow fails with `TokenUriTooLong`
+/// rather than truncating.
+struct UriBuffer<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> UriBuffer<N> {
+    fn new() -> Self {
+        UriBuffer {
+            buf: [0u8; N],
+            len: 0,
+        }
+    }
+
+    fn reserve(&mut self, n: usize) -> Result<&mut [u8], ContractError> {
+        if n > N - self.len {
+            return Err(ContractError::TokenUriTooLong);
+        }
+        let start = self.len;
+        self.len += n;
+        Ok(&mut self.buf[start..self.len])
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> Result<(), ContractError> {
+        self.reserve(bytes.len())?.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    fn push_string(&mut self, s: &String) -> Result<(), ContractError> {
+        s.copy_into_slice(self.reserve(s.len() as usize)?);
+        Ok(())
+    }
+
+    fn push_u64(&mut self, value: u64) -> Result<(), ContractError> {
+        self.push_u128(value as u128)
+    }
+
+    fn push_i128(&mut self, value: i128) -> Result<(), ContractError> {
+        if value < 0 {
+            self.push(b"-")?;
+        }
+        self.push_u128(value.unsigned_abs())
+    }
+
+    fn push_u128(&mut self, mut value: u128) -> Result<(), ContractError> {
+        let mut digits = [0u8; 39];
+        let mut i = digits.len();
+        loop {
+            i -= 1;
+            digits[i] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        self.push(&digits[i..])
+    }
+
+    /// Append `bytes` as padded standard base64 (RFC 4648).
+    fn push_base64(&mut self, bytes: &[u8]) -> Result<(), ContractError> {
+        const ALPHABET: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        for chunk in bytes.chunks(3) {
+            let b = [
+                chunk[0],
+                chunk.get(1).copied().unwrap_or(0),
+                chunk.get(2).copied().unwrap_or(0),
+            ];
+            let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+            let out = self.reserve(4)?;
+            for (i, slot) in out.iter_mut().enumerate() {
+                *slot = if i <= chunk.len() {
+                    ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize]
+                } else {
+                    b'='
+                };
+            }
+        }
+        Ok(())
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    fn to_string(&self, e: &Env) -> String {
+        String::from_bytes(e, self.as_bytes())
+    }
+}
+
+fn require_core_contract(e: &Env) -> Result<(), ContractError> {
+    let core_contract: Address = e
+        .storage()
+        .instance()
+        .get(&DataKey::CoreContract)
+        .ok_or(ContractError::CoreContractNotSet)?;
+    core_contract.require_auth();
+    Ok(())
+}
+
+fn require_live_ledger(e: &Env, live_until_ledger: u32) -> Result<(), ContractError> {
+    if live_until_ledger < e.ledger().sequence()
+        || live_until_ledger > e.ledger().max_live_until_ledger()
+    {
+        return Err(ContractError::InvalidExpiration);
+    }
+    Ok(())
+}
+
+/// Keep an approval entry alive at least until `live_until_ledger`, so it cannot be
+/// archived while the approval is still meant to be usable.
+fn extend_approval_ttl(e: &Env, key: &DataKey, live_until_ledger: u32) {
+    let extend_to = live_until_ledger - e.ledger().sequence();
+    e.storage()
+        .persistent()
+        .extend_ttl(key, extend_to, extend_to);
+}
+
+/// Default `transfer_policy` (v2) to `Free`, the only behaviour earlier tokens had.
+fn migrate_transfer_policies(e: &Env) {
+    let token_ids: Vec<u32> = e
+        .storage()
+        .instance()
+        .get(&DataKey::TokenIds)
+        .unwrap_or(Vec::new(e));
+    let policy_key = Symbol::new(e, "transfer_policy");
+    for token_id in token_ids.iter() {
+        let key = DataKey::NFT(token_id);
+        let Some(raw) = e.storage().persistent().get::<_, Val>(&key) else {
+            continue;
+        };
+        let Ok(mut fields) = Map::<Symbol, Val>::try_from_val(e, &raw) else {
+            continue;
+        };
+        if !fields.contains_key(policy_key.clone()) {
+            fields.set(policy_key.clone(), TransferPolicy::Free.into_val(e));
+            e.storage().persistent().set(&key, &fields);
+        }
+    }
+}
+
+/// Fill in `CommitmentMetadata` fields added after v1 on tokens written by older
+/// versions: `current_value` (v4) starts at `initial_amount`, since top-ups and
+/// partial exits were not synced before; `is_basket` (v3) is false, as baskets were
+/// not minted before it. Basket tokens minted before v5 stored the minting contract
+/// as `asset_address`; it is cleared.
+fn migrate_metadata_fields(e: &Env) {
+    let token_ids: Vec<u32> = e
+        .storage()
+        .instance()
+        .get(&DataKey::TokenIds)
+        .unwrap_or(Vec::new(e));
+    let metadata_key = Symbol::new(e, "metadata");
+    let value_key = Symbol::new(e, "current_value");
+    let basket_key = Symbol::new(e, "is_basket");
+    let asset_key = Symbol::new(e, "asset_address");
+    for token_id in token_ids.iter() {
+        let key = DataKey::NFT(token_id);
+        let Some(raw) = e.storage().persistent().get::<_, Val>(&key) else {
+            continue;
+        };
+        let Ok(mut fields) = Map::<Symbol, Val>::try_from_val(e, &raw) else {
+            continue;
+        };
+        let Some(Ok(mut metadata)) = fields
+            .get(metadata_key.clone())
+            .map(|raw| Map::<Symbol, Val>::try_from_val(e, &raw))
+        else {
+            continue;
+        };
+        let mut changed = false;
+        if !metadata.contains_key(value_key.clone()) {
+            let initial_amount = metadata
+                .get(Symbol::new(e, "initial_amount"))
+                .unwrap_or(0i128.into_val(e));
+            metadata.set(value_key.clone(), initial_amount);
+            changed = true;
+        }
+        match metadata.get(basket_key.clone()) {
+            None => {
+                metadata.set(basket_key.clone(), false.into_val(e));
+                changed = true;
+            }
+            Some(is_basket) if bool::try_from_val(e, &is_basket) == Ok(true) => {
+                if metadata
+                    .get(asset_key.clone())
+                    .is_some_and(|raw| !raw.is_void())
+                {
+                    metadata.set(asset_key.clone(), ().into_val(e));
+                    changed = true;
+                }
+            }
+            Some(_) => {}
+        }
+        if changed {
+            fields.set(metadata_key.clone(), metadata.to_val());
+            e.storage().persistent().set(&key, &fields);
+        }
+    }
+}
+
 fn read_version(e: &Env) -> u32 {
     e.storage()
         .instance()
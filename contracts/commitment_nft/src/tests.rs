@@ -4,10 +4,27 @@ extern crate std;
 
 use crate::*;
 use soroban_sdk::{
-    symbol_short,
-    testutils::{Address as _, Events, Ledger},
+    contract, contractimpl, symbol_short,
+    testutils::{storage::Persistent as _, Address as _, Events, Ledger},
     vec, Address, Env, IntoVal, String,
 };
+use std::string::ToString;
+
+/// Stand-in for commitment_core that accepts transfer notifications.
+#[contract]
+struct MockCoreContract;
+
+#[contractimpl]
+impl MockCoreContract {
+    pub fn on_nft_transfer(
+        _e: Env,
+        _commitment_id: String,
+        _token_id: u32,
+        _from: Address,
+        _to: Address,
+    ) {
+    }
+}
 
 fn setup_contract(e: &Env) -> (Address, CommitmentNFTContractClient<'_>) {
     let contract_id = e.register_contract(None, CommitmentNFTContract);
@@ -22,8 +39,9 @@ fn setup_contract_with_core(e: &Env) -> (Address, CommitmentNFTContractClient<'_
     e.mock_all_auths();
     let (admin, client) = setup_contract(e);
     client.initialize(&admin);
-    let core_id = e.register_contract(None, CommitmentNFTContract);
-    let _ = client.set_core_contract(&core_id);
+    let core_id = e.register_contract(None, MockCoreContract);
+    client.set_core_contract(&core_id);
+    client.add_minter(&admin);
     (admin, client, core_id)
 }
 
@@ -93,6 +111,7 @@ fn mint_to_owner(
     label: &str,
 ) -> u32 {
     client.mint(
+        &client.get_admin(),
         owner,
         &String::from_str(e, label),
         &1, // 1 day duration — easy to settle
@@ -101,6 +120,7 @@ fn mint_to_owner(
         &1000,
         asset_address,
         &5,
+        &TransferPolicy::Free,
     )
 }
 
@@ -125,16 +145,19 @@ fn test_initialize_twice_fails() {
 #[test]
 fn test_mint() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
         create_test_metadata(&e, &asset_address);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -143,6 +166,7 @@ fn test_mint() {
         &amount,
         &asset,
         &penalty,
+        &TransferPolicy::Free,
     );
 
     assert_eq!(token_id, 0);
@@ -170,14 +194,17 @@ fn test_mint() {
 #[test]
 fn test_mint_multiple() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     // Mint 3 NFTs
     let token_id_0 = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "commitment_0"),
         &30,
@@ -186,10 +213,12 @@ fn test_mint_multiple() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
     assert_eq!(token_id_0, 0);
 
     let token_id_1 = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "commitment_1"),
         &30,
@@ -198,10 +227,12 @@ fn test_mint_multiple() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
     assert_eq!(token_id_1, 1);
 
     let token_id_2 = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "commitment_2"),
         &30,
@@ -210,6 +241,7 @@ fn test_mint_multiple() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
     assert_eq!(token_id_2, 2);
 
@@ -221,7 +253,8 @@ fn test_mint_multiple() {
 #[should_panic(expected = "Error(Contract, #1)")] // NotInitialized
 fn test_mint_without_initialize_fails() {
     let e = Env::default();
-    let (_admin, client) = setup_contract(&e);
+    e.mock_all_auths();
+    let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
@@ -229,6 +262,7 @@ fn test_mint_without_initialize_fails() {
         create_test_metadata(&e, &asset_address);
 
     client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -237,6 +271,7 @@ fn test_mint_without_initialize_fails() {
         &amount,
         &asset,
         &penalty,
+        &TransferPolicy::Free,
     );
 }
 
@@ -248,13 +283,16 @@ fn test_mint_without_initialize_fails() {
 #[should_panic(expected = "Error(Contract, #12)")] // InvalidCommitmentType
 fn test_mint_empty_commitment_type() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "commitment_empty"),
         &30,
@@ -263,6 +301,7 @@ fn test_mint_empty_commitment_type() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 }
 
@@ -270,13 +309,16 @@ fn test_mint_empty_commitment_type() {
 #[should_panic(expected = "Error(Contract, #12)")] // InvalidCommitmentType
 fn test_mint_invalid_commitment_type() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "commitment_invalid"),
         &30,
@@ -285,6 +327,7 @@ fn test_mint_invalid_commitment_type() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 }
 
@@ -292,13 +335,16 @@ fn test_mint_invalid_commitment_type() {
 #[should_panic(expected = "Error(Contract, #12)")] // InvalidCommitmentType
 fn test_mint_wrong_case_commitment_type() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "commitment_case"),
         &30,
@@ -307,6 +353,7 @@ fn test_mint_wrong_case_commitment_type() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 }
 
@@ -317,11 +364,13 @@ fn test_mint_wrong_case_commitment_type() {
 #[test]
 fn test_get_metadata() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let commitment_id = String::from_str(&e, "test_commitment");
     let duration = 30u32;
@@ -330,6 +379,7 @@ fn test_get_metadata() {
     let amount = 5000i128;
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -338,6 +388,7 @@ fn test_get_metadata() {
         &amount,
         &asset_address,
         &10,
+        &TransferPolicy::Free,
     );
 
     let nft = client.get_metadata(&token_id);
@@ -347,7 +398,7 @@ fn test_get_metadata() {
     assert_eq!(nft.metadata.max_loss_percent, max_loss);
     assert_eq!(nft.metadata.commitment_type, commitment_type);
     assert_eq!(nft.metadata.initial_amount, amount);
-    assert_eq!(nft.metadata.asset_address, asset_address);
+    assert_eq!(nft.metadata.asset_address, Some(asset_address));
     assert_eq!(nft.owner, owner);
     assert_eq!(nft.token_id, token_id);
 }
@@ -371,16 +422,19 @@ fn test_get_metadata_nonexistent_token() {
 #[test]
 fn test_owner_of() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
         create_test_metadata(&e, &asset_address);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -389,6 +443,7 @@ fn test_owner_of() {
         &amount,
         &asset,
         &penalty,
+        &TransferPolicy::Free,
     );
 
     let retrieved_owner = client.owner_of(&token_id);
@@ -413,16 +468,19 @@ fn test_owner_of_nonexistent_token() {
 #[test]
 fn test_is_active() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
         create_test_metadata(&e, &asset_address);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -431,10 +489,11 @@ fn test_is_active() {
         &amount,
         &asset,
         &penalty,
+        &TransferPolicy::Free,
     );
 
     // Newly minted NFT should be active
-    assert_eq!(client.is_active(&token_id), true);
+    assert!(client.is_active(&token_id));
 }
 
 #[test]
@@ -505,15 +564,18 @@ fn test_total_supply_initial() {
 #[test]
 fn test_total_supply_after_minting() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     // Mint 5 NFTs
     for _ in 0..5 {
         client.mint(
+            &admin,
             &owner,
             &String::from_str(&e, "commitment"),
             &30,
@@ -522,6 +584,7 @@ fn test_total_supply_after_minting() {
             &1000,
             &asset_address,
             &5,
+            &TransferPolicy::Free,
         );
     }
 
@@ -533,13 +596,14 @@ fn test_total_supply_after_minting() {
 fn test_total_supply_unchanged_after_transfer_and_settle() {
     let e = Env::default();
     e.mock_all_auths();
-    let (_admin, client, core_id) = setup_contract_with_core(&e);
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
     let owner1 = Address::generate(&e);
     let owner2 = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     assert_eq!(client.total_supply(), 0);
     let token_id = client.mint(
+        &admin,
         &owner1,
         &String::from_str(&e, "c1"),
         &1,
@@ -548,6 +612,7 @@ fn test_total_supply_unchanged_after_transfer_and_settle() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
     assert_eq!(client.total_supply(), 1);
     e.ledger().with_mut(|li| {
@@ -578,16 +643,19 @@ fn test_balance_of_initial() {
 #[test]
 fn test_balance_of_after_minting() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner1 = Address::generate(&e);
     let owner2 = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     // Mint 3 NFTs for owner1
     for _ in 0..3 {
         client.mint(
+            &admin,
             &owner1,
             &String::from_str(&e, "owner1_commitment"),
             &30,
@@ -596,12 +664,14 @@ fn test_balance_of_after_minting() {
             &1000,
             &asset_address,
             &5,
+            &TransferPolicy::Free,
         );
     }
 
     // Mint 2 NFTs for owner2
     for _ in 0..2 {
         client.mint(
+            &admin,
             &owner2,
             &String::from_str(&e, "owner2_commitment"),
             &30,
@@ -610,6 +680,7 @@ fn test_balance_of_after_minting() {
             &1000,
             &asset_address,
             &5,
+            &TransferPolicy::Free,
         );
     }
 
@@ -622,13 +693,14 @@ fn test_balance_of_after_minting() {
 fn test_balance_of_decremented_after_transfer() {
     let e = Env::default();
     e.mock_all_auths();
-    let (_admin, client, core_id) = setup_contract_with_core(&e);
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
     let owner = Address::generate(&e);
     let recipient = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     assert_eq!(client.balance_of(&owner), 0);
     let token_id = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "c1"),
         &1,
@@ -637,6 +709,7 @@ fn test_balance_of_decremented_after_transfer() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
     assert_eq!(client.balance_of(&owner), 1);
     assert_eq!(client.balance_of(&recipient), 0);
@@ -667,15 +740,18 @@ fn test_get_all_metadata_empty() {
 #[test]
 fn test_get_all_metadata() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     // Mint 3 NFTs
     for _ in 0..3 {
         client.mint(
+            &admin,
             &owner,
             &String::from_str(&e, "commitment"),
             &30,
@@ -684,6 +760,7 @@ fn test_get_all_metadata() {
             &1000,
             &asset_address,
             &5,
+            &TransferPolicy::Free,
         );
     }
 
@@ -715,16 +792,19 @@ fn test_get_nfts_by_owner_empty() {
 #[test]
 fn test_get_nfts_by_owner() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner1 = Address::generate(&e);
     let owner2 = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     // Mint 2 NFTs for owner1
     for _ in 0..2 {
         client.mint(
+            &admin,
             &owner1,
             &String::from_str(&e, "owner1"),
             &30,
@@ -733,12 +813,14 @@ fn test_get_nfts_by_owner() {
             &1000,
             &asset_address,
             &5,
+            &TransferPolicy::Free,
         );
     }
 
     // Mint 3 NFTs for owner2
     for _ in 0..3 {
         client.mint(
+            &admin,
             &owner2,
             &String::from_str(&e, "owner2"),
             &30,
@@ -747,6 +829,7 @@ fn test_get_nfts_by_owner() {
             &1000,
             &asset_address,
             &5,
+            &TransferPolicy::Free,
         );
     }
 
@@ -784,13 +867,14 @@ fn test_transfer() {
     let e = Env::default();
     e.mock_all_auths();
 
-    let (_admin, client, core_id) = setup_contract_with_core(&e);
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
     let owner1 = Address::generate(&e);
     let owner2 = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     // Mint with 1 day duration so we can settle it
     let token_id = client.mint(
+        &admin,
         &owner1,
         &String::from_str(&e, "commitment_001"),
         &1, // 1 day duration
@@ -799,6 +883,7 @@ fn test_transfer() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     // Verify initial state
@@ -813,7 +898,7 @@ fn test_transfer() {
     client.settle(&token_id);
 
     // Verify NFT is now inactive (unlocked)
-    assert_eq!(client.is_active(&token_id), false);
+    assert!(!client.is_active(&token_id));
 
     // Transfer NFT
     client.transfer(&owner1, &owner2, &token_id);
@@ -854,11 +939,13 @@ fn test_transfer_not_owner() {
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
         create_test_metadata(&e, &asset_address);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -867,6 +954,7 @@ fn test_transfer_not_owner() {
         &amount,
         &asset,
         &penalty,
+        &TransferPolicy::Free,
     );
 
     // Try to transfer from non-owner (should fail)
@@ -899,11 +987,13 @@ fn test_transfer_to_self() {
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
         create_test_metadata(&e, &asset_address);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -912,6 +1002,7 @@ fn test_transfer_to_self() {
         &amount,
         &asset,
         &penalty,
+        &TransferPolicy::Free,
     );
 
     // Try to transfer to self (should fail)
@@ -929,11 +1020,13 @@ fn test_transfer_locked_nft() {
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
         create_test_metadata(&e, &asset_address);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -942,17 +1035,18 @@ fn test_transfer_locked_nft() {
         &amount,
         &asset,
         &penalty,
+        &TransferPolicy::Free,
     );
 
     // Verify NFT is active
-    assert_eq!(client.is_active(&token_id), true);
+    assert!(client.is_active(&token_id));
 
     // Transfer active NFT (now allowed for secondary market)
     client.transfer(&owner, &recipient, &token_id);
-    
+
     // Verify ownership changed
     assert_eq!(client.owner_of(&token_id), recipient);
-    assert_eq!(client.is_active(&token_id), true); // Still active after transfer
+    assert!(client.is_active(&token_id)); // Still active after transfer
 }
 
 #[test]
@@ -960,13 +1054,14 @@ fn test_transfer_after_settlement() {
     let e = Env::default();
     e.mock_all_auths();
 
-    let (_admin, client, core_id) = setup_contract_with_core(&e);
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
     let owner = Address::generate(&e);
     let recipient = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     // Mint with 1 day duration
     let token_id = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "test_commitment"),
         &1, // 1 day duration
@@ -975,10 +1070,11 @@ fn test_transfer_after_settlement() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     // Verify NFT is active (locked) initially
-    assert_eq!(client.is_active(&token_id), true);
+    assert!(client.is_active(&token_id));
 
     // Fast forward time past expiration (2 days = 172800 seconds)
     e.ledger().with_mut(|li| {
@@ -989,7 +1085,7 @@ fn test_transfer_after_settlement() {
     client.settle(&token_id);
 
     // Verify NFT is now inactive (unlocked)
-    assert_eq!(client.is_active(&token_id), false);
+    assert!(!client.is_active(&token_id));
 
     // Transfer should now succeed
     client.transfer(&owner, &recipient, &token_id);
@@ -1023,11 +1119,13 @@ fn test_transfer_edge_case_self_transfer() {
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
         create_test_metadata(&e, &asset_address);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -1036,6 +1134,7 @@ fn test_transfer_edge_case_self_transfer() {
         &amount,
         &asset,
         &penalty,
+        &TransferPolicy::Free,
     );
 
     // Verify initial state
@@ -1068,11 +1167,13 @@ fn test_transfer_edge_case_from_non_owner() {
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
         create_test_metadata(&e, &asset_address);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -1081,6 +1182,7 @@ fn test_transfer_edge_case_from_non_owner() {
         &amount,
         &asset,
         &penalty,
+        &TransferPolicy::Free,
     );
 
     // Verify initial ownership
@@ -1113,11 +1215,13 @@ fn test_transfer_edge_case_address_validation_by_sdk() {
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
         create_test_metadata(&e, &asset_address);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -1126,6 +1230,7 @@ fn test_transfer_edge_case_address_validation_by_sdk() {
         &amount,
         &asset,
         &penalty,
+        &TransferPolicy::Free,
     );
 
     // The Address type in Soroban SDK is strongly typed and cannot be constructed
@@ -1163,7 +1268,7 @@ fn test_transfer_edge_cases_comprehensive() {
     let e = Env::default();
     e.mock_all_auths();
 
-    let (_admin, client, core_id) = setup_contract_with_core(&e);
+    let (admin, client, core_id) = setup_contract_with_core(&e);
     let owner1 = Address::generate(&e);
     let owner2 = Address::generate(&e);
     let owner3 = Address::generate(&e);
@@ -1171,6 +1276,7 @@ fn test_transfer_edge_cases_comprehensive() {
 
     // Mint two separate NFTs to test transfer chains
     let token_id_1 = client.mint(
+        &admin,
         &owner1,
         &String::from_str(&e, "commitment_edge_case_1"),
         &1, // 1 day to allow settlement
@@ -1179,9 +1285,11 @@ fn test_transfer_edge_cases_comprehensive() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     let token_id_2 = client.mint(
+        &admin,
         &owner1,
         &String::from_str(&e, "commitment_edge_case_2"),
         &1, // 1 day to allow settlement
@@ -1190,6 +1298,7 @@ fn test_transfer_edge_cases_comprehensive() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     // ===== Validation: Initial state =====
@@ -1307,6 +1416,150 @@ fn test_transfer_edge_cases_comprehensive() {
     );
 }
 
+// ============================================
+// Approval Tests
+// ============================================
+
+#[test]
+fn test_approve_and_transfer_from() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let token_id = mint_to_owner(&e, &client, &owner, &asset_address, "c1");
+
+    assert_eq!(client.get_approved(&token_id), None);
+    client.approve(&owner, &spender, &token_id, &100);
+    assert_eq!(client.get_approved(&token_id), Some(spender.clone()));
+
+    client.transfer_from(&spender, &owner, &recipient, &token_id);
+    assert_eq!(client.owner_of(&token_id), recipient);
+    assert_eq!(client.balance_of(&owner), 0);
+    assert_eq!(client.balance_of(&recipient), 1);
+
+    // The approval is consumed by the transfer
+    assert_eq!(client.get_approved(&token_id), None);
+    let result = client.try_transfer_from(&spender, &recipient, &owner, &token_id);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+}
+
+#[test]
+fn test_approval_expires_and_revokes() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let token_id = mint_to_owner(&e, &client, &owner, &asset_address, "c1");
+
+    e.ledger().with_mut(|li| li.sequence_number = 10);
+    let result = client.try_approve(&owner, &spender, &token_id, &9);
+    assert_eq!(result, Err(Ok(ContractError::InvalidExpiration)));
+
+    client.approve(&owner, &spender, &token_id, &10);
+    e.ledger().with_mut(|li| li.sequence_number = 11);
+    assert_eq!(client.get_approved(&token_id), None);
+    let result = client.try_transfer_from(&spender, &owner, &recipient, &token_id);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+
+    client.approve(&owner, &spender, &token_id, &20);
+    client.approve(&owner, &spender, &token_id, &0);
+    assert_eq!(client.get_approved(&token_id), None);
+}
+
+#[test]
+fn test_approvals_live_as_long_as_their_expiration() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let operator = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let token_id = mint_to_owner(&e, &client, &owner, &asset_address, "c1");
+
+    e.ledger().with_mut(|li| li.sequence_number = 100);
+    let live_until = 100 + 500_000;
+    client.approve(&owner, &spender, &token_id, &live_until);
+    client.set_approval_for_all(&owner, &operator, &live_until);
+    e.as_contract(&client.address, || {
+        let storage = e.storage().persistent();
+        assert!(storage.get_ttl(&DataKey::Approval(token_id)) >= 500_000);
+        assert!(
+            storage.get_ttl(&DataKey::OperatorApproval(owner.clone(), operator.clone())) >= 500_000
+        );
+    });
+
+    // An approval the entry could not live long enough to honour is rejected
+    let beyond_max = e.ledger().max_live_until_ledger() + 1;
+    assert_eq!(
+        client.try_approve(&owner, &spender, &token_id, &beyond_max),
+        Err(Ok(ContractError::InvalidExpiration))
+    );
+    assert_eq!(
+        client.try_set_approval_for_all(&owner, &operator, &beyond_max),
+        Err(Ok(ContractError::InvalidExpiration))
+    );
+}
+
+#[test]
+fn test_approve_requires_owner_or_operator() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let operator = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let token_id = mint_to_owner(&e, &client, &owner, &asset_address, "c1");
+
+    let result = client.try_approve(&operator, &spender, &token_id, &100);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+
+    client.set_approval_for_all(&owner, &operator, &100);
+    client.approve(&operator, &spender, &token_id, &100);
+    assert_eq!(client.get_approved(&token_id), Some(spender));
+}
+
+#[test]
+fn test_approval_for_all_transfer_and_revoke() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let operator = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let token_1 = mint_to_owner(&e, &client, &owner, &asset_address, "c1");
+    let token_2 = mint_to_owner(&e, &client, &owner, &asset_address, "c2");
+
+    assert!(!client.is_approved_for_all(&owner, &operator));
+    client.set_approval_for_all(&owner, &operator, &100);
+    assert!(client.is_approved_for_all(&owner, &operator));
+    let event = e.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        vec![
+            &e,
+            symbol_short!("ApprAll").into_val(&e),
+            owner.into_val(&e)
+        ]
+    );
+
+    client.transfer_from(&operator, &owner, &recipient, &token_1);
+    assert_eq!(client.owner_of(&token_1), recipient);
+
+    client.set_approval_for_all(&owner, &operator, &0);
+    assert!(!client.is_approved_for_all(&owner, &operator));
+    let result = client.try_transfer_from(&operator, &owner, &recipient, &token_2);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+
+    // Operator approval for one owner does not cover another owner's tokens
+    client.set_approval_for_all(&owner, &operator, &100);
+    let result = client.try_transfer_from(&operator, &recipient, &owner, &token_1);
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorized)));
+}
+
 // ============================================
 // Settle Tests
 // ============================================
@@ -1314,12 +1567,14 @@ fn test_transfer_edge_cases_comprehensive() {
 #[test]
 fn test_settle() {
     let e = Env::default();
-    let (_admin, client, core_id) = setup_contract_with_core(&e);
+    e.mock_all_auths();
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     // Mint with 1 day duration
     let token_id = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "test_commitment"),
         &1, // 1 day duration
@@ -1328,10 +1583,11 @@ fn test_settle() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     // NFT should be active initially
-    assert_eq!(client.is_active(&token_id), true);
+    assert!(client.is_active(&token_id));
 
     // Fast forward time past expiration (2 days = 172800 seconds)
     e.ledger().with_mut(|li| {
@@ -1339,13 +1595,13 @@ fn test_settle() {
     });
 
     // Verify it's expired
-    assert_eq!(client.is_expired(&token_id), true);
+    assert!(client.is_expired(&token_id));
 
     // Settle the NFT after expiry
     client.settle(&token_id);
 
     // NFT should now be inactive
-    assert_eq!(client.is_active(&token_id), false);
+    assert!(!client.is_active(&token_id));
 
     // Verify Settle event
     let events = e.events().all();
@@ -1369,11 +1625,13 @@ fn test_settle() {
 #[should_panic(expected = "Error(Contract, #9)")] // NotExpired
 fn test_settle_not_expired() {
     let e = Env::default();
-    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    e.mock_all_auths();
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "test_commitment"),
         &30, // 30 days duration
@@ -1382,6 +1640,7 @@ fn test_settle_not_expired() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     // Try to settle before expiration, should fail with NotExpired
@@ -1392,11 +1651,13 @@ fn test_settle_not_expired() {
 #[should_panic(expected = "Error(Contract, #8)")] // AlreadySettled
 fn test_settle_already_settled() {
     let e = Env::default();
-    let (_admin, client, core_id) = setup_contract_with_core(&e);
+    e.mock_all_auths();
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "test_commitment"),
         &1,
@@ -1405,6 +1666,7 @@ fn test_settle_already_settled() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     // Fast forward time
@@ -1419,11 +1681,13 @@ fn test_settle_already_settled() {
 #[test]
 fn test_settle_succeeds_after_expiry() {
     let e = Env::default();
-    let (_admin, client, core_id) = setup_contract_with_core(&e);
+    e.mock_all_auths();
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "test_commitment"),
         &1,
@@ -1432,12 +1696,13 @@ fn test_settle_succeeds_after_expiry() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
     e.ledger().with_mut(|li| {
         li.timestamp = 172800;
     });
     client.settle(&token_id);
-    assert_eq!(client.is_active(&token_id), false);
+    assert!(!client.is_active(&token_id));
 }
 
 // ============================================
@@ -1447,13 +1712,16 @@ fn test_settle_succeeds_after_expiry() {
 #[test]
 fn test_is_expired() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "test_commitment"),
         &1, // 1 day
@@ -1462,10 +1730,11 @@ fn test_is_expired() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     // Should not be expired initially
-    assert_eq!(client.is_expired(&token_id), false);
+    assert!(!client.is_expired(&token_id));
 
     // Fast forward 2 days
     e.ledger().with_mut(|li| {
@@ -1473,7 +1742,7 @@ fn test_is_expired() {
     });
 
     // Should now be expired
-    assert_eq!(client.is_expired(&token_id), true);
+    assert!(client.is_expired(&token_id));
 }
 
 #[test]
@@ -1494,19 +1763,22 @@ fn test_is_expired_nonexistent_token() {
 #[test]
 fn test_token_exists() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     // Token 0 should not exist yet
-    assert_eq!(client.token_exists(&0), false);
+    assert!(!client.token_exists(&0));
 
     let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
         create_test_metadata(&e, &asset_address);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &commitment_id,
         &duration,
@@ -1515,13 +1787,14 @@ fn test_token_exists() {
         &amount,
         &asset,
         &penalty,
+        &TransferPolicy::Free,
     );
 
     // Token should now exist
-    assert_eq!(client.token_exists(&token_id), true);
+    assert!(client.token_exists(&token_id));
 
     // Non-existent token should return false
-    assert_eq!(client.token_exists(&999), false);
+    assert!(!client.token_exists(&999));
 }
 
 // ============================================
@@ -1555,13 +1828,16 @@ fn test_get_admin_not_initialized() {
 #[should_panic(expected = "Error(Contract, #11)")] // InvalidMaxLoss
 fn test_mint_max_loss_percent_over_100() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "commitment_001"),
         &30,
@@ -1570,19 +1846,23 @@ fn test_mint_max_loss_percent_over_100() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 }
 
 #[test]
 fn test_mint_max_loss_percent_zero() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "commitment_001"),
         &30,
@@ -1591,6 +1871,7 @@ fn test_mint_max_loss_percent_zero() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     assert_eq!(token_id, 0);
@@ -1602,13 +1883,16 @@ fn test_mint_max_loss_percent_zero() {
 #[should_panic(expected = "Error(Contract, #10)")] // InvalidDuration
 fn test_mint_duration_days_zero() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "commitment_001"),
         &0, // duration_days = 0
@@ -1617,19 +1901,23 @@ fn test_mint_duration_days_zero() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 }
 
 #[test]
 fn test_mint_duration_days_one() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "commitment_001"),
         &1, // duration_days = 1 (minimum valid)
@@ -1638,6 +1926,7 @@ fn test_mint_duration_days_one() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     assert_eq!(token_id, 0);
@@ -1648,13 +1937,16 @@ fn test_mint_duration_days_one() {
 #[test]
 fn test_mint_duration_days_max() {
     let e = Env::default();
+    e.mock_all_auths();
     let (admin, client) = setup_contract(&e);
     let owner = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "commitment_001"),
         &u32::MAX, // duration_days = u32::MAX
@@ -1663,6 +1955,7 @@ fn test_mint_duration_days_max() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     assert_eq!(token_id, 0);
@@ -1682,6 +1975,7 @@ fn test_mint_duration_days_max() {
 #[test]
 fn test_metadata_timestamps() {
     let e = Env::default();
+    e.mock_all_auths();
 
     // Set initial ledger timestamp
     e.ledger().with_mut(|li| {
@@ -1693,8 +1987,10 @@ fn test_metadata_timestamps() {
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let token_id = client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "test"),
         &30, // 30 days
@@ -1703,6 +1999,7 @@ fn test_metadata_timestamps() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     let metadata = client.get_metadata(&token_id);
@@ -1718,13 +2015,14 @@ fn test_balance_updates_after_transfer() {
     let e = Env::default();
     e.mock_all_auths();
 
-    let (_admin, client, core_id) = setup_contract_with_core(&e);
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
     let owner1 = Address::generate(&e);
     let owner2 = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     // Mint multiple NFTs for owner1 with 1 day duration so we can settle them
     client.mint(
+        &admin,
         &owner1,
         &String::from_str(&e, "commitment_0"),
         &1, // 1 day duration
@@ -1733,8 +2031,10 @@ fn test_balance_updates_after_transfer() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
     client.mint(
+        &admin,
         &owner1,
         &String::from_str(&e, "commitment_1"),
         &1, // 1 day duration
@@ -1743,8 +2043,10 @@ fn test_balance_updates_after_transfer() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
     client.mint(
+        &admin,
         &owner1,
         &String::from_str(&e, "commitment_2"),
         &1, // 1 day duration
@@ -1753,6 +2055,7 @@ fn test_balance_updates_after_transfer() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     assert_eq!(client.balance_of(&owner1), 3);
@@ -1797,9 +2100,11 @@ fn test_mint_blocked_when_paused() {
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
     client.pause();
 
     client.mint(
+        &admin,
         &owner,
         &String::from_str(&e, "paused_commitment"),
         &30,
@@ -1808,6 +2113,7 @@ fn test_mint_blocked_when_paused() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 }
 
@@ -1823,8 +2129,10 @@ fn test_transfer_blocked_when_paused() {
     let asset_address = Address::generate(&e);
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     let token_id = client.mint(
+        &admin,
         &owner1,
         &String::from_str(&e, "commitment_001"),
         &30,
@@ -1833,6 +2141,7 @@ fn test_transfer_blocked_when_paused() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
 
     client.pause();
@@ -1844,12 +2153,13 @@ fn _test_unpause_restores_transfer() {
     let e = Env::default();
     e.mock_all_auths();
 
-    let (_admin, client, core_id) = setup_contract_with_core(&e);
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
     let owner1 = Address::generate(&e);
     let owner2 = Address::generate(&e);
     let asset_address = Address::generate(&e);
 
     let token_id = client.mint(
+        &admin,
         &owner1,
         &String::from_str(&e, "commitment_002"),
         &1, // 1 day duration so we can settle
@@ -1858,6 +2168,7 @@ fn _test_unpause_restores_transfer() {
         &1000,
         &asset_address,
         &5,
+        &TransferPolicy::Free,
     );
     e.ledger().with_mut(|li| {
         li.timestamp = 172800;
@@ -1916,6 +2227,7 @@ fn test_invariant_balance_sum_equals_supply_after_mints() {
     let owners: [&Address; 4] = [&owner_a, &owner_b, &owner_c, &owner_d];
 
     client.initialize(&admin);
+    client.add_minter(&admin);
 
     // Base case: empty state
     assert_eq!(client.total_supply(), 0);
@@ -2203,3 +2515,562 @@ fn test_invariant_transfer_chain_preserves_supply() {
     assert_eq!(client.balance_of(&c), 0);
     assert_eq!(client.balance_of(&d), 1);
 }
+
+// ============================================================================
+// Value Update Tests (top-up / partial exit sync from core)
+// ============================================================================
+
+#[test]
+fn test_update_value_by_core_contract() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let token_id = mint_to_owner(&e, &client, &owner, &asset_address, "value_sync");
+
+    assert_eq!(client.get_metadata(&token_id).metadata.current_value, 1000);
+    client.update_value(&token_id, &1500);
+
+    let nft = client.get_metadata(&token_id);
+    assert_eq!(nft.metadata.current_value, 1500);
+    assert_eq!(nft.metadata.initial_amount, 1000);
+}
+
+#[test]
+fn test_update_value_without_core_contract_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (admin, client) = setup_contract(&e);
+    client.initialize(&admin);
+    client.add_minter(&admin);
+    let owner = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let token_id = mint_to_owner(&e, &client, &owner, &asset_address, "value_no_core");
+
+    assert_eq!(
+        client.try_update_value(&token_id, &1500),
+        Err(Ok(ContractError::CoreContractNotSet))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")] // AlreadySettled
+fn test_update_value_inactive_token_fails() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let token_id = mint_to_owner(&e, &client, &owner, &asset_address, "value_inactive");
+
+    client.mark_inactive(&token_id);
+    client.update_value(&token_id, &1500);
+}
+
+// ============================================================================
+// Collection Metadata Tests
+// ============================================================================
+
+#[test]
+fn test_name_and_symbol() {
+    let e = Env::default();
+    let (_admin, client) = setup_contract(&e);
+    assert_eq!(client.name(), String::from_str(&e, "Commitment NFT"));
+    assert_eq!(client.symbol(), String::from_str(&e, "COMMIT"));
+}
+
+/// Decode an on-chain `data:application/json;base64,` token URI and parse its JSON.
+fn decode_token_uri(uri: &String) -> serde_json::Value {
+    use base64::Engine as _;
+    let uri = uri.to_string();
+    let payload = uri
+        .strip_prefix("data:application/json;base64,")
+        .expect("token URI is not a base64 JSON data URI");
+    let json = base64::engine::general_purpose::STANDARD
+        .decode(payload)
+        .expect("token URI payload is not valid base64");
+    serde_json::from_slice(&json).expect("token URI payload is not valid JSON")
+}
+
+#[test]
+fn test_token_uri_renders_json() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let token_id = mint_to_owner(&e, &client, &owner, &asset_address, "uri");
+    let expires_at = client.get_metadata(&token_id).metadata.expires_at;
+
+    let expected = serde_json::json!({
+        "name": std::format!("Commitment NFT #{token_id}"),
+        "attributes": [
+            {"trait_type": "type", "value": "balanced"},
+            {"trait_type": "status", "value": "active"},
+            {"display_type": "date", "trait_type": "expiry", "value": expires_at},
+            {"trait_type": "initial_amount", "value": "1000"},
+            {"trait_type": "asset", "value": asset_address.to_string().to_string()},
+        ]
+    });
+    assert_eq!(decode_token_uri(&client.token_uri(&token_id)), expected);
+
+    client.mark_inactive(&token_id);
+    let json = decode_token_uri(&client.token_uri(&token_id));
+    assert_eq!(json["attributes"][1]["value"], "inactive");
+}
+
+#[test]
+fn test_token_uri_fits_worst_case_metadata() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let token_id = client.mint(
+        &admin,
+        &owner,
+        &String::from_str(&e, "worst_case"),
+        &365,
+        &100,
+        &String::from_str(&e, "aggressive"),
+        &i128::MAX,
+        &Address::generate(&e),
+        &100,
+        &TransferPolicy::Free,
+    );
+
+    let json = decode_token_uri(&client.token_uri(&token_id));
+    assert_eq!(json["attributes"][0]["value"], "aggressive");
+    assert_eq!(json["attributes"][3]["value"], i128::MAX.to_string());
+}
+
+#[test]
+fn test_uri_buffer_fails_instead_of_truncating() {
+    let mut buf = UriBuffer::<8>::new();
+    buf.push(b"Zm9v").unwrap();
+    assert_eq!(buf.push(b"toolong"), Err(ContractError::TokenUriTooLong));
+    assert_eq!(buf.as_bytes(), b"Zm9v");
+
+    let mut buf = UriBuffer::<16>::new();
+    buf.push_base64(b"f").unwrap();
+    buf.push_base64(b"fo").unwrap();
+    buf.push_base64(b"foo").unwrap();
+    assert_eq!(buf.as_bytes(), b"Zg==Zm8=Zm9v");
+}
+
+#[test]
+fn test_mint_basket_omits_single_asset_attributes() {
+    let e = Env::default();
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let token_id = client.mint_basket(
+        &admin,
+        &owner,
+        &String::from_str(&e, "basket"),
+        &30,
+        &10,
+        &String::from_str(&e, "balanced"),
+        &5,
+        &TransferPolicy::Free,
+    );
+
+    let nft = client.get_metadata(&token_id);
+    assert!(nft.metadata.is_basket);
+    assert_eq!(nft.metadata.initial_amount, 0);
+    assert_eq!(nft.metadata.asset_address, None);
+
+    let json = decode_token_uri(&client.token_uri(&token_id));
+    let attributes = json["attributes"].as_array().unwrap();
+    assert_eq!(attributes.len(), 4);
+    assert_eq!(
+        attributes[3],
+        serde_json::json!({"trait_type": "basket", "value": "true"})
+    );
+}
+
+#[test]
+fn test_token_uri_with_base_uri() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let token_id = mint_to_owner(&e, &client, &owner, &asset_address, "uri");
+
+    let base_uri = String::from_str(&e, "https://commitlabs.example/nft/");
+    client.set_base_uri(&base_uri);
+    assert_eq!(client.get_base_uri(), Some(base_uri));
+    assert_eq!(
+        client.token_uri(&token_id),
+        String::from_str(
+            &e,
+            &std::format!("https://commitlabs.example/nft/{token_id}")
+        )
+    );
+
+    // Clearing the base URI falls back to on-chain JSON
+    client.set_base_uri(&String::from_str(&e, ""));
+    assert_eq!(client.get_base_uri(), None);
+    assert!(client
+        .token_uri(&token_id)
+        .to_string()
+        .starts_with("data:application/json;base64,"));
+}
+
+#[test]
+fn test_token_uri_errors() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    assert_eq!(
+        client.try_token_uri(&99),
+        Err(Ok(ContractError::TokenNotFound))
+    );
+
+    let too_long = "a".repeat(201);
+    assert_eq!(
+        client.try_set_base_uri(&String::from_str(&e, &too_long)),
+        Err(Ok(ContractError::InvalidBaseUri))
+    );
+}
+
+// ============================================================================
+// Transfer Policy Tests
+// ============================================================================
+
+fn mint_with_policy(
+    e: &Env,
+    client: &CommitmentNFTContractClient,
+    owner: &Address,
+    policy: TransferPolicy,
+) -> u32 {
+    let asset_address = Address::generate(e);
+    let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
+        create_test_metadata(e, &asset_address);
+    client.mint(
+        &client.get_admin(),
+        owner,
+        &commitment_id,
+        &duration,
+        &max_loss,
+        &commitment_type,
+        &amount,
+        &asset,
+        &penalty,
+        &policy,
+    )
+}
+
+#[test]
+fn test_locked_until_expiry_blocks_transfer_before_expiry() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let token_id = mint_with_policy(&e, &client, &owner, TransferPolicy::LockedUntilExpiry);
+    assert_eq!(
+        client.get_metadata(&token_id).transfer_policy,
+        TransferPolicy::LockedUntilExpiry
+    );
+
+    assert_eq!(
+        client.try_transfer(&owner, &buyer, &token_id),
+        Err(Ok(ContractError::NFTLocked))
+    );
+    client.approve(&owner, &buyer, &token_id, &100);
+    assert_eq!(
+        client.try_transfer_from(&buyer, &owner, &buyer, &token_id),
+        Err(Ok(ContractError::NFTLocked))
+    );
+
+    let expires_at = client.get_metadata(&token_id).metadata.expires_at;
+    e.ledger().with_mut(|li| li.timestamp = expires_at);
+    client.transfer(&owner, &buyer, &token_id);
+    assert_eq!(client.owner_of(&token_id), buyer);
+}
+
+#[test]
+fn test_soulbound_token_never_transfers() {
+    let e = Env::default();
+    let (_admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let token_id = mint_with_policy(&e, &client, &owner, TransferPolicy::Soulbound);
+
+    assert_eq!(
+        client.try_transfer(&owner, &buyer, &token_id),
+        Err(Ok(ContractError::TokenSoulbound))
+    );
+
+    let expires_at = client.get_metadata(&token_id).metadata.expires_at;
+    e.ledger().with_mut(|li| li.timestamp = expires_at);
+    client.settle(&token_id);
+    assert_eq!(
+        client.try_transfer(&owner, &buyer, &token_id),
+        Err(Ok(ContractError::TokenSoulbound))
+    );
+    assert_eq!(client.owner_of(&token_id), owner);
+}
+
+#[test]
+fn test_migrate_defaults_transfer_policy() {
+    let e = Env::default();
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let token_id = mint_with_policy(&e, &client, &owner, TransferPolicy::Soulbound);
+
+    // Simulate a v1 token written before transfer policies existed
+    e.as_contract(&client.address, || {
+        let key = DataKey::NFT(token_id);
+        let raw: Val = e.storage().persistent().get(&key).unwrap();
+        let mut fields = Map::<Symbol, Val>::try_from_val(&e, &raw).unwrap();
+        fields.remove(Symbol::new(&e, "transfer_policy"));
+        e.storage().persistent().set(&key, &fields);
+        e.storage().instance().set(&DataKey::Version, &1u32);
+    });
+
+    client.migrate(&admin, &1);
+    assert_eq!(client.get_version(), 5);
+    assert_eq!(
+        client.get_metadata(&token_id).transfer_policy,
+        TransferPolicy::Free
+    );
+}
+
+#[test]
+fn test_migrate_defaults_basket_flag() {
+    let e = Env::default();
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let token_id = mint_with_policy(&e, &client, &owner, TransferPolicy::Free);
+
+    // Simulate a v2 token written before baskets were marked
+    strip_metadata_fields(&e, &client, token_id, &["is_basket"], 2);
+
+    client.migrate(&admin, &2);
+    assert_eq!(client.get_version(), 5);
+    assert!(!client.get_metadata(&token_id).metadata.is_basket);
+}
+
+/// Rewrite a token's stored metadata without `names`, as an older layout would
+/// have stored it, and set the contract's stored version to `version`.
+fn strip_metadata_fields(
+    e: &Env,
+    client: &CommitmentNFTContractClient,
+    token_id: u32,
+    names: &[&str],
+    version: u32,
+) {
+    e.as_contract(&client.address, || {
+        let key = DataKey::NFT(token_id);
+        let raw: Val = e.storage().persistent().get(&key).unwrap();
+        let mut fields = Map::<Symbol, Val>::try_from_val(e, &raw).unwrap();
+        let metadata_key = Symbol::new(e, "metadata");
+        let mut metadata =
+            Map::<Symbol, Val>::try_from_val(e, &fields.get(metadata_key.clone()).unwrap())
+                .unwrap();
+        for name in names {
+            metadata.remove(Symbol::new(e, name));
+        }
+        fields.set(metadata_key, metadata.to_val());
+        e.storage().persistent().set(&key, &fields);
+        e.storage().instance().set(&DataKey::Version, &version);
+    });
+}
+
+#[test]
+fn test_migrate_backfills_current_value() {
+    let e = Env::default();
+    let (admin, client, _core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let buyer = Address::generate(&e);
+    let token_id = mint_with_policy(&e, &client, &owner, TransferPolicy::Free);
+    let initial_amount = client.get_metadata(&token_id).metadata.initial_amount;
+
+    // Simulate a v3 token written before current_value was tracked
+    strip_metadata_fields(&e, &client, token_id, &["current_value"], 3);
+    assert!(client.try_get_metadata(&token_id).is_err());
+
+    client.migrate(&admin, &3);
+    assert_eq!(client.get_version(), 5);
+    assert_eq!(
+        client.get_metadata(&token_id).metadata.current_value,
+        initial_amount
+    );
+    client.transfer(&owner, &buyer, &token_id);
+    assert_eq!(client.owner_of(&token_id), buyer);
+}
+
+#[test]
+fn test_migrate_clears_basket_asset_address() {
+    let e = Env::default();
+    let (admin, client, core_id) = setup_contract_with_core(&e);
+    let owner = Address::generate(&e);
+    let basket_id = client.mint_basket(
+        &admin,
+        &owner,
+        &String::from_str(&e, "basket"),
+        &30,
+        &10,
+        &String::from_str(&e, "balanced"),
+        &5,
+        &TransferPolicy::Free,
+    );
+    let token_id = mint_with_policy(&e, &client, &owner, TransferPolicy::Free);
+    let asset_address = client.get_metadata(&token_id).metadata.asset_address;
+
+    // Simulate a v4 basket token that stored its minting contract as the asset
+    e.as_contract(&client.address, || {
+        let key = DataKey::NFT(basket_id);
+        let mut nft: CommitmentNFT = e.storage().persistent().get(&key).unwrap();
+        nft.metadata.asset_address = Some(core_id.clone());
+        e.storage().persistent().set(&key, &nft);
+        e.storage().instance().set(&DataKey::Version, &4u32);
+    });
+
+    client.migrate(&admin, &4);
+    assert_eq!(client.get_version(), 5);
+    assert_eq!(client.get_metadata(&basket_id).metadata.asset_address, None);
+    assert_eq!(
+        client.get_metadata(&token_id).metadata.asset_address,
+        asset_address
+    );
+}
+
+// ============================================================================
+// Minter and Core-only Lifecycle Tests
+// ============================================================================
+
+#[test]
+fn test_mint_requires_authorized_minter() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (admin, client) = setup_contract(&e);
+    client.initialize(&admin);
+    let minter = Address::generate(&e);
+    let owner = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let (commitment_id, duration, max_loss, commitment_type, amount, asset, penalty) =
+        create_test_metadata(&e, &asset_address);
+
+    assert!(!client.is_minter(&minter));
+    let result = client.try_mint(
+        &minter,
+        &owner,
+        &commitment_id,
+        &duration,
+        &max_loss,
+        &commitment_type,
+        &amount,
+        &asset,
+        &penalty,
+        &TransferPolicy::Free,
+    );
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedMinter)));
+
+    client.add_minter(&minter);
+    assert!(client.is_minter(&minter));
+    let token_id = client.mint(
+        &minter,
+        &owner,
+        &commitment_id,
+        &duration,
+        &max_loss,
+        &commitment_type,
+        &amount,
+        &asset,
+        &penalty,
+        &TransferPolicy::Free,
+    );
+    assert_eq!(client.owner_of(&token_id), owner);
+
+    client.remove_minter(&minter);
+    assert!(!client.is_minter(&minter));
+    let result = client.try_mint(
+        &minter,
+        &owner,
+        &commitment_id,
+        &duration,
+        &max_loss,
+        &commitment_type,
+        &amount,
+        &asset,
+        &penalty,
+        &TransferPolicy::Free,
+    );
+    assert_eq!(result, Err(Ok(ContractError::NotAuthorizedMinter)));
+}
+
+#[test]
+fn test_set_core_contract_moves_minter_role() {
+    let e = Env::default();
+    let (_admin, client, core_id) = setup_contract_with_core(&e);
+    assert!(client.is_minter(&core_id));
+
+    let new_core = Address::generate(&e);
+    client.set_core_contract(&new_core);
+    assert!(!client.is_minter(&core_id));
+    assert!(client.is_minter(&new_core));
+}
+
+#[test]
+fn test_settle_and_mark_inactive_require_core_contract() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (admin, client) = setup_contract(&e);
+    client.initialize(&admin);
+    client.add_minter(&admin);
+    let owner = Address::generate(&e);
+    let asset_address = Address::generate(&e);
+    let token_id = mint_to_owner(&e, &client, &owner, &asset_address, "no_core");
+
+    e.ledger().with_mut(|li| li.timestamp = 172_800);
+    assert_eq!(
+        client.try_settle(&token_id),
+        Err(Ok(ContractError::CoreContractNotSet))
+    );
+    assert_eq!(
+        client.try_mark_inactive(&token_id),
+        Err(Ok(ContractError::CoreContractNotSet))
+    );
+    assert!(client.is_active(&token_id));
+}
+
+// ============================================================================
+// Two-step Admin Transfer Tests
+// ============================================================================
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (admin, client) = setup_contract(&e);
+    client.initialize(&admin);
+    let new_admin = Address::generate(&e);
+
+    client.propose_admin(&admin, &new_admin);
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+
+    let stranger = Address::generate(&e);
+    assert_eq!(
+        client.try_accept_admin(&stranger),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+
+    client.accept_admin(&new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+#[test]
+fn test_cancel_admin_proposal() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (admin, client) = setup_contract(&e);
+    client.initialize(&admin);
+    let new_admin = Address::generate(&e);
+
+    client.propose_admin(&admin, &new_admin);
+    client.cancel_admin_proposal(&admin);
+    assert_eq!(
+        client.try_accept_admin(&new_admin),
+        Err(Ok(ContractError::NotAuthorized))
+    );
+    assert_eq!(client.get_admin(), admin);
+}
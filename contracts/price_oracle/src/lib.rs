@@ -5,7 +5,7 @@
 //! Provides whitelisted price feeds with validation, time-based validity (staleness),
 //! and optional fallback. Used for value calculation, drawdown, compliance, and fees.
 
-use shared_utils::Validation;
+use shared_utils::{AccessControl, Validation};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
 };
@@ -284,13 +284,45 @@ impl PriceOracleContract {
         read_version(&e)
     }
 
-    /// Update admin (admin-only).
+    /// Update admin (admin-only). Deprecated: routed through `propose_admin`, so the change
+    /// only takes effect once `new_admin` calls `accept_admin`.
     pub fn set_admin(e: Env, caller: Address, new_admin: Address) -> Result<(), OracleError> {
+        Self::propose_admin(e, caller, new_admin)
+    }
+
+    /// Nominate a new admin (admin-only). Takes effect once the nominee calls `accept_admin`.
+    pub fn propose_admin(e: Env, caller: Address, new_admin: Address) -> Result<(), OracleError> {
+        require_admin_result(&e, &caller)?;
+        AccessControl::propose_admin(&e, &caller, &new_admin);
+        Ok(())
+    }
+
+    /// Accept a pending admin nomination. Caller must be the nominee.
+    pub fn accept_admin(e: Env, caller: Address) -> Result<(), OracleError> {
+        let admin = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::Admin)
+            .ok_or(OracleError::NotInitialized)?;
+        if !AccessControl::accept_admin(&e, &caller, &admin) {
+            return Err(OracleError::Unauthorized);
+        }
+        e.storage().instance().set(&DataKey::Admin, &caller);
+        Ok(())
+    }
+
+    /// Withdraw a pending admin nomination (admin-only).
+    pub fn cancel_admin_proposal(e: Env, caller: Address) -> Result<(), OracleError> {
         require_admin_result(&e, &caller)?;
-        e.storage().instance().set(&DataKey::Admin, &new_admin);
+        AccessControl::cancel_admin_proposal(&e, &caller);
         Ok(())
     }
 
+    /// Get the pending admin nominee, if any.
+    pub fn get_pending_admin(e: Env) -> Option<Address> {
+        AccessControl::get_pending_admin(&e)
+    }
+
     /// Upgrade contract WASM (admin-only).
     pub fn upgrade(e: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), OracleError> {
         require_admin_result(&e, &caller)?;
@@ -327,3 +327,31 @@ fn test_migrate_version_checks_and_replay_safety() {
     });
     assert!(!legacy_exists);
 }
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let new_admin = Address::generate(&e);
+    let contract_id = e.register_contract(None, PriceOracleContract);
+    let client = PriceOracleContractClient::new(&e, &contract_id);
+    client.initialize(&admin);
+
+    client.propose_admin(&admin, &new_admin);
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(
+        client.try_accept_admin(&Address::generate(&e)),
+        Err(Ok(OracleError::Unauthorized))
+    );
+
+    client.cancel_admin_proposal(&admin);
+    assert_eq!(
+        client.try_accept_admin(&new_admin),
+        Err(Ok(OracleError::Unauthorized))
+    );
+
+    client.propose_admin(&admin, &new_admin);
+    client.accept_admin(&new_admin);
+    assert_eq!(client.get_admin(), new_admin);
+}
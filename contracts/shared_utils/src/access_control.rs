@@ -1,12 +1,15 @@
 //! Access control patterns and utilities
 
 use super::storage::Storage;
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
 
 /// Access control helper functions
 pub struct AccessControl;
 
 impl AccessControl {
+    /// Storage key for the admin nominated by `propose_admin`
+    pub const PENDING_ADMIN_KEY: Symbol = symbol_short!("pend_adm");
+
     /// Require that the caller is the admin
     ///
     /// # Arguments
@@ -100,6 +103,75 @@ impl AccessControl {
 
         panic!("Unauthorized: caller is not the owner or admin");
     }
+
+    /// Nominate `new_admin` as the next admin (step one of a two-step transfer).
+    ///
+    /// The calling contract must already have authenticated `current_admin`
+    /// against its own admin record. Replaces any earlier proposal.
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `current_admin` - The admin making the proposal
+    /// * `new_admin` - The nominated admin
+    pub fn propose_admin(e: &Env, current_admin: &Address, new_admin: &Address) {
+        e.storage()
+            .instance()
+            .set(&Self::PENDING_ADMIN_KEY, new_admin);
+        e.events().publish(
+            (symbol_short!("AdmProp"), current_admin.clone()),
+            (new_admin.clone(), e.ledger().timestamp()),
+        );
+    }
+
+    /// Get the admin nominated by `propose_admin`, if any
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    pub fn get_pending_admin(e: &Env) -> Option<Address> {
+        e.storage().instance().get(&Self::PENDING_ADMIN_KEY)
+    }
+
+    /// Withdraw a pending admin proposal. No-op if nothing is pending.
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `current_admin` - The admin cancelling the proposal (already authenticated)
+    pub fn cancel_admin_proposal(e: &Env, current_admin: &Address) {
+        if let Some(pending) = Self::get_pending_admin(e) {
+            e.storage().instance().remove(&Self::PENDING_ADMIN_KEY);
+            e.events().publish(
+                (symbol_short!("AdmCancel"), current_admin.clone()),
+                (pending, e.ledger().timestamp()),
+            );
+        }
+    }
+
+    /// Accept a pending admin proposal (step two of a two-step transfer).
+    ///
+    /// Requires auth from `caller` and clears the proposal. The calling contract
+    /// is responsible for writing `caller` into its own admin record.
+    ///
+    /// # Arguments
+    /// * `e` - The environment
+    /// * `caller` - The nominated admin
+    /// * `previous_admin` - The admin being replaced (for the event)
+    ///
+    /// # Returns
+    /// `false` if no proposal is pending or `caller` is not the nominee
+    pub fn accept_admin(e: &Env, caller: &Address, previous_admin: &Address) -> bool {
+        caller.require_auth();
+        match Self::get_pending_admin(e) {
+            Some(pending) if pending == *caller => {
+                e.storage().instance().remove(&Self::PENDING_ADMIN_KEY);
+                e.events().publish(
+                    (symbol_short!("AdmAccept"), caller.clone()),
+                    (previous_admin.clone(), e.ledger().timestamp()),
+                );
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -136,6 +208,54 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_two_step_admin_transfer() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = <soroban_sdk::Address as TestAddress>::generate(&env);
+        let nominee = <soroban_sdk::Address as TestAddress>::generate(&env);
+        let other = <soroban_sdk::Address as TestAddress>::generate(&env);
+
+        let contract_id = env.register_contract(None, TestContract);
+
+        // Each accept runs in its own frame: an address may only authorize once per frame
+        env.as_contract(&contract_id, || {
+            assert_eq!(AccessControl::get_pending_admin(&env), None);
+            assert!(!AccessControl::accept_admin(&env, &nominee, &admin));
+        });
+        env.as_contract(&contract_id, || {
+            AccessControl::propose_admin(&env, &admin, &nominee);
+            assert_eq!(
+                AccessControl::get_pending_admin(&env),
+                Some(nominee.clone())
+            );
+            assert!(!AccessControl::accept_admin(&env, &other, &admin));
+        });
+        env.as_contract(&contract_id, || {
+            assert!(AccessControl::accept_admin(&env, &nominee, &admin));
+            assert_eq!(AccessControl::get_pending_admin(&env), None);
+        });
+    }
+
+    #[test]
+    fn test_cancel_admin_proposal() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = <soroban_sdk::Address as TestAddress>::generate(&env);
+        let nominee = <soroban_sdk::Address as TestAddress>::generate(&env);
+
+        let contract_id = env.register_contract(None, TestContract);
+
+        env.as_contract(&contract_id, || {
+            AccessControl::propose_admin(&env, &admin, &nominee);
+            AccessControl::cancel_admin_proposal(&env, &admin);
+            assert_eq!(AccessControl::get_pending_admin(&env), None);
+        });
+        env.as_contract(&contract_id, || {
+            assert!(!AccessControl::accept_admin(&env, &nominee, &admin));
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Unauthorized function call for address")]
     fn test_require_owner() {
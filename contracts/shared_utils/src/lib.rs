@@ -12,6 +12,7 @@
 //! - Access control patterns
 //! - Event emission patterns
 //! - Rate limiting helpers
+//! - Fee helpers (basis points)
 
 pub mod access_control;
 pub mod batch;
@@ -19,6 +20,7 @@ pub mod emergency;
 pub mod error_codes;
 pub mod errors;
 pub mod events;
+pub mod fees;
 pub mod math;
 pub mod pausable;
 pub mod rate_limiting;
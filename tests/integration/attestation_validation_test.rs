@@ -129,6 +129,7 @@ fn test_attest_succeeds_after_commitment_created() {
         min_fee_threshold: 1000,
             grace_period_days: 0,
         grace_period_days: 1,
+        transfer_policy: commitment_core::TransferPolicy::Free,
     };
 
     let commitment_id = env.as_contract(&core_id, || {
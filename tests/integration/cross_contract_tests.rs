@@ -12,8 +12,8 @@ use soroban_sdk::{
     Address, Env, String, Symbol, IntoVal, Vec,
 };
 
-use commitment_core::{CommitmentCoreContract, CommitmentRules};
-use commitment_nft::CommitmentNFTContract;
+use commitment_core::{CommitmentCoreContract, CommitmentRules, CommitmentStatus, TransferPolicy};
+use commitment_nft::{CommitmentNFTContract, ContractError as NftError};
 use attestation_engine::{AttestationEngineContract, AttestationError};
 use allocation_logic::{AllocationStrategiesContract, RiskLevel, Strategy};
 
@@ -63,6 +63,7 @@ fn test_verify_compliance_uses_core_commitment_data() {
         .as_contract(&harness.contracts.commitment_core, || {
             CommitmentCoreContract::update_value(
                 harness.env.clone(),
+                harness.accounts.admin.clone(),
                 commitment_id.clone(),
                 new_value,
             )
@@ -372,6 +373,7 @@ fn test_commitment_settlement_calls_nft_settle() {
         early_exit_penalty: 5,
         min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     let commitment_id = harness
@@ -401,7 +403,7 @@ fn test_commitment_settlement_calls_nft_settle() {
     harness
         .env
         .as_contract(&harness.contracts.commitment_core, || {
-            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone())
+            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone(), user.clone())
         });
 
     // Verify NFT is no longer active
@@ -418,7 +420,302 @@ fn test_commitment_settlement_calls_nft_settle() {
         .as_contract(&harness.contracts.commitment_core, || {
             CommitmentCoreContract::get_commitment(harness.env.clone(), commitment_id.clone())
         });
-    assert_eq!(commitment.status, String::from_str(&harness.env, "settled"));
+    assert_eq!(commitment.status, CommitmentStatus::Settled);
+}
+
+/// Test: Rollover settles the old NFT and mints one for the successor
+#[test]
+fn test_rollover_settles_old_nft_and_mints_successor() {
+    let harness = TestHarness::new();
+    let user = &harness.accounts.user1;
+    let amount = 1_000_000_000_000i128;
+
+    harness.approve_tokens(user, &harness.contracts.commitment_core, amount);
+
+    let rules = CommitmentRules {
+        duration_days: 1,
+        max_loss_percent: 10,
+        commitment_type: String::from_str(&harness.env, "balanced"),
+        early_exit_penalty: 5,
+        min_fee_threshold: 1000,
+        grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
+    };
+
+    let commitment_id = harness
+        .env
+        .as_contract(&harness.contracts.commitment_core, || {
+            CommitmentCoreContract::create_commitment(
+                harness.env.clone(),
+                user.clone(),
+                amount,
+                harness.contracts.token.clone(),
+                rules.clone(),
+            )
+        });
+
+    harness.advance_days(2);
+
+    let successor_id = harness
+        .env
+        .as_contract(&harness.contracts.commitment_core, || {
+            CommitmentCoreContract::rollover(harness.env.clone(), commitment_id.clone(), rules)
+        });
+
+    let successor = harness
+        .env
+        .as_contract(&harness.contracts.commitment_core, || {
+            CommitmentCoreContract::get_commitment(harness.env.clone(), successor_id.clone())
+        });
+    assert_eq!(successor.amount, amount);
+    assert_eq!(successor.nft_token_id, 1);
+
+    harness
+        .env
+        .as_contract(&harness.contracts.commitment_nft, || {
+            assert!(!CommitmentNFTContract::is_active(harness.env.clone(), 0).unwrap());
+            assert!(CommitmentNFTContract::is_active(harness.env.clone(), 1).unwrap());
+        });
+}
+
+#[test]
+fn test_resolve_violation_marks_nft_inactive() {
+    let harness = TestHarness::new();
+    let user = &harness.accounts.user1;
+    let admin = &harness.accounts.admin;
+    let amount = 1_000_000_000_000i128;
+
+    harness.approve_tokens(user, &harness.contracts.commitment_core, amount);
+
+    let rules = CommitmentRules {
+        duration_days: 30,
+        max_loss_percent: 10,
+        commitment_type: String::from_str(&harness.env, "balanced"),
+        early_exit_penalty: 5,
+        min_fee_threshold: 1000,
+        grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
+    };
+
+    let commitment_id = harness
+        .env
+        .as_contract(&harness.contracts.commitment_core, || {
+            CommitmentCoreContract::create_commitment(
+                harness.env.clone(),
+                user.clone(),
+                amount,
+                harness.contracts.token.clone(),
+                rules,
+            )
+        });
+
+    harness
+        .env
+        .as_contract(&harness.contracts.commitment_core, || {
+            CommitmentCoreContract::update_value(
+                harness.env.clone(),
+                admin.clone(),
+                commitment_id.clone(),
+                amount / 2,
+            );
+            CommitmentCoreContract::resolve_violation(harness.env.clone(), commitment_id.clone());
+        });
+
+    harness
+        .env
+        .as_contract(&harness.contracts.commitment_nft, || {
+            assert!(!CommitmentNFTContract::is_active(harness.env.clone(), 0).unwrap());
+        });
+}
+
+#[test]
+fn test_nft_transfer_moves_commitment_ownership() {
+    let harness = TestHarness::new();
+    let seller = &harness.accounts.user1;
+    let buyer = &harness.accounts.user2;
+    let amount = 1_000_000_000_000i128;
+
+    harness.approve_tokens(seller, &harness.contracts.commitment_core, amount);
+
+    let rules = CommitmentRules {
+        duration_days: 30,
+        max_loss_percent: 10,
+        commitment_type: String::from_str(&harness.env, "balanced"),
+        early_exit_penalty: 5,
+        min_fee_threshold: 1000,
+        grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
+    };
+
+    let commitment_id = harness
+        .env
+        .as_contract(&harness.contracts.commitment_core, || {
+            CommitmentCoreContract::create_commitment(
+                harness.env.clone(),
+                seller.clone(),
+                amount,
+                harness.contracts.token.clone(),
+                rules,
+            )
+        });
+
+    harness
+        .env
+        .as_contract(&harness.contracts.commitment_nft, || {
+            CommitmentNFTContract::transfer(harness.env.clone(), seller.clone(), buyer.clone(), 0)
+                .unwrap();
+        });
+
+    harness
+        .env
+        .as_contract(&harness.contracts.commitment_core, || {
+            let commitment =
+                CommitmentCoreContract::get_commitment(harness.env.clone(), commitment_id.clone());
+            assert_eq!(commitment.owner, *buyer);
+            let buyer_ids =
+                CommitmentCoreContract::get_owner_commitments(harness.env.clone(), buyer.clone());
+            assert_eq!(buyer_ids.len(), 1);
+            let seller_ids =
+                CommitmentCoreContract::get_owner_commitments(harness.env.clone(), seller.clone());
+            assert_eq!(seller_ids.len(), 0);
+        });
+}
+
+/// Test: NFTs minted by other minters transfer while core is set, without touching
+/// core commitments, even when they reuse a core commitment ID
+#[test]
+fn test_non_core_minted_nft_transfers_with_core_set() {
+    let harness = TestHarness::new();
+    let seller = &harness.accounts.user1;
+    let buyer = &harness.accounts.user2;
+    let minter = Address::generate(&harness.env);
+    let amount = 1_000_000_000_000i128;
+
+    harness.approve_tokens(seller, &harness.contracts.commitment_core, amount);
+
+    let rules = CommitmentRules {
+        duration_days: 30,
+        max_loss_percent: 10,
+        commitment_type: String::from_str(&harness.env, "balanced"),
+        early_exit_penalty: 5,
+        min_fee_threshold: 1000,
+        grace_period_days: 0,
+        transfer_policy: TransferPolicy::Free,
+    };
+
+    let commitment_id = harness
+        .env
+        .as_contract(&harness.contracts.commitment_core, || {
+            CommitmentCoreContract::create_commitment(
+                harness.env.clone(),
+                seller.clone(),
+                amount,
+                harness.contracts.token.clone(),
+                rules,
+            )
+        });
+
+    harness
+        .env
+        .as_contract(&harness.contracts.commitment_nft, || {
+            CommitmentNFTContract::add_minter(harness.env.clone(), minter.clone()).unwrap();
+        });
+
+    for external_id in [
+        String::from_str(&harness.env, "external_1"),
+        commitment_id.clone(),
+    ] {
+        let token_id = harness
+            .env
+            .as_contract(&harness.contracts.commitment_nft, || {
+                CommitmentNFTContract::mint(
+                    harness.env.clone(),
+                    minter.clone(),
+                    seller.clone(),
+                    external_id,
+                    30,
+                    10,
+                    String::from_str(&harness.env, "balanced"),
+                    1_000,
+                    harness.contracts.token.clone(),
+                    5,
+                    commitment_nft::TransferPolicy::Free,
+                )
+                .unwrap()
+            });
+        harness
+            .env
+            .as_contract(&harness.contracts.commitment_nft, || {
+                CommitmentNFTContract::transfer(
+                    harness.env.clone(),
+                    seller.clone(),
+                    buyer.clone(),
+                    token_id,
+                )
+                .unwrap();
+                assert_eq!(
+                    CommitmentNFTContract::owner_of(harness.env.clone(), token_id).unwrap(),
+                    *buyer
+                );
+            });
+    }
+
+    harness
+        .env
+        .as_contract(&harness.contracts.commitment_core, || {
+            let commitment =
+                CommitmentCoreContract::get_commitment(harness.env.clone(), commitment_id.clone());
+            assert_eq!(commitment.owner, *seller);
+            let buyer_ids =
+                CommitmentCoreContract::get_owner_commitments(harness.env.clone(), buyer.clone());
+            assert_eq!(buyer_ids.len(), 0);
+        });
+}
+
+/// Test: A commitment's transfer policy is applied to the NFT minted for it
+#[test]
+fn test_locked_commitment_nft_cannot_transfer_before_expiry() {
+    let harness = TestHarness::new();
+    let seller = &harness.accounts.user1;
+    let buyer = &harness.accounts.user2;
+    let amount = 1_000_000_000_000i128;
+
+    harness.approve_tokens(seller, &harness.contracts.commitment_core, amount);
+
+    let rules = CommitmentRules {
+        duration_days: 30,
+        max_loss_percent: 10,
+        commitment_type: String::from_str(&harness.env, "balanced"),
+        early_exit_penalty: 5,
+        min_fee_threshold: 1000,
+        grace_period_days: 0,
+        transfer_policy: TransferPolicy::LockedUntilExpiry,
+    };
+
+    harness
+        .env
+        .as_contract(&harness.contracts.commitment_core, || {
+            CommitmentCoreContract::create_commitment(
+                harness.env.clone(),
+                seller.clone(),
+                amount,
+                harness.contracts.token.clone(),
+                rules,
+            )
+        });
+
+    harness
+        .env
+        .as_contract(&harness.contracts.commitment_nft, || {
+            let nft = CommitmentNFTContract::get_metadata(harness.env.clone(), 0).unwrap();
+            assert_eq!(
+                nft.transfer_policy,
+                commitment_nft::TransferPolicy::LockedUntilExpiry
+            );
+            let result =
+                CommitmentNFTContract::transfer(harness.env.clone(), seller.clone(), buyer.clone(), 0);
+            assert_eq!(result, Err(NftError::NFTLocked));
+        });
 }
 
 /// Test: Allocation logic interacts with pools correctly
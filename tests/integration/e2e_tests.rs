@@ -12,7 +12,7 @@ use soroban_sdk::{
     Address, Env, String,
 };
 
-use commitment_core::{CommitmentCoreContract, CommitmentRules};
+use commitment_core::{CommitmentCoreContract, CommitmentRules, CommitmentStatus, TransferPolicy};
 use commitment_nft::CommitmentNFTContract;
 use attestation_engine::AttestationEngineContract;
 use allocation_logic::{AllocationStrategiesContract, RiskLevel, Strategy};
@@ -47,6 +47,7 @@ fn test_e2e_complete_commitment_lifecycle() {
         early_exit_penalty: 5,
         min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     let commitment_id = harness
@@ -68,7 +69,7 @@ fn test_e2e_complete_commitment_lifecycle() {
             CommitmentCoreContract::get_commitment(harness.env.clone(), commitment_id.clone())
         });
     assert_eq!(commitment.owner, *user);
-    assert_eq!(commitment.status, String::from_str(&harness.env, "active"));
+    assert_eq!(commitment.status, CommitmentStatus::Active);
 
     // Verify NFT minted
     let nft_balance = harness
@@ -130,7 +131,7 @@ fn test_e2e_complete_commitment_lifecycle() {
     harness
         .env
         .as_contract(&harness.contracts.commitment_core, || {
-            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone())
+            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone(), user.clone())
         });
 
     // ========== PHASE 5: VERIFICATION ==========
@@ -142,7 +143,7 @@ fn test_e2e_complete_commitment_lifecycle() {
         });
     assert_eq!(
         settled_commitment.status,
-        String::from_str(&harness.env, "settled")
+        CommitmentStatus::Settled
     );
 
     // Verify NFT is inactive
@@ -185,6 +186,7 @@ fn test_e2e_early_exit_with_penalty() {
         early_exit_penalty,
         min_fee_threshold: 500,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     let commitment_id = harness
@@ -220,7 +222,7 @@ fn test_e2e_early_exit_with_penalty() {
         });
     assert_eq!(
         commitment.status,
-        String::from_str(&harness.env, "early_exit")
+        CommitmentStatus::EarlyExit
     );
 
     // Verify penalty was applied
@@ -387,6 +389,7 @@ fn test_e2e_violation_detection_flow() {
         early_exit_penalty: 3,
         min_fee_threshold: 100,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     let commitment_id = harness
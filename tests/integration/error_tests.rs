@@ -10,7 +10,7 @@
 use crate::harness::{TestHarness, DEFAULT_USER_BALANCE, SECONDS_PER_DAY};
 use soroban_sdk::{testutils::Address as _, Address, Env, String};
 
-use commitment_core::{CommitmentCoreContract, CommitmentError, CommitmentRules};
+use commitment_core::{CommitmentCoreContract, CommitmentError, CommitmentRules, TransferPolicy};
 use commitment_nft::{CommitmentNFTContract, ContractError as NftError};
 use attestation_engine::{AttestationEngineContract, AttestationError};
 use allocation_logic::{AllocationStrategiesContract, Error as AllocationError, RiskLevel, Strategy};
@@ -220,6 +220,7 @@ fn test_error_expiration_overflow() {
         early_exit_penalty: 5,
         min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     harness
@@ -252,6 +253,7 @@ fn test_error_zero_duration_commitment() {
         early_exit_penalty: 5,
         min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     harness
@@ -284,6 +286,7 @@ fn test_error_invalid_max_loss_percent() {
         early_exit_penalty: 5,
         min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     harness
@@ -316,6 +319,7 @@ fn test_error_invalid_commitment_type() {
         early_exit_penalty: 5,
         min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     harness
@@ -532,6 +536,7 @@ fn test_error_double_settlement() {
         early_exit_penalty: 5,
         min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     let commitment_id = harness
@@ -553,7 +558,7 @@ fn test_error_double_settlement() {
     harness
         .env
         .as_contract(&harness.contracts.commitment_core, || {
-            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone())
+            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone(), user.clone())
         });
 
     // Second settlement should fail (commitment not active)
@@ -585,6 +590,7 @@ fn test_boundary_max_duration() {
         early_exit_penalty: 5,
         min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     let commitment_id = harness
@@ -656,6 +662,7 @@ fn test_boundary_max_loss_percent_100() {
         early_exit_penalty: 5,
         min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     let commitment_id = harness
@@ -694,6 +701,7 @@ fn test_boundary_max_loss_percent_0() {
         early_exit_penalty: 0,
         min_fee_threshold: 0,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     let commitment_id = harness
@@ -790,7 +798,7 @@ fn test_error_premature_settlement() {
     harness
         .env
         .as_contract(&harness.contracts.commitment_core, || {
-            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone())
+            CommitmentCoreContract::settle(harness.env.clone(), commitment_id.clone(), user.clone())
         });
 }
 
@@ -13,7 +13,7 @@ use soroban_sdk::{
     Address, Env, String, IntoVal, Symbol,
 };
 
-use commitment_core::{CommitmentCoreContract, CommitmentRules};
+use commitment_core::{CommitmentCoreContract, CommitmentRules, CommitmentStatus, TransferPolicy};
 use commitment_nft::CommitmentNFTContract;
 
 /// Test: Simulate frontend wallet connection and basic interaction
@@ -69,6 +69,7 @@ fn test_frontend_create_commitment_flow() {
         early_exit_penalty: 5,
         min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     // Step 3: Create commitment (frontend transaction submission)
@@ -405,7 +406,7 @@ fn test_frontend_early_exit_flow() {
         });
     assert_eq!(
         commitment.status,
-        String::from_str(&harness.env, "early_exit")
+        CommitmentStatus::EarlyExit
     );
 }
 
@@ -430,6 +431,7 @@ fn test_frontend_commitment_type_rules_display() {
             early_exit_penalty: 5,
             min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
         };
 
         let id = harness
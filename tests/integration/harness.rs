@@ -14,7 +14,7 @@ use soroban_sdk::{
     Address, Env, String, Map,
 };
 
-use commitment_core::{CommitmentCoreContract, CommitmentRules};
+use commitment_core::{CommitmentCoreContract, CommitmentRules, TransferPolicy};
 use commitment_nft::CommitmentNFTContract;
 use attestation_engine::AttestationEngineContract;
 use allocation_logic::{AllocationStrategiesContract, RiskLevel, Strategy};
@@ -274,6 +274,7 @@ impl TestHarness {
             early_exit_penalty: 5,
             min_fee_threshold: 1000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
         }
     }
 
@@ -286,6 +287,7 @@ impl TestHarness {
             early_exit_penalty: 3,
             min_fee_threshold: 500,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
         }
     }
 
@@ -298,6 +300,7 @@ impl TestHarness {
             early_exit_penalty: 10,
             min_fee_threshold: 2000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
         }
     }
 
@@ -4,7 +4,10 @@
 #![cfg(test)]
 
 use attestation_engine::{AttestationEngineContract, AttestationEngineContractClient};
-use commitment_core::{CommitmentCoreContract, CommitmentCoreContractClient, CommitmentRules};
+use commitment_core::{
+    CommitmentCoreContract, CommitmentCoreContractClient, CommitmentRules, CommitmentStatus,
+    TransferPolicy,
+};
 use commitment_nft::{CommitmentNFTContract, CommitmentNFTContractClient};
 use price_oracle::{PriceOracleContract, PriceOracleContractClient};
 use soroban_sdk::{
@@ -72,6 +75,7 @@ impl IntegrationTestFixture {
             early_exit_penalty: 5,
             min_fee_threshold: 100_0000000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
         }
     }
 }
@@ -98,7 +102,7 @@ fn test_create_commitment_with_attestation_flow() {
     let commitment = fixture.core_client.get_commitment(&commitment_id);
     assert_eq!(commitment.owner, fixture.owner);
     assert_eq!(commitment.amount, 1000_0000000);
-    assert_eq!(commitment.status, String::from_str(&fixture.env, "active"));
+    assert_eq!(commitment.status, CommitmentStatus::Active);
 
     // Step 2: Record attestation for the commitment
     let mut data = Map::new(&fixture.env);
@@ -132,6 +136,7 @@ fn test_commitment_value_update_with_health_tracking() {
         early_exit_penalty: 5,
         min_fee_threshold: 100_0000000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     // Create commitment
@@ -145,7 +150,7 @@ fn test_commitment_value_update_with_health_tracking() {
     // Update value in core contract
     fixture
         .core_client
-        .update_value(&commitment_id, &1050_0000000);
+        .update_value(&fixture.admin, &commitment_id, &1050_0000000);
 
     // Record health metrics in attestation engine
     fixture
@@ -164,7 +169,7 @@ fn test_commitment_value_update_with_health_tracking() {
     // Verify commitment status
     let commitment = fixture.core_client.get_commitment(&commitment_id);
     assert_eq!(commitment.current_value, 1050_0000000);
-    assert_eq!(commitment.status, String::from_str(&fixture.env, "active"));
+    assert_eq!(commitment.status, CommitmentStatus::Active);
 }
 
 #[test]
@@ -194,13 +199,13 @@ fn test_settlement_flow_end_to_end() {
     });
 
     // Settle commitment
-    fixture.core_client.settle(&commitment_id);
+    fixture.core_client.settle(&commitment_id, &fixture.owner);
 
     // Verify commitment is settled
     let settled_commitment = fixture.core_client.get_commitment(&commitment_id);
     assert_eq!(
         settled_commitment.status,
-        String::from_str(&fixture.env, "settled")
+        CommitmentStatus::Settled
     );
 }
 
@@ -216,6 +221,7 @@ fn test_early_exit_flow_end_to_end() {
         early_exit_penalty: 10,
         min_fee_threshold: 100_0000000,
             grace_period_days: 0,
+            transfer_policy: TransferPolicy::Free,
     };
 
     // Create commitment
@@ -229,7 +235,7 @@ fn test_early_exit_flow_end_to_end() {
     // Update value
     fixture
         .core_client
-        .update_value(&commitment_id, &1100_0000000);
+        .update_value(&fixture.admin, &commitment_id, &1100_0000000);
 
     // Record attestation for early exit
     let mut data = Map::new(&fixture.env);
@@ -255,7 +261,7 @@ fn test_early_exit_flow_end_to_end() {
     let commitment = fixture.core_client.get_commitment(&commitment_id);
     assert_eq!(
         commitment.status,
-        String::from_str(&fixture.env, "early_exit")
+        CommitmentStatus::EarlyExit
     );
 }
 
@@ -342,13 +348,13 @@ fn test_gas_multiple_operations() {
     // Multiple update operations
     fixture
         .core_client
-        .update_value(&commitment_id, &1010_0000000);
+        .update_value(&fixture.admin, &commitment_id, &1010_0000000);
     fixture
         .core_client
-        .update_value(&commitment_id, &1020_0000000);
+        .update_value(&fixture.admin, &commitment_id, &1020_0000000);
     fixture
         .core_client
-        .update_value(&commitment_id, &1030_0000000);
+        .update_value(&fixture.admin, &commitment_id, &1030_0000000);
 
     // Multiple attestation operations
     fixture